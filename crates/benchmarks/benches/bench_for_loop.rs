@@ -6,7 +6,7 @@ use vm::{Options, VirtualMachine};
 
 criterion_group!(benches, criterion_benchmark);
 criterion_main!(benches);
-const CODE: &'static str = r#"
+const CODE: &str = r#"
 for (let i = 0;i<10000;i = i + 1) {
 
 }
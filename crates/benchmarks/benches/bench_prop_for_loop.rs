@@ -6,7 +6,7 @@ use vm::{Options, VirtualMachine};
 
 criterion_group!(benches, criterion_benchmark);
 criterion_main!(benches);
-const CODE: &'static str = r#"
+const CODE: &str = r#"
 var obj = new Object()
 obj.x = 0
 for (;obj.x < 10000;obj.x = obj.x + 1) {
@@ -49,9 +49,8 @@ macro_rules! _memoffset__raw_const {
 #[doc(hidden)]
 macro_rules! _memoffset__raw_const {
     ($path:expr) => {{
-        // This is UB because we create an intermediate reference to uninitialized memory.
-        // Nothing we can do about that without `raw_const!` though.
-        &$path as *const _
+        // `addr_of!` avoids an intermediate reference to uninitialized or unaligned memory.
+        core::ptr::addr_of!($path)
     }};
 }
 
@@ -115,7 +114,6 @@ macro_rules! raw_field {
 ///
 /// The `base` pointer *must not* be dangling, but it *may* point to
 /// uninitialized memory.
-
 #[macro_export(local_inner_macros)]
 macro_rules! raw_field_tuple {
     ($base:expr, $parent:ty, $field:tt) => {{
@@ -183,7 +181,6 @@ macro_rules! object_offsetof {
 ///     assert!(offset_of_tuple!((u8, u32), 1) >= 0, "Tuples do not have a defined layout");
 /// }
 /// ```
-
 #[macro_export(local_inner_macros)]
 macro_rules! offset_of_tuple {
     ($parent:ty, $field:tt) => {{
@@ -200,7 +197,7 @@ pub const fn bitcount<T>() -> usize {
     size_of::<T>() * 8
 }
 pub const fn divide_rounding_up(numerator: u64, denominator: u64) -> u64 {
-    (numerator + denominator - 1) / denominator
+    numerator.div_ceil(denominator)
 }
 
 pub const fn roundup_to_multiple_of_non_power_of_two(divisor: usize, x: u64) -> u64 {
@@ -211,7 +208,7 @@ pub const fn mask(x: u64, mask: usize) -> u64 {
 }
 
 pub const fn round_up_to_multiple_of_impl(divisor: usize, x: u64) -> u64 {
-    x + (divisor as u64 - 1) & !(divisor as u64 - 1)
+    (x + (divisor as u64 - 1)) & !(divisor as u64 - 1)
 }
 
 pub const fn round_up_to_multiple_of(divisor: usize, x: u64) -> u64 {
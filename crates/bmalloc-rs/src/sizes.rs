@@ -5,7 +5,7 @@ pub const MB: usize = KB * KB;
 pub const GB: usize = KB * KB * KB;
 pub const ALIGNMENT: usize = 8;
 pub const ALIGNMENT_MASK: usize = ALIGNMENT - 1;
-pub const CHUNK_SIZE: usize = 1 * MB;
+pub const CHUNK_SIZE: usize = MB;
 pub const CHUNK_MASK: usize = !(CHUNK_SIZE - 1);
 pub const SMALL_LINE_SIZE: usize = 256;
 pub const SMALL_PAGE_SIZE: usize = 4096;
@@ -4,6 +4,7 @@ use synstructure::{decl_derive, Structure};
 decl_derive!([Trace, attributes(unsafe_ignore_trace)] => derive_trace);
 
 fn derive_trace(mut s: Structure<'_>) -> proc_macro2::TokenStream {
+    s.underscore_const(true);
     s.filter(|bi| {
         !bi.ast()
             .attrs
@@ -34,6 +34,27 @@ pub struct ByteCode {
     pub strict: bool,
     #[unsafe_ignore_trace]
     pub var_names: Vec<Symbol>,
+    /// Set for class constructors, these throw when called without `new`.
+    #[unsafe_ignore_trace]
+    pub class_constructor: bool,
+    /// Set for constructors of classes with an `extends` clause. Their `this` is bound by
+    /// `super(...)`.
+    #[unsafe_ignore_trace]
+    pub derived: bool,
+    /// Set for generator functions, calling these creates a generator object.
+    #[unsafe_ignore_trace]
+    pub generator: bool,
+    /// Set for async functions, calling these returns a promise of the function result.
+    #[unsafe_ignore_trace]
+    pub is_async: bool,
+    /// Set for arrow functions. These take `this` and `arguments` of the enclosing function
+    /// and are not constructors.
+    #[unsafe_ignore_trace]
+    pub is_arrow: bool,
+    /// Set for methods, getters and setters of classes and object literals. These are not
+    /// constructors.
+    #[unsafe_ignore_trace]
+    pub is_method: bool,
 }
 
 impl ByteCode {
@@ -136,7 +157,19 @@ impl ByteCode {
                     Op::OP_SWAP => {
                         writeln!(output, "swap")?;
                     }
+                    Op::OP_SWAP_DROP => {
+                        writeln!(output, "swap_drop")?;
+                    }
+                    Op::OP_ROT => {
+                        writeln!(output, "rot")?;
+                    }
+                    Op::OP_REVERSE => {
+                        let n = pc.cast::<u32>().read_unaligned();
+                        pc = pc.add(4);
+                        writeln!(output, "reverse {}", n)?;
+                    }
                     Op::OP_SPREAD_ARR => writeln!(output, "spread_arr")?,
+                    Op::OP_SPREAD_OBJ => writeln!(output, "spread_obj")?,
                     Op::OP_CALL => {
                         let argc = pc.cast::<u32>().read_unaligned();
                         pc = pc.add(4);
@@ -147,6 +180,14 @@ impl ByteCode {
                         pc = pc.add(4);
                         writeln!(output, "new <{}>", argc)?;
                     }
+                    Op::OP_SUPER_CALL => {
+                        let argc = pc.cast::<u32>().read_unaligned();
+                        pc = pc.add(4);
+                        writeln!(output, "super_call <{}>", argc)?;
+                    }
+                    Op::OP_CREATE_CLASS => {
+                        writeln!(output, "create_class")?;
+                    }
                     Op::OP_RET => {
                         writeln!(output, "ret")?;
                     }
@@ -214,6 +255,9 @@ impl ByteCode {
                     Op::OP_TYPEOF => {
                         writeln!(output, "typeof")?;
                     }
+                    Op::OP_TO_STRING => {
+                        writeln!(output, "to_string")?;
+                    }
                     Op::OP_JMP_FALSE => {
                         let off = pc.cast::<i32>().read_unaligned();
                         pc = pc.add(4);
@@ -241,12 +285,50 @@ impl ByteCode {
                         writeln!(output, "push_scope")?;
                     }
                     Op::OP_SET_GETTER_SETTER => {
-                        writeln!(output, "set_getter_setter")?;
+                        let enumerable = pc.cast::<u32>().read_unaligned();
+                        pc = pc.add(4);
+                        writeln!(output, "set_getter_setter <{}>", enumerable)?;
                     }
                     Op::OP_SET_GETTER_SETTER_BY_ID => {
                         let ix = pc.cast::<u32>().read_unaligned();
                         pc = pc.add(4);
-                        writeln!(output, "set_getter_setter_by_id @{}", ix)?;
+                        let enumerable = pc.cast::<u32>().read_unaligned();
+                        pc = pc.add(4);
+                        writeln!(output, "set_getter_setter_by_id @{} <{}>", ix, enumerable)?;
+                    }
+                    Op::OP_DEFINE_METHOD => {
+                        let enumerable = pc.cast::<u32>().read_unaligned();
+                        pc = pc.add(4);
+                        writeln!(output, "define_method <{}>", enumerable)?;
+                    }
+                    Op::OP_DEFINE_METHOD_BY_ID => {
+                        let ix = pc.cast::<u32>().read_unaligned();
+                        pc = pc.add(4);
+                        let enumerable = pc.cast::<u32>().read_unaligned();
+                        pc = pc.add(4);
+                        writeln!(output, "define_method_by_id @{} <{}>", ix, enumerable)?;
+                    }
+                    Op::OP_DEFINE_PROP => {
+                        writeln!(output, "define_prop")?;
+                    }
+                    Op::OP_SET_PROTO => {
+                        writeln!(output, "set_proto")?;
+                    }
+                    Op::OP_GET_SUPER => {
+                        writeln!(output, "get_super")?;
+                    }
+                    Op::OP_GET_SUPER_PROP => {
+                        let ix = pc.cast::<u32>().read_unaligned();
+                        pc = pc.add(4);
+                        writeln!(output, "get_super_prop @{}", ix)?;
+                    }
+                    Op::OP_SET_SUPER => {
+                        writeln!(output, "set_super")?;
+                    }
+                    Op::OP_SET_SUPER_PROP => {
+                        let ix = pc.cast::<u32>().read_unaligned();
+                        pc = pc.add(4);
+                        writeln!(output, "set_super_prop @{}", ix)?;
                     }
                     Op::OP_POP_SCOPE => {
                         writeln!(output, "pop_scope")?;
@@ -261,6 +343,19 @@ impl ByteCode {
                             (pc as usize - start as usize) as i32 + off
                         )?;
                     }
+                    Op::OP_TRY_PUSH_FINALLY => {
+                        let off = pc.cast::<i32>().read_unaligned();
+                        pc = pc.add(4);
+                        writeln!(
+                            output,
+                            "try_push_finally {}[->{}]",
+                            off,
+                            (pc as usize - start as usize) as i32 + off
+                        )?;
+                    }
+                    Op::OP_TRY_POP => {
+                        writeln!(output, "try_pop")?;
+                    }
                     Op::OP_DECL_LET => {
                         let name = pc.cast::<u32>().read_unaligned();
                         pc = pc.add(4);
@@ -288,6 +383,48 @@ impl ByteCode {
                     Op::OP_IN => {
                         writeln!(output, "in")?;
                     }
+                    Op::OP_INSTANCEOF => {
+                        writeln!(output, "instanceof")?;
+                    }
+                    Op::OP_NEXT_PROP => {
+                        writeln!(output, "next_prop")?;
+                    }
+                    Op::OP_GET_ITERATOR => {
+                        writeln!(output, "get_iterator")?;
+                    }
+                    Op::OP_ITERATOR_NEXT => {
+                        writeln!(output, "iterator_next")?;
+                    }
+                    Op::OP_ITERATOR_CLOSE => {
+                        writeln!(output, "iterator_close")?;
+                    }
+                    Op::OP_ITERATOR_CLOSE_THROW => {
+                        writeln!(output, "iterator_close_throw")?;
+                    }
+                    Op::OP_ITERATOR_VALUE => {
+                        writeln!(output, "iterator_value")?;
+                    }
+                    Op::OP_ITERATOR_REST => {
+                        writeln!(output, "iterator_rest")?;
+                    }
+                    Op::OP_REQUIRE_OBJECT_COERCIBLE => {
+                        writeln!(output, "require_object_coercible")?;
+                    }
+                    Op::OP_GET_AND_EXCLUDE => {
+                        writeln!(output, "get_and_exclude")?;
+                    }
+                    Op::OP_OBJECT_REST => {
+                        writeln!(output, "object_rest")?;
+                    }
+                    Op::OP_YIELD => {
+                        writeln!(output, "yield")?;
+                    }
+                    Op::OP_YIELD_STAR => {
+                        writeln!(output, "yield_star")?;
+                    }
+                    Op::OP_AWAIT => {
+                        writeln!(output, "await")?;
+                    }
                     _ => todo!("{:?}", op),
                 }
             }
@@ -308,6 +445,12 @@ impl ByteCode {
             names: vec![],
             params: Vec::from(params),
             strict,
+            class_constructor: false,
+            derived: false,
+            generator: false,
+            is_async: false,
+            is_arrow: false,
+            is_method: false,
         })
     }
 }
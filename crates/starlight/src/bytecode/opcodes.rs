@@ -50,6 +50,17 @@ pub enum Op {
     /// `( a b -- b )`
     OP_SWAP_DROP,
 
+    /// Rotates the three items on top of the stack, bringing the third one to the top.
+    ///
+    /// `( a b c -- b c a )`
+    OP_ROT,
+
+    /// Takes a varint argument `n` and reverses the order of the `n` items on top of the stack.
+    /// Used to pass values evaluated left to right as call arguments.
+    ///
+    /// `( a1 ... an -- an ... a1 )`
+    OP_REVERSE,
+
     /// Pushes `undefined` onto the stack.
     ///
     /// `( -- undefined )`
@@ -107,6 +118,12 @@ pub enum Op {
     ///
     /// `( a -- Number(a) )`
     OP_POS,
+
+    /// Takes a value from the top of the stack and pushes the result of ToString on it.
+    /// Used for substitutions of template literals.
+    ///
+    /// `( a -- ToString(a) )`
+    OP_TO_STRING,
     OP_TYPEOF,
     OP_NOP,
     /// Takes 2 values from the top of the stack and performs addition operation:
@@ -167,9 +184,73 @@ pub enum Op {
     OP_GET_PROP,
     OP_SET_PROP,
 
+    /// Takes two varint arguments -- index of the property name in the literals table and
+    /// a flag telling whether the property is enumerable. Defines an accessor property with
+    /// getter `g` and setter `s` on object `o`. An `undefined` getter or setter keeps the
+    /// one the property already has, so `get` and `set` members of the same name combine.
+    ///
+    /// `( g s o -- )`
     OP_SET_GETTER_SETTER_BY_ID,
+
+    /// Like `OP_SET_GETTER_SETTER_BY_ID` but takes the property name `p` from the stack and
+    /// sets the `name` of the getter or the setter from it. Takes one varint argument -- the
+    /// enumerable flag.
+    ///
+    /// `( g s p o -- )`
     OP_SET_GETTER_SETTER,
 
+    /// Takes two varint arguments -- index of the method name in the literals table and
+    /// a flag telling whether the property is enumerable. Defines method `f` as a writable
+    /// and configurable property of object `o`.
+    ///
+    /// `( f o -- )`
+    OP_DEFINE_METHOD_BY_ID,
+
+    /// Like `OP_DEFINE_METHOD_BY_ID` but takes the method name `p` from the stack and
+    /// sets the `name` of `f` from it. Takes one varint argument -- the enumerable flag.
+    ///
+    /// `( f p o -- )`
+    OP_DEFINE_METHOD,
+
+    /// Defines property `p` of object `o` as an enumerable, writable and configurable data
+    /// property with value `v`. Used for computed keys of object literals.
+    ///
+    /// `( o p v -- )`
+    OP_DEFINE_PROP,
+
+    /// Sets the prototype of object `o` to `p` if `p` is an object or `null`, otherwise
+    /// does nothing. Used for `__proto__: p` members of object literals.
+    ///
+    /// `( o p -- )`
+    OP_SET_PROTO,
+
+    /// Takes a varint argument -- index of the property name in the literals table. Looks
+    /// the property up starting from the prototype of home object `h`, getters receive the
+    /// current `this`. Used for `super.name`.
+    ///
+    /// `( h -- a )`
+    OP_GET_SUPER_PROP,
+
+    /// Like `OP_GET_SUPER_PROP` but takes the property name `p` from the stack. Used for
+    /// `super[p]`.
+    ///
+    /// `( p h -- a )`
+    OP_GET_SUPER,
+
+    /// Takes a varint argument -- index of the property name in the literals table. Assigns
+    /// `a` to the property as if it was looked up starting from the prototype of home object
+    /// `h`: setters found there receive the current `this`, otherwise the property is created
+    /// or updated on `this`. Used for `super.name = a`.
+    ///
+    /// `( a h -- )`
+    OP_SET_SUPER_PROP,
+
+    /// Like `OP_SET_SUPER_PROP` but takes the property name `p` from the stack. Used for
+    /// `super[p] = a`.
+    ///
+    /// `( a p h -- )`
+    OP_SET_SUPER,
+
     /// Takes 1 value from the stack and a varint argument -- index of the var name
     /// in the literals table. Tries to find the variable in the current scope
     /// chain and assign the value to it. If the variable is not found -- creates
@@ -243,7 +324,19 @@ pub enum Op {
     /// `( -- [] )`
     OP_CREATE_ARR,
     OP_CREATE_ARRN,
+
+    /// Replaces `it` with the list of values produced by iterating it. Used for spread
+    /// elements of array literals and spread arguments of calls, `OP_CREATE_ARRN`, `OP_CALL`,
+    /// `OP_NEW` and `OP_SUPER_CALL` expand the list in place.
+    ///
+    /// `( it -- [v0 ... vN] )`
     OP_SPREAD_ARR,
+
+    /// Copies own enumerable properties of `s` to object `o`. Nothing is copied if `s` is
+    /// `undefined` or `null`. Used for spread members of object literals.
+    ///
+    /// `( o s -- )`
+    OP_SPREAD_OBJ,
     /// Yields the next property name.
     /// Used in the for..in construct.
     ///
@@ -259,15 +352,100 @@ pub enum Op {
     /// `( o h -- false)`
     OP_NEXT_PROP,
 
-    /// Takes the number of arguments as parameter.
+    /// Gets an iterator from the iterable `o`, see `GetIterator` in the spec.
+    /// Pushes the iterator `i` and its `next` method `n`.
+    /// Used in the for..of construct.
+    ///
+    /// `( o -- i n )`
+    OP_GET_ITERATOR,
+
+    /// Calls the `next` method of the iterator. Used in the for..of construct.
+    /// Evaluation will either:
+    ///
+    /// a) produce the iterator, the next value and true value:
     ///
-    /// Pops N function arguments from stack, then pops function, then pops `this`.
+    /// `( i n -- i n v true )`
+    ///
+    /// b) produce a false value only, indicating the iterator is done:
+    ///
+    /// `( i n -- false )`
+    OP_ITERATOR_NEXT,
+
+    /// Closes the iterator by calling its `return` method if there is one.
+    /// Used when `break` leaves the for..of construct. An iterator whose `next`
+    /// method is empty is done and is not closed.
+    ///
+    /// `( i n -- )`
+    OP_ITERATOR_CLOSE,
+
+    /// Closes the iterator ignoring errors thrown by its `return` method and
+    /// rethrows `e`. Used when an exception leaves the for..of construct.
+    ///
+    /// `( i n e -- )`
+    OP_ITERATOR_CLOSE_THROW,
+
+    /// Pushes the next value of the iterator, or `undefined` once it is done.
+    /// Used by array destructuring. `next` method is replaced with empty value
+    /// once the iterator is done or throws.
+    ///
+    /// `( i n -- i n' v )`
+    OP_ITERATOR_VALUE,
+
+    /// Collects the remaining values of the iterator into a new array. Used by
+    /// rest elements of array destructuring.
+    ///
+    /// `( i n -- i empty a )`
+    OP_ITERATOR_REST,
+
+    /// Throws a TypeError if `v` is `undefined` or `null`. Used by object
+    /// destructuring.
+    ///
+    /// `( v -- v )`
+    OP_REQUIRE_OBJECT_COERCIBLE,
+
+    /// Gets property `k` of `o` and appends the property key to the array of
+    /// excluded keys `ex`. Used by object destructuring with a rest element.
+    ///
+    /// `( o ex k -- o ex o[k] )`
+    OP_GET_AND_EXCLUDE,
+
+    /// Creates a new object with own enumerable properties of `o` except the
+    /// ones in the array of excluded keys `ex`.
+    ///
+    /// `( o ex -- r )`
+    OP_OBJECT_REST,
+
     /// Calls a function and populates TOS with the returned value.
     ///
-    /// `( this f a0 a1 ... aN -- f(a0,a1,...) )`
+    /// Pops arguments down to the empty value pushed before them, the first argument is on
+    /// top of the stack. Then pops the function and then `this`, an empty `this` is replaced
+    /// by `undefined`. The parameter is the number of argument expressions which may differ
+    /// from the number of arguments when spread arguments are used.
+    ///
+    /// `( this f empty aN ... a1 a0 -- f(a0,a1,...) )`
     OP_CALL,
+
+    /// Same as `OP_CALL` but calls the function as a constructor.
+    ///
+    /// `( this f empty aN ... a1 a0 -- new f(a0,a1,...) )`
     OP_NEW,
 
+    /// Takes the number of arguments as parameter.
+    ///
+    /// Pops class constructor `ctor`, then pops arguments down to the empty value like
+    /// `OP_CALL`. Calls the prototype of `ctor` as a constructor and binds the result to
+    /// `this` of the current derived constructor. Used for `super(...)`.
+    ///
+    /// `( empty aN ... a1 a0 ctor -- this )`
+    OP_SUPER_CALL,
+
+    /// Sets up class constructor `ctor`: makes its `prototype` property read-only and, if
+    /// heritage `h` is not empty, links `ctor` and its prototype object to the parent class.
+    /// `h` is empty when the class has no `extends` clause.
+    ///
+    /// `( h ctor -- ctor proto )`
+    OP_CREATE_CLASS,
+
     /// Checks that TOS is a callable and if not saves an exception
     /// that will will be thrown by CALL after all arguments have been evaluated.
     OP_CHECK_CALL,
@@ -281,6 +459,24 @@ pub enum Op {
     /// `( -- )`
     OP_RET,
 
+    /// Suspends the current generator frame. Pops value `v` and yields it to the caller of
+    /// `next`. When the generator is resumed by `next`, the value `r` passed to it is pushed.
+    ///
+    /// `( v -- r )`
+    OP_YIELD,
+
+    /// Suspends the current generator frame and delegates to the iterator of `o` until it is
+    /// done. Pushes the value the iterator completed with.
+    ///
+    /// `( o -- r )`
+    OP_YIELD_STAR,
+
+    /// Suspends the current async function frame until the promise of `v` is settled.
+    /// Pushes the fulfillment value `r`, or throws the rejection reason.
+    ///
+    /// `( v -- r )`
+    OP_AWAIT,
+
     /// Deletes the property of given name `p` from the given object `o`. Returns
     /// boolean value `a`.
     ///
@@ -308,7 +504,8 @@ pub enum Op {
     ///
     /// `( A: a -- T: a )`
     ///
-    /// TODO: implement me
+    /// Unlike `catch` blocks, `finally` blocks also run when a generator is closed by its
+    /// `return` method, the thrown value is then empty.
     OP_TRY_PUSH_FINALLY,
 
     /// Pops a value (bcode offset of `finally` or `catch` block) from "try
@@ -1,6 +1,9 @@
 use hashbrown::HashMap;
 use scope_analyzer::{Scope, VisitFnDecl};
-use swc_ecmascript::{ast::*, utils::IsDirective};
+use swc_ecmascript::{
+    ast::*,
+    utils::{find_ids, Id, IsDirective},
+};
 use wtf_rs::pure_nan;
 
 use crate::{
@@ -8,7 +11,11 @@ use crate::{
     bytecode::*,
     gc::cell::{Gc, Trace, Tracer},
     gc::handle::Handle,
-    runtime::symbol::Symbol,
+    interpreter::frame::BlockType,
+    runtime::{
+        array::JsArray, attributes::*, error::JsSyntaxError, object::JsObject,
+        property_descriptor::DataDescriptor, string::JsString, symbol::Symbol, value::JsValue,
+    },
     vm::VirtualMachineRef,
 };
 
@@ -16,17 +23,39 @@ pub mod scope_analyzer;
 pub struct LoopControlInfo {
     breaks: Vec<Box<dyn FnOnce(&mut Compiler)>>,
     continues: Vec<Box<dyn FnOnce(&mut Compiler)>>,
+    ty: BlockType,
+    /// Labels attached to this block by labelled statements.
+    labels: Vec<Symbol>,
+    /// Scope depth at which `break` and `continue` targets of this block are placed.
+    scope_depth: u32,
+    /// Number of values this block keeps on the operand stack while its body runs.
+    values: u32,
+    /// Code that discards values this block keeps on the operand stack while its body runs
+    /// (e.g. the discriminant of `switch`). Emitted when control jumps out of the block.
+    exit: &'static [Op],
+    /// Number of `try` handlers active at `break` and `continue` targets of this block.
+    try_depth: u32,
+    /// `finally` block that is run when control jumps out of a `try` statement.
+    finalizer: Option<BlockStmt>,
 }
 pub struct Compiler {
     builder: ByteCodeBuilder,
     vm: VirtualMachineRef,
     lci: Vec<LoopControlInfo>,
     fmap: HashMap<Symbol, u32>,
+    scope_depth: u32,
+    try_depth: u32,
+    /// Labels of the labelled statement being compiled, taken by the next `push_lci`.
+    labels: Vec<Symbol>,
+    /// Binding of the class scope holding the home object `super.name` is looked up from,
+    /// `None` outside of class methods.
+    home: Option<Symbol>,
+    /// First early error found in the code, `compile_script` throws it as a `SyntaxError`.
+    error: Option<String>,
 }
 impl Compiler {
     pub fn intern_str(&mut self, s: &str) -> Symbol {
-        let interned = self.vm.intern_or_known_symbol(s);
-        interned
+        self.vm.intern_or_known_symbol(s)
     }
     pub fn intern(&mut self, id: &Ident) -> Symbol {
         let s: &str = &id.sym;
@@ -39,7 +68,7 @@ impl Compiler {
         self.builder.get_sym(interned)
     }
 
-    pub fn compile_script(mut vm: VirtualMachineRef, p: &Script) -> Gc<ByteCode> {
+    pub fn compile_script(mut vm: VirtualMachineRef, p: &Script) -> Result<Gc<ByteCode>, JsValue> {
         let name = vm.intern_or_known_symbol("<global>");
         let code = ByteCode::new(&mut vm, name, &[], false);
         let mut code = Handle::new(vm.space(), code);
@@ -51,18 +80,54 @@ impl Compiler {
                 name_map: Default::default(),
             },
             fmap: Default::default(),
-            vm: vm,
+            vm,
+            scope_depth: 0,
+            try_depth: 0,
+            labels: vec![],
+            home: None,
+            error: None,
         };
 
-        let is_strict = match p.body.get(0) {
-            Some(ref body) => body.is_use_strict(),
+        let is_strict = match p.body.first() {
+            Some(body) => body.is_use_strict(),
             None => false,
         };
         code.strict = is_strict;
         compiler.compile(&p.body);
         compiler.builder.emit(Op::OP_PUSH_UNDEFINED, &[], false);
         compiler.builder.emit(Op::OP_RET, &[], false);
-        compiler.builder.finish(&mut compiler.vm)
+        let code = compiler.builder.finish(&mut compiler.vm);
+        match compiler.error {
+            Some(msg) => {
+                let msg = JsString::new(&mut vm, msg).root(vm.space());
+                Err(JsValue::new(JsSyntaxError::new(&mut vm, *msg, None)))
+            }
+            None => Ok(code),
+        }
+    }
+
+    /// Mark `code` as the code of a generator or an async function.
+    fn set_function_kind(&mut self, mut code: Gc<ByteCode>, is_generator: bool, is_async: bool) {
+        if is_generator && is_async {
+            self.syntax_error("async generators are not supported");
+            return;
+        }
+        code.generator = is_generator;
+        code.is_async = is_async;
+    }
+
+    /// Record early error `msg`, only the first one is reported.
+    fn syntax_error(&mut self, msg: &str) {
+        if self.error.is_none() {
+            self.error = Some(msg.to_string());
+        }
+    }
+
+    /// Keep the first early error of nested function compiler `compiler`.
+    fn take_error(&mut self, compiler: Compiler) {
+        if self.error.is_none() {
+            self.error = compiler.error;
+        }
     }
     pub fn compile_fn(&mut self, fun: &Function) {
         let is_strict = match fun.body {
@@ -87,28 +152,23 @@ impl Compiler {
         self.builder.finish(&mut self.vm);
     }
     pub fn compile(&mut self, body: &[Stmt]) {
+        self.declare_lexical_bindings(body);
         let mut i = 0;
         VisitFnDecl::visit(body, &mut |decl| {
             if true {
                 let name = self.intern(&decl.ident);
-                let mut rest = None;
-                let mut params = vec![];
-                for x in decl.function.params.iter() {
-                    match x.pat {
-                        Pat::Ident(ref x) => params.push(self.intern(&x.id)),
-                        Pat::Rest(ref r) => match &*r.arg {
-                            Pat::Ident(ref id) => {
-                                rest = Some(self.intern(&id.id));
-                            }
-                            _ => unreachable!(),
-                        },
-                        _ => todo!(),
-                    }
-                }
+                let pats = decl
+                    .function
+                    .params
+                    .iter()
+                    .map(|param| &param.pat)
+                    .collect::<Vec<_>>();
+                let (params, rest, patterns) = self.function_params(&pats);
 
                 let code = ByteCode::new(&mut self.vm, name, &params, false);
                 let mut code = Handle::new(self.vm.space(), code);
                 code.rest_param = rest;
+                self.set_function_kind(*code, decl.function.is_generator, decl.function.is_async);
                 let mut compiler = Compiler {
                     lci: Vec::new(),
                     builder: ByteCodeBuilder {
@@ -118,9 +178,16 @@ impl Compiler {
                     },
                     fmap: Default::default(),
                     vm: self.vm,
+                    scope_depth: 0,
+                    try_depth: 0,
+                    labels: vec![],
+                    home: None,
+                    error: None,
                 };
 
+                compiler.emit_param_patterns(&patterns);
                 compiler.compile_fn(&decl.function);
+                self.take_error(compiler);
                 let ix = self.builder.code.codes.len();
                 self.builder.code.codes.push(*code);
                 self.fmap.insert(name, ix as _);
@@ -159,6 +226,9 @@ impl Compiler {
 
     pub fn emit(&mut self, expr: &Expr, used: bool) {
         match expr {
+            Expr::OptChain(_) | Expr::Member(_) | Expr::Call(_) if has_optional(expr) => {
+                self.emit_opt_chain(expr, used);
+            }
             Expr::Arrow(fun) => {
                 let is_strict = match &fun.body {
                     BlockStmtOrExpr::BlockStmt(block) => {
@@ -171,8 +241,11 @@ impl Compiler {
                     _ => false,
                 };
                 let name = self.vm.intern_or_known_symbol("<anonymous>");
-                let code = ByteCode::new(&mut self.vm, name, &[], false);
+                let pats = fun.params.iter().collect::<Vec<_>>();
+                let (params, rest, patterns) = self.function_params(&pats);
+                let code = ByteCode::new(&mut self.vm, name, &params, false);
                 let mut code = Handle::new(self.vm.space(), code);
+                code.rest_param = rest;
                 let mut compiler = Compiler {
                     lci: Vec::new(),
                     builder: ByteCodeBuilder {
@@ -182,22 +255,16 @@ impl Compiler {
                     },
                     fmap: Default::default(),
                     vm: self.vm,
+                    scope_depth: 0,
+                    try_depth: 0,
+                    labels: vec![],
+                    home: self.home,
+                    error: None,
                 };
                 code.strict = is_strict;
-                for param in fun.params.iter() {
-                    match param {
-                        Pat::Ident(ref ident) => {
-                            code.params.push(compiler.intern(&ident.id));
-                        }
-                        Pat::Rest(restpat) => match *restpat.arg {
-                            Pat::Ident(ref id) => {
-                                code.rest_param = Some(compiler.intern(&id.id));
-                            }
-                            _ => unreachable!(),
-                        },
-                        p => todo!("arrow param {:?}", p),
-                    }
-                }
+                code.is_arrow = true;
+                self.set_function_kind(*code, fun.is_generator, fun.is_async);
+                compiler.emit_param_patterns(&patterns);
                 match &fun.body {
                     BlockStmtOrExpr::BlockStmt(block) => {
                         compiler.compile(&block.stmts);
@@ -210,36 +277,33 @@ impl Compiler {
                     }
                 }
                 let code = compiler.builder.finish(&mut self.vm);
+                self.take_error(compiler);
                 let ix = self.builder.code.codes.len();
                 self.builder.code.codes.push(code);
-                let nix = self.builder.get_sym(name);
                 self.builder.emit(Op::OP_GET_FUNCTION, &[ix as _], false);
+                if !used {
+                    self.builder.emit(Op::OP_DROP, &[], false);
+                }
             }
             Expr::Fn(fun) => {
-                self.builder.emit(Op::OP_PUSH_SCOPE, &[], false);
+                self.push_scope();
                 let name = fun
                     .ident
                     .as_ref()
                     .map(|x| self.intern(x))
                     .unwrap_or_else(|| self.vm.intern("<anonymous>"));
-                let mut rest = None;
-                let mut params = vec![];
-                for x in fun.function.params.iter() {
-                    match x.pat {
-                        Pat::Ident(ref x) => params.push(self.intern(&x.id)),
-                        Pat::Rest(ref r) => match &*r.arg {
-                            Pat::Ident(ref id) => {
-                                rest = Some(self.intern(&id.id));
-                            }
-                            _ => unreachable!(),
-                        },
-                        _ => todo!(),
-                    }
-                }
+                let pats = fun
+                    .function
+                    .params
+                    .iter()
+                    .map(|param| &param.pat)
+                    .collect::<Vec<_>>();
+                let (params, rest, patterns) = self.function_params(&pats);
 
                 let code = ByteCode::new(&mut self.vm, name, &params, false);
                 let mut code = Handle::new(self.vm.space(), code);
                 code.rest_param = rest;
+                self.set_function_kind(*code, fun.function.is_generator, fun.function.is_async);
                 let mut compiler = Compiler {
                     lci: Vec::new(),
                     builder: ByteCodeBuilder {
@@ -249,16 +313,23 @@ impl Compiler {
                     },
                     fmap: Default::default(),
                     vm: self.vm,
+                    scope_depth: 0,
+                    try_depth: 0,
+                    labels: vec![],
+                    home: None,
+                    error: None,
                 };
 
+                compiler.emit_param_patterns(&patterns);
                 compiler.compile_fn(&fun.function);
+                self.take_error(compiler);
                 let ix = self.builder.code.codes.len();
                 self.builder.code.codes.push(*code);
                 let nix = self.builder.get_sym(name);
                 self.builder.emit(Op::OP_GET_FUNCTION, &[ix as _], false);
                 self.builder.emit(Op::OP_DUP, &[], false);
                 self.builder.emit(Op::OP_SET_VAR, &[nix as _], true);
-                self.builder.emit(Op::OP_POP_SCOPE, &[], false);
+                self.pop_scope();
             }
             Expr::This(_) => {
                 if used {
@@ -286,66 +357,75 @@ impl Compiler {
             }
 
             Expr::Call(call) => {
-                self.builder.emit(Op::OP_PUSH_EMPTY, &[], false);
-                for arg in call.args.iter().rev() {
-                    self.emit(&arg.expr, true);
-                    if arg.spread.is_some() {
-                        self.builder.emit(Op::OP_SPREAD_ARR, &[], false);
-                    }
-                }
-
                 match call.callee {
-                    ExprOrSuper::Super(_) => todo!(), // todo super call
-                    ExprOrSuper::Expr(ref expr) => match &**expr {
-                        Expr::Member(member) => {
-                            let name = if let Expr::Ident(id) = &*member.prop {
-                                let s: &str = &id.sym;
-                                let name = self.intern_str(s);
-                                self.builder.get_sym(name)
-                            } else {
-                                unreachable!()
-                            };
-                            match member.obj {
-                                ExprOrSuper::Expr(ref expr) => {
-                                    self.emit(expr, true);
-                                    self.builder.emit(Op::OP_DUP, &[], false);
-                                }
-                                ExprOrSuper::Super(_super) => {
-                                    todo!()
-                                }
-                            }
-
-                            self.builder.emit(Op::OP_GET_PROP, &[name], true);
-                        }
-                        _ => {
-                            self.builder.emit(Op::OP_PUSH_EMPTY, &[], false);
-                            self.emit(&**expr, true);
+                    ExprOrSuper::Super(_) => {
+                        self.emit_args(&call.args);
+                        let ix = self.builder.get_sym(Symbol::class_constructor());
+                        self.builder.emit(Op::OP_GET_VAR, &[ix], true);
+                        self.builder
+                            .emit(Op::OP_SUPER_CALL, &[call.args.len() as u32], false);
+                        if !used {
+                            self.builder.emit(Op::OP_DROP, &[], false);
                         }
-                    },
+                        return;
+                    }
+                    ExprOrSuper::Expr(ref expr) => self.emit_callee(expr),
                 }
-
+                self.emit_args(&call.args);
                 self.builder
                     .emit(Op::OP_CALL, &[call.args.len() as u32], false);
                 if !used {
                     self.builder.emit(Op::OP_DROP, &[], false);
                 }
             }
-            Expr::New(call) => {
-                self.builder.emit(Op::OP_PUSH_EMPTY, &[], false);
-                let argc = call.args.as_ref().map(|x| x.len() as u32).unwrap_or(0);
-                if let Some(ref args) = call.args {
-                    for arg in args.iter().rev() {
-                        self.emit(&arg.expr, true);
-                        if arg.spread.is_some() {
-                            self.builder.emit(Op::OP_SPREAD_ARR, &[], false);
+            Expr::Tpl(tpl) => {
+                let mut exprs = tpl.exprs.iter();
+                for (ix, quasi) in tpl.quasis.iter().enumerate() {
+                    let cooked = quasi.cooked.as_ref().map(|x| &x.value[..]).unwrap_or("");
+                    if ix == 0 || !cooked.is_empty() {
+                        let mut vm = self.vm;
+                        let lit = self.builder.get_val(&mut vm, Val::Str(cooked.to_string()));
+                        self.builder.emit(Op::OP_PUSH_LIT, &[lit], false);
+                        if ix != 0 {
+                            self.builder.emit(Op::OP_ADD, &[], false);
                         }
                     }
+                    if let Some(expr) = exprs.next() {
+                        self.emit(expr, true);
+                        self.builder.emit(Op::OP_TO_STRING, &[], false);
+                        self.builder.emit(Op::OP_ADD, &[], false);
+                    }
                 }
-
+                if !used {
+                    self.builder.emit(Op::OP_DROP, &[], false);
+                }
+            }
+            Expr::TaggedTpl(tagged) => {
+                self.emit_callee(&tagged.tag);
+                self.builder.emit(Op::OP_PUSH_EMPTY, &[], false);
+                // substitutions are evaluated left to right, the call takes its first
+                // argument from the top of the stack.
+                for expr in tagged.exprs.iter() {
+                    self.emit(expr, true);
+                }
+                if tagged.exprs.len() > 1 {
+                    self.builder
+                        .emit(Op::OP_REVERSE, &[tagged.exprs.len() as u32], false);
+                }
+                let ix = self.template_object(&tagged.quasis);
+                self.builder.emit(Op::OP_PUSH_LIT, &[ix], false);
+                self.builder
+                    .emit(Op::OP_CALL, &[tagged.exprs.len() as u32 + 1], false);
+                if !used {
+                    self.builder.emit(Op::OP_DROP, &[], false);
+                }
+            }
+            Expr::New(call) => {
                 self.builder.emit(Op::OP_PUSH_EMPTY, &[], false);
                 self.emit(&*call.callee, true);
-
-                self.builder.emit(Op::OP_NEW, &[argc], false);
+                let args = call.args.as_deref().unwrap_or(&[]);
+                self.emit_args(args);
+                self.builder.emit(Op::OP_NEW, &[args.len() as u32], false);
                 if !used {
                     self.builder.emit(Op::OP_DROP, &[], false);
                 }
@@ -359,6 +439,10 @@ impl Compiler {
             Expr::Ident(name) => {
                 let s: &str = &name.sym;
                 let name = self.intern_str(s);
+                if !used && [Symbol::undefined(), Symbol::NaN(), Symbol::Infinity()].contains(&name)
+                {
+                    return;
+                }
                 if name == Symbol::undefined() {
                     self.builder.emit(Op::OP_PUSH_UNDEFINED, &[], false)
                 } else if name == Symbol::NaN() {
@@ -369,19 +453,22 @@ impl Compiler {
                 } else if name == Symbol::Infinity() {
                     let ix = self
                         .builder
-                        .get_val(&mut self.vm, Val::Float(std::f64::INFINITY.to_bits()));
+                        .get_val(&mut self.vm, Val::Float(f64::INFINITY.to_bits()));
                     self.builder.emit(Op::OP_PUSH_LIT, &[ix], false);
                 } else {
                     assert!(self.vm.description(name) != "undefined");
+                    // unused reads are still performed, they throw for bindings in their TDZ
+                    // and for unresolvable references.
                     let ix = self.builder.get_sym(name);
-                    if used {
-                        self.builder.emit(Op::OP_GET_VAR, &[ix], true);
+                    self.builder.emit(Op::OP_GET_VAR, &[ix], true);
+                    if !used {
+                        self.builder.emit(Op::OP_DROP, &[], false);
                     }
                 }
             }
 
             Expr::Member(member) => {
-                let name = if let Expr::Ident(id) = &*member.prop {
+                let name = if let (Expr::Ident(id), false) = (&*member.prop, member.computed) {
                     let s: &str = &id.sym;
                     let name = self.vm.intern_or_known_symbol(s);
                     Some(self.builder.get_sym(name))
@@ -389,24 +476,33 @@ impl Compiler {
                     self.emit(&member.prop, true);
                     None
                 };
-                match member.obj {
+                let is_super = match member.obj {
                     ExprOrSuper::Expr(ref expr) => {
                         self.emit(expr, true);
+                        false
                     }
-                    ExprOrSuper::Super(_super) => {
-                        todo!()
+                    ExprOrSuper::Super(_) => {
+                        self.emit_home_object();
+                        true
                     }
-                }
+                };
 
-                if let Some(ix) = name {
-                    self.builder.emit(Op::OP_GET_PROP, &[ix], true);
-                } else {
-                    self.builder.emit(Op::OP_GET, &[], false);
+                match name {
+                    Some(ix) if is_super => self.builder.emit(Op::OP_GET_SUPER_PROP, &[ix], false),
+                    Some(ix) => self.builder.emit(Op::OP_GET_PROP, &[ix], true),
+                    None if is_super => self.builder.emit(Op::OP_GET_SUPER, &[], false),
+                    None => self.builder.emit(Op::OP_GET, &[], false),
                 }
                 if !used {
                     self.builder.emit(Op::OP_DROP, &[], false);
                 }
             }
+            Expr::Class(class) => {
+                self.emit_class(&class.class, class.ident.as_ref());
+                if !used {
+                    self.builder.emit(Op::OP_DROP, &[], false);
+                }
+            }
             Expr::Unary(unary) => {
                 self.emit(&unary.arg, true);
                 match unary.op {
@@ -424,69 +520,60 @@ impl Compiler {
 
             Expr::Object(object_lit) => {
                 self.builder.emit(Op::OP_CREATE_OBJ, &[], false);
+                // methods and accessors find the object through the hidden home object binding
+                let has_methods = object_lit.props.iter().any(|prop| match prop {
+                    PropOrSpread::Prop(prop) => {
+                        matches!(**prop, Prop::Method(_) | Prop::Getter(_) | Prop::Setter(_))
+                    }
+                    PropOrSpread::Spread(_) => false,
+                });
+                if has_methods {
+                    self.push_scope();
+                    self.builder.emit(Op::OP_DUP, &[], false);
+                    let home = self.builder.get_sym(Symbol::home_object());
+                    self.builder.emit(Op::OP_DECL_IMMUTABLE, &[home], true);
+                }
                 for prop in object_lit.props.iter() {
                     match prop {
-                        PropOrSpread::Prop(prop) => match &**prop {
-                            Prop::Shorthand(ident) => {
-                                self.builder.emit(Op::OP_DUP, &[], false);
-                                let ix = self.intern(ident);
-                                let sym = self.builder.get_sym(ix);
-                                self.builder.emit(Op::OP_GET_VAR, &[sym], true);
-                                self.builder.emit(Op::OP_SWAP, &[], false);
-                                self.builder.emit(Op::OP_SET_PROP, &[sym], true);
-                            }
-                            Prop::KeyValue(assign) => {
-                                self.builder.emit(Op::OP_DUP, &[], false);
-                                self.emit(&assign.value, true);
-                                match assign.key {
-                                    PropName::Ident(ref id) => {
-                                        let ix = self.intern(id);
-                                        let sym = self.builder.get_sym(ix);
-                                        self.builder.emit(Op::OP_SWAP, &[], false);
-                                        self.builder.emit(Op::OP_SET_PROP, &[sym], true);
-                                    }
-                                    PropName::Str(ref s) => {
-                                        let ix = self
-                                            .builder
-                                            .get_val(&mut self.vm, Val::Str(s.value.to_string()));
-                                        self.builder.emit(Op::OP_SWAP, &[], false);
-                                        self.builder.emit(Op::OP_PUSH_LIT, &[ix], false);
-                                        self.builder.emit(Op::OP_SWAP, &[], false);
-                                        self.builder.emit(Op::OP_SET, &[], false);
-                                    }
-                                    PropName::Num(n) => {
-                                        let val = n.value;
-                                        if val as i32 as f64 == val {
-                                            self.builder.emit(Op::OP_SWAP, &[], false);
-                                            self.builder.emit(
-                                                Op::OP_PUSH_INT,
-                                                &[val as i32 as u32],
-                                                false,
-                                            );
-                                            self.builder.emit(Op::OP_SWAP, &[], false);
-                                            self.builder.emit(Op::OP_SET, &[], false);
-                                        } else {
-                                            let ix = self
-                                                .builder
-                                                .get_val(&mut self.vm, Val::Float(val.to_bits()));
-                                            self.builder.emit(Op::OP_SWAP, &[], false);
-                                            self.builder.emit(Op::OP_PUSH_LIT, &[ix], false);
-                                            self.builder.emit(Op::OP_SWAP, &[], false);
-                                            self.builder.emit(Op::OP_SET, &[], false);
-                                        }
-                                    }
-                                    _ => todo!(),
-                                }
-                            }
-                            p => todo!("{:?}", p),
-                        },
-                        _ => todo!(),
+                        PropOrSpread::Prop(prop) => self.emit_object_prop(prop),
+                        PropOrSpread::Spread(spread) => {
+                            self.builder.emit(Op::OP_DUP, &[], false);
+                            self.emit(&spread.expr, true);
+                            self.builder.emit(Op::OP_SPREAD_OBJ, &[], false);
+                        }
                     }
                 }
+                if has_methods {
+                    self.pop_scope();
+                }
+                if !used {
+                    self.builder.emit(Op::OP_DROP, &[], false);
+                }
             }
             Expr::Paren(p) => {
                 self.emit(&p.expr, used);
             }
+            Expr::Yield(yield_expr) => {
+                match yield_expr.arg {
+                    Some(ref arg) => self.emit(arg, true),
+                    None => self.builder.emit(Op::OP_PUSH_UNDEFINED, &[], false),
+                }
+                if yield_expr.delegate {
+                    self.builder.emit(Op::OP_YIELD_STAR, &[], false);
+                } else {
+                    self.builder.emit(Op::OP_YIELD, &[], false);
+                }
+                if !used {
+                    self.builder.emit(Op::OP_DROP, &[], false);
+                }
+            }
+            Expr::Await(await_expr) => {
+                self.emit(&await_expr.arg, true);
+                self.builder.emit(Op::OP_AWAIT, &[], false);
+                if !used {
+                    self.builder.emit(Op::OP_DROP, &[], false);
+                }
+            }
             Expr::Assign(assign) => {
                 let x = 0;
                 match assign.op {
@@ -504,37 +591,41 @@ impl Compiler {
                                 if used {
                                     self.builder.emit(Op::OP_DUP, &[], false);
                                 }
-                                let name = if let Expr::Ident(id) = &*member.prop {
-                                    let s: &str = &id.sym;
-                                    let name = self.vm.intern_or_known_symbol(s);
-                                    Some(self.builder.get_sym(name))
-                                } else {
-                                    self.emit(&member.prop, true);
-                                    None
-                                };
-                                match member.obj {
-                                    ExprOrSuper::Expr(ref expr) => {
-                                        self.emit(expr, true);
-                                    }
-                                    ExprOrSuper::Super(_super) => {
-                                        todo!()
-                                    }
-                                }
-
-                                if let Some(ix) = name {
-                                    self.builder.emit(Op::OP_SET_PROP, &[ix], true);
-                                } else {
-                                    self.builder.emit(Op::OP_SET, &[], false);
-                                }
+                                self.emit_member_store(member);
                             }
                             Expr::Ident(id) => {
                                 self.emit(&assign.right, true);
-                                let sym = self.get_ident(&id);
+                                if used {
+                                    self.builder.emit(Op::OP_DUP, &[], false);
+                                }
+                                let sym = self.get_ident(id);
                                 self.builder.emit(Op::OP_SET_VAR, &[sym], true);
                             }
                             e => todo!("{:?}", e,),
                         },
                     },
+                    AssignOp::AndAssign | AssignOp::OrAssign | AssignOp::NullishAssign => {
+                        // the target is not assigned if its current value decides the result
+                        self.emit_load_from(&assign.left);
+                        self.builder.emit(Op::OP_DUP, &[], false);
+                        let jskip = match assign.op {
+                            AssignOp::AndAssign => self.cjmp(false),
+                            AssignOp::OrAssign => self.cjmp(true),
+                            _ => {
+                                self.builder.emit(Op::OP_PUSH_NULL, &[], false);
+                                self.builder.emit(Op::OP_EQ, &[], false);
+                                self.cjmp(false)
+                            }
+                        };
+                        self.builder.emit(Op::OP_DROP, &[], false);
+                        self.emit(&assign.right, true);
+                        self.builder.emit(Op::OP_DUP, &[], false);
+                        self.emit_store_from(&assign.left);
+                        jskip(self);
+                        if !used {
+                            self.builder.emit(Op::OP_DROP, &[], false);
+                        }
+                    }
                     op => {
                         self.emit_load_from(&assign.left);
                         if used {
@@ -570,6 +661,20 @@ impl Compiler {
                         }
                         return;
                     }
+                    BinaryOp::NullishCoalescing => {
+                        self.emit(&binary.left, true);
+                        self.builder.emit(Op::OP_DUP, &[], false);
+                        self.builder.emit(Op::OP_PUSH_NULL, &[], false);
+                        self.builder.emit(Op::OP_EQ, &[], false);
+                        let jdefined = self.cjmp(false);
+                        self.builder.emit(Op::OP_DROP, &[], false);
+                        self.emit(&binary.right, true);
+                        jdefined(self);
+                        if !used {
+                            self.builder.emit(Op::OP_DROP, &[], false);
+                        }
+                        return;
+                    }
                     BinaryOp::LogicalAnd => {
                         self.emit(&binary.left, true);
                         self.builder.emit(Op::OP_DUP, &[], false);
@@ -613,6 +718,7 @@ impl Compiler {
                     BinaryOp::Lt => self.builder.emit(Op::OP_LT, &[], false),
                     BinaryOp::LtEq => self.builder.emit(Op::OP_LE, &[], false),
                     BinaryOp::In => self.builder.emit(Op::OP_IN, &[], false),
+                    BinaryOp::InstanceOf => self.builder.emit(Op::OP_INSTANCEOF, &[], false),
 
                     _ => todo!(),
                 }
@@ -643,30 +749,7 @@ impl Compiler {
                 let ix = self.get_ident(id);
                 self.builder.emit(Op::OP_GET_VAR, &[ix], true);
             }
-            Expr::Member(member) => {
-                let name = if let Expr::Ident(id) = &*member.prop {
-                    let s: &str = &id.sym;
-                    let name = self.vm.intern_or_known_symbol(s);
-                    Some(self.builder.get_sym(name))
-                } else {
-                    self.emit(&member.prop, true);
-                    None
-                };
-                match member.obj {
-                    ExprOrSuper::Expr(ref expr) => {
-                        self.emit(expr, true);
-                    }
-                    ExprOrSuper::Super(_super) => {
-                        todo!()
-                    }
-                }
-
-                if let Some(ix) = name {
-                    self.builder.emit(Op::OP_GET_PROP, &[ix], true);
-                } else {
-                    self.builder.emit(Op::OP_GET, &[], false);
-                }
-            }
+            Expr::Member(_) => self.emit(e, true),
             e => todo!("{:?}", e,),
         }
     }
@@ -689,36 +772,23 @@ impl Compiler {
                 self.builder.emit(Op::OP_SET_VAR, &[ix], true);
             }
             Expr::Member(member) => {
-                let name = if let Expr::Ident(id) = &*member.prop {
-                    let s: &str = &id.sym;
-                    let name = self.vm.intern_or_known_symbol(s);
-                    Some(self.builder.get_sym(name))
-                } else {
-                    self.emit(&member.prop, true);
-                    None
-                };
-                match member.obj {
-                    ExprOrSuper::Expr(ref expr) => {
-                        self.emit(expr, true);
-                    }
-                    ExprOrSuper::Super(_super) => {
-                        todo!()
-                    }
-                }
-
-                if let Some(ix) = name {
-                    self.builder.emit(Op::OP_SET_PROP, &[ix], true);
-                } else {
-                    self.builder.emit(Op::OP_SET, &[], false);
-                }
+                self.emit_member_store(member);
             }
             e => todo!("{:?}", e,),
         }
     }
-    pub fn push_lci(&mut self, continue_target: u32) {
+    pub fn push_lci(&mut self, ty: BlockType, values: u32, exit: &'static [Op]) {
+        let labels = std::mem::take(&mut self.labels);
         self.lci.push(LoopControlInfo {
             continues: vec![],
             breaks: vec![],
+            ty,
+            labels,
+            scope_depth: self.scope_depth,
+            values,
+            exit,
+            try_depth: self.try_depth,
+            finalizer: None,
         });
     }
 
@@ -728,51 +798,213 @@ impl Compiler {
             break_(self);
         }
     }
+
+    pub fn push_scope(&mut self) {
+        self.builder.emit(Op::OP_PUSH_SCOPE, &[], false);
+        self.scope_depth += 1;
+    }
+
+    pub fn pop_scope(&mut self) {
+        self.builder.emit(Op::OP_POP_SCOPE, &[], false);
+        self.scope_depth -= 1;
+    }
+    /// Emit `OP_TRY_POP` and `OP_POP_SCOPE` to go from depths `(scope_depth, try_depth)`
+    /// down to `(to_scope, to_try)`.
+    fn unwind_depths(
+        &mut self,
+        (scope_depth, try_depth): (u32, u32),
+        (to_scope, to_try): (u32, u32),
+    ) -> (u32, u32) {
+        for _ in to_try..try_depth {
+            self.builder.emit(Op::OP_TRY_POP, &[], false);
+        }
+        for _ in to_scope..scope_depth {
+            self.builder.emit(Op::OP_POP_SCOPE, &[], false);
+        }
+        (to_scope, to_try)
+    }
+
+    /// Emit code leaving blocks `lci[len..]` from the innermost one: pops `try` handlers and
+    /// scopes, runs `finally` blocks and discards values the blocks keep on the operand stack.
+    /// If `keep_top` is true, the value on top of the stack (e.g. the value of `return`) is
+    /// kept above them. Compile-time state is not changed, returns depths reached.
+    pub fn unwind_blocks(&mut self, len: usize, keep_top: bool) -> (u32, u32) {
+        let mut depths = (self.scope_depth, self.try_depth);
+        for ix in (len..self.lci.len()).rev() {
+            let target = (self.lci[ix].scope_depth, self.lci[ix].try_depth);
+            depths = self.unwind_depths(depths, target);
+            if let Some(finalizer) = self.lci[ix].finalizer.clone() {
+                // `finally` block is compiled as if control was at the `try` statement, so
+                // jumps out of it see only blocks that enclose the `try` statement.
+                let inner = self.lci.split_off(ix);
+                let saved = (self.scope_depth, self.try_depth);
+                self.scope_depth = depths.0;
+                self.try_depth = depths.1;
+                self.emit_block(&finalizer);
+                self.scope_depth = saved.0;
+                self.try_depth = saved.1;
+                self.lci.extend(inner);
+            } else {
+                if keep_top {
+                    match self.lci[ix].values {
+                        0 => {}
+                        1 => self.builder.emit(Op::OP_SWAP, &[], false),
+                        2 => {
+                            self.builder.emit(Op::OP_ROT, &[], false);
+                            self.builder.emit(Op::OP_ROT, &[], false);
+                        }
+                        n => unreachable!("block keeps {} values", n),
+                    }
+                }
+                let exit = self.lci[ix].exit;
+                for op in exit {
+                    self.builder.emit(*op, &[], false);
+                }
+            }
+        }
+        depths
+    }
+
+    /// Emit jump out of blocks nested deeper than `lci[ix]` to a target of `lci[ix]`.
+    pub fn jump_to_lci(&mut self, ix: usize) -> impl FnOnce(&mut Self) {
+        let depths = self.unwind_blocks(ix + 1, false);
+        let target = (self.lci[ix].scope_depth, self.lci[ix].try_depth);
+        self.unwind_depths(depths, target);
+        self.jmp()
+    }
+
+    pub fn emit_block(&mut self, block: &BlockStmt) {
+        self.push_scope();
+        self.declare_lexical_bindings(&block.stmts);
+        for stmt in block.stmts.iter() {
+            self.emit_stmt(stmt);
+        }
+        self.pop_scope();
+    }
+
+    /// Find `break` target: innermost loop or `switch`, or block with the given label.
+    fn break_target(&mut self, label: &Option<Ident>) -> Option<usize> {
+        match label {
+            Some(label) => {
+                let label = self.intern(label);
+                self.lci.iter().rposition(|lci| lci.labels.contains(&label))
+            }
+            None => self
+                .lci
+                .iter()
+                .rposition(|lci| lci.ty == BlockType::Loop || lci.ty == BlockType::Switch),
+        }
+    }
+
+    /// Find `continue` target: innermost loop or loop with the given label.
+    fn continue_target(&mut self, label: &Option<Ident>) -> Option<usize> {
+        let label = label.as_ref().map(|label| self.intern(label));
+        self.lci.iter().rposition(|lci| {
+            lci.ty == BlockType::Loop && label.is_none_or(|label| lci.labels.contains(&label))
+        })
+    }
     pub fn emit_stmt(&mut self, stmt: &Stmt) {
         match stmt {
             Stmt::Expr(expr) => {
                 self.emit(&expr.expr, false);
             }
             Stmt::Block(block) => {
-                self.builder.emit(Op::OP_PUSH_SCOPE, &[], false);
-                for stmt in block.stmts.iter() {
-                    self.emit_stmt(stmt);
-                }
-                self.builder.emit(Op::OP_POP_SCOPE, &[], false);
+                self.emit_block(block);
             }
             Stmt::Return(ret) => {
                 match ret.arg {
                     Some(ref arg) => self.emit(&**arg, true),
                     None => self.builder.emit(Op::OP_PUSH_UNDEFINED, &[], false),
                 }
+                // enclosing `finally` blocks run and `for-of` iterators are closed before
+                // returning, the return value stays on top of the stack meanwhile.
+                if self
+                    .lci
+                    .iter()
+                    .any(|lci| lci.finalizer.is_some() || lci.exit.contains(&Op::OP_ITERATOR_CLOSE))
+                {
+                    self.unwind_blocks(0, true);
+                }
                 self.builder.emit(Op::OP_RET, &[], false);
             }
-            Stmt::Break(_) => {
-                // self.builder.emit(Op::OP_POP_SCOPE, &[], false);
-                let br = self.jmp();
-                self.lci.last_mut().unwrap().breaks.push(Box::new(br));
+            Stmt::Break(break_stmt) => {
+                let ix = match self.break_target(&break_stmt.label) {
+                    Some(ix) => ix,
+                    None => {
+                        self.syntax_error(&jump_error("break", &break_stmt.label));
+                        return;
+                    }
+                };
+                let br = self.jump_to_lci(ix);
+                self.lci[ix].breaks.push(Box::new(br));
+            }
+            Stmt::Continue(continue_stmt) => {
+                let ix = match self.continue_target(&continue_stmt.label) {
+                    Some(ix) => ix,
+                    None => {
+                        self.syntax_error(&jump_error("continue", &continue_stmt.label));
+                        return;
+                    }
+                };
+                let j = self.jump_to_lci(ix);
+                self.lci[ix].continues.push(Box::new(j));
+            }
+            Stmt::Labeled(labeled) => {
+                let label = self.intern(&labeled.label);
+                self.labels.push(label);
+                match &*labeled.body {
+                    Stmt::For(_)
+                    | Stmt::ForIn(_)
+                    | Stmt::ForOf(_)
+                    | Stmt::While(_)
+                    | Stmt::DoWhile(_)
+                    | Stmt::Switch(_)
+                    | Stmt::Labeled(_) => self.emit_stmt(&labeled.body),
+                    body => {
+                        // any other statement is a target for `break label` only.
+                        self.push_lci(BlockType::Label, 0, &[]);
+                        self.emit_stmt(body);
+                        self.pop_lci();
+                    }
+                }
             }
-            Stmt::Continue(_) => {
-                self.builder.emit(Op::OP_POP_SCOPE, &[], false);
-                let j = self.jmp();
-                self.lci.last_mut().unwrap().continues.push(Box::new(j));
+            Stmt::DoWhile(do_while) => {
+                let head = self.builder.code.code.len();
+                self.push_lci(BlockType::Loop, 0, &[]);
+                self.emit_stmt(&do_while.body);
+                while let Some(c) = self.lci.last_mut().unwrap().continues.pop() {
+                    c(self);
+                }
+                self.emit(&do_while.test, true);
+                let jend = self.cjmp(false);
+                self.goto(head);
+                jend(self);
+                self.pop_lci();
             }
             Stmt::For(for_stmt) => {
-                self.builder.emit(Op::OP_PUSH_SCOPE, &[], false);
+                self.push_scope();
+                // `let` bindings are copied to a fresh scope for each iteration so closures
+                // created in the body capture the value of the current iteration.
+                let mut per_iteration = vec![];
                 match for_stmt.init {
                     Some(ref init) => match init {
                         VarDeclOrExpr::Expr(ref e) => {
                             self.emit(e, false);
                         }
                         VarDeclOrExpr::VarDecl(ref decl) => {
+                            self.declare_uninitialized(decl);
                             self.emit_var_decl(decl);
+                            if decl.kind == VarDeclKind::Let {
+                                per_iteration = self.bound_names(decl);
+                            }
                         }
                     },
                     None => {}
                 }
+                self.emit_copy_bindings(&per_iteration);
 
                 let head = self.builder.code.code.len();
-                self.push_lci(head as _);
+                self.push_lci(BlockType::Loop, 0, &[]);
                 match for_stmt.test {
                     Some(ref test) => {
                         self.emit(&**test, true);
@@ -786,19 +1018,19 @@ impl Compiler {
                 while let Some(c) = self.lci.last_mut().unwrap().continues.pop() {
                     c(self);
                 }
+                self.emit_copy_bindings(&per_iteration);
                 if let Some(fin) = &for_stmt.update {
                     self.emit(&**fin, false);
                 }
                 self.goto(head as _);
-                self.pop_lci();
-                self.builder.emit(Op::OP_POP_SCOPE, &[], false);
                 jend(self);
+                self.pop_lci();
 
-                self.builder.emit(Op::OP_POP_SCOPE, &[], false);
+                self.pop_scope();
             }
             Stmt::While(while_stmt) => {
                 let head = self.builder.code.code.len();
-                self.push_lci(head as _);
+                self.push_lci(BlockType::Loop, 0, &[]);
                 self.emit(&while_stmt.test, true);
                 let jend = self.cjmp(false);
                 self.emit_stmt(&while_stmt.body);
@@ -810,6 +1042,104 @@ impl Compiler {
                 jend(self);
                 self.pop_lci();
             }
+            Stmt::ForIn(for_in) => {
+                // The object and the enumeration handle stay on the stack while the loop runs.
+                // `OP_NEXT_PROP` consumes both once enumeration is done, `break` drops them.
+                self.emit(&for_in.right, true);
+                self.builder.emit(Op::OP_PUSH_NULL, &[], false);
+                self.push_lci(BlockType::Loop, 2, &[Op::OP_DROP, Op::OP_DROP]);
+                let head = self.builder.code.code.len();
+                self.builder.emit(Op::OP_NEXT_PROP, &[], false);
+                let jend = self.cjmp(false);
+                let scoped = self.emit_for_head_store(&for_in.left);
+                self.emit_stmt(&for_in.body);
+                if scoped {
+                    self.pop_scope();
+                }
+                while let Some(c) = self.lci.last_mut().unwrap().continues.pop() {
+                    c(self);
+                }
+                self.goto(head);
+                jend(self);
+                let jexit = self.jmp();
+                self.pop_lci();
+                self.builder.emit(Op::OP_DROP, &[], false);
+                self.builder.emit(Op::OP_DROP, &[], false);
+                jexit(self);
+            }
+            Stmt::ForOf(for_of) => {
+                // The iterator and its `next` method stay on the stack while the loop runs.
+                // The body runs under a `finally` handler so that an exception or generator
+                // `return` closes the iterator, `break` closes it with `OP_ITERATOR_CLOSE`.
+                self.emit(&for_of.right, true);
+                self.builder.emit(Op::OP_GET_ITERATOR, &[], false);
+                self.push_lci(BlockType::Loop, 2, &[Op::OP_ITERATOR_CLOSE]);
+                let head = self.builder.code.code.len();
+                self.builder.emit(Op::OP_ITERATOR_NEXT, &[], false);
+                let jend = self.cjmp(false);
+                let try_push = self.try_finally();
+                let scoped = self.emit_for_head_store(&for_of.left);
+                self.emit_stmt(&for_of.body);
+                if scoped {
+                    self.pop_scope();
+                }
+                self.try_pop();
+                while let Some(c) = self.lci.last_mut().unwrap().continues.pop() {
+                    c(self);
+                }
+                self.goto(head);
+                try_push(self);
+                // the handler runs with the stack of the loop head: drop the iteration value
+                // under the error.
+                self.builder.emit(Op::OP_SWAP_DROP, &[], false);
+                self.builder.emit(Op::OP_ITERATOR_CLOSE_THROW, &[], false);
+                jend(self);
+                let jexit = self.jmp();
+                self.pop_lci();
+                self.builder.emit(Op::OP_ITERATOR_CLOSE, &[], false);
+                jexit(self);
+            }
+            Stmt::Switch(switch) => {
+                // The discriminant stays on the stack while case tests and bodies run and is
+                // dropped when the switch is left. `continue` drops it through `unwind_blocks`.
+                self.emit(&switch.discriminant, true);
+                self.push_scope();
+                for case in switch.cases.iter() {
+                    self.declare_lexical_bindings(&case.cons);
+                }
+                self.push_lci(BlockType::Switch, 1, &[Op::OP_DROP]);
+                let mut jumps: Vec<Option<Box<dyn FnOnce(&mut Compiler)>>> =
+                    Vec::with_capacity(switch.cases.len());
+                for case in switch.cases.iter() {
+                    match case.test {
+                        Some(ref test) => {
+                            self.builder.emit(Op::OP_DUP, &[], false);
+                            self.emit(&**test, true);
+                            self.builder.emit(Op::OP_EQ_EQ, &[], false);
+                            jumps.push(Some(Box::new(self.cjmp(true))));
+                        }
+                        None => jumps.push(None),
+                    }
+                }
+                // no case matched: go to `default` if there is one, otherwise leave the switch.
+                let jdefault = self.jmp();
+                match switch.cases.iter().position(|case| case.test.is_none()) {
+                    Some(ix) => jumps[ix] = Some(Box::new(jdefault)),
+                    None => self.lci.last_mut().unwrap().breaks.push(Box::new(jdefault)),
+                }
+                // bodies are emitted in source order so execution falls through to the next case.
+                for (case, jump) in switch.cases.iter().zip(jumps) {
+                    if let Some(jump) = jump {
+                        jump(self);
+                    }
+                    for stmt in case.cons.iter() {
+                        self.emit_stmt(stmt);
+                    }
+                }
+                self.pop_lci();
+                self.pop_scope();
+                self.builder.emit(Op::OP_DROP, &[], false);
+            }
             Stmt::If(if_stmt) => {
                 self.emit(&if_stmt.test, true);
                 let jelse = self.cjmp(false);
@@ -838,6 +1168,11 @@ impl Compiler {
                     let nix = self.builder.get_sym(sym);
                     self.builder.emit(Op::OP_SET_VAR, &[nix], true);
                 }
+                Decl::Class(class) => {
+                    self.emit_class(&class.class, Some(&class.ident));
+                    let ix = self.get_ident(&class.ident);
+                    self.builder.emit(Op::OP_DECL_LET, &[ix], true);
+                }
                 _ => (),
             },
 
@@ -847,117 +1182,248 @@ impl Compiler {
                 self.builder.emit(Op::OP_THROW, &[], false);
             }
             Stmt::Try(try_stmt) => {
-                let try_push = self.try_();
-                if !try_stmt.block.stmts.is_empty() {
-                    self.builder.emit(Op::OP_PUSH_SCOPE, &[], false);
-                }
-                for stmt in try_stmt.block.stmts.iter() {
-                    self.emit_stmt(stmt);
-                }
-                if !try_stmt.block.stmts.is_empty() {
-                    self.builder.emit(Op::OP_POP_SCOPE, &[], false);
-                }
-                let mut jfinally = self.jmp();
-                try_push(self);
-                let jcatch_finally = match try_stmt.handler {
-                    Some(ref catch) => {
-                        if !catch.body.stmts.is_empty() {
-                            self.builder.emit(Op::OP_PUSH_SCOPE, &[], false);
-                        }
-                        match catch.param {
-                            Some(ref pat) => {
-                                self.generate_pat_store(pat, true, true);
-                            }
-                            None => {
-                                self.builder.emit(Op::OP_DROP, &[], false);
-                            }
-                        }
-                        for stmt in catch.body.stmts.iter() {
-                            self.emit_stmt(stmt);
-                        }
-                        if !catch.body.stmts.is_empty() {
-                            self.builder.emit(Op::OP_POP_SCOPE, &[], false);
-                        }
-                        self.jmp()
-                    }
-                    None => {
-                        self.builder.emit(Op::OP_DROP, &[], false);
-                        self.jmp()
+                // `finally` block is registered so that `break`, `continue` and `return` run it
+                // through `unwind_blocks`. Its exception handler covers `try` and `catch` blocks.
+                let jfinally = match try_stmt.finalizer {
+                    Some(ref finalizer) => {
+                        self.push_lci(BlockType::Finally, 0, &[]);
+                        self.lci.last_mut().unwrap().finalizer = Some(finalizer.clone());
+                        Some(self.try_finally())
                     }
+                    None => None,
                 };
-
-                jfinally(self);
-                jcatch_finally(self);
-                match try_stmt.finalizer {
-                    Some(ref block) => {
-                        if !block.stmts.is_empty() {
-                            self.builder.emit(Op::OP_PUSH_SCOPE, &[], false);
-                        }
-                        for stmt in block.stmts.iter() {
-                            self.emit_stmt(stmt);
+                let jcatch = try_stmt.handler.as_ref().map(|_| self.try_());
+                self.emit_block(&try_stmt.block);
+                if let Some(ref catch) = try_stmt.handler {
+                    self.try_pop();
+                    let jend = self.jmp();
+                    // exception handler is popped by the interpreter, exception value is on TOS.
+                    jcatch.unwrap()(self);
+                    self.push_scope();
+                    match catch.param {
+                        Some(ref pat) => {
+                            self.generate_pat_store(pat, true, true);
                         }
-                        if !block.stmts.is_empty() {
-                            self.builder.emit(Op::OP_POP_SCOPE, &[], false);
+                        None => {
+                            self.builder.emit(Op::OP_DROP, &[], false);
                         }
                     }
-                    None => {}
+                    for stmt in catch.body.stmts.iter() {
+                        self.emit_stmt(stmt);
+                    }
+                    self.pop_scope();
+                    jend(self);
+                }
+                if let Some(ref finalizer) = try_stmt.finalizer {
+                    self.pop_lci();
+                    self.try_pop();
+                    self.emit_block(finalizer);
+                    let jend = self.jmp();
+                    // exception stays on the stack while `finally` block runs and is rethrown.
+                    jfinally.unwrap()(self);
+                    self.emit_block(finalizer);
+                    self.builder.emit(Op::OP_THROW, &[], false);
+                    jend(self);
                 }
             }
 
             _ => todo!(),
         }
     }
+    /// Store value on top of the stack into the head of a `for-in` or `for-of` loop.
+    ///
+    /// `let` and `const` heads get a fresh scope for each iteration so closures capture the
+    /// current value. Returns true if such scope was pushed and has to be popped after the body.
+    pub fn emit_for_head_store(&mut self, left: &VarDeclOrPat) -> bool {
+        match left {
+            VarDeclOrPat::VarDecl(decl) => match decl.kind {
+                VarDeclKind::Var => {
+                    self.generate_pat_store(&decl.decls[0].name, false, false);
+                    false
+                }
+                kind => {
+                    self.push_scope();
+                    self.generate_pat_store(&decl.decls[0].name, true, kind == VarDeclKind::Let);
+                    true
+                }
+            },
+            VarDeclOrPat::Pat(pat) => {
+                self.generate_pat_store(pat, false, false);
+                false
+            }
+        }
+    }
+    /// Store value on top of the stack into pattern `pat`. `decl` is set for `let` and `const`
+    /// bindings, `mutable` for `let`.
     pub fn generate_pat_store(&mut self, pat: &Pat, decl: bool, mutable: bool) {
         match pat {
-            Pat::Ident(id) => {
-                let name = self.get_ident(&id.id);
-                if decl && mutable {
-                    self.builder.emit(Op::OP_DECL_LET, &[name], true);
-                } else if decl && !mutable {
-                    self.builder.emit(Op::OP_DECL_IMMUTABLE, &[name], true);
+            Pat::Ident(id) => self.emit_binding_store(&id.id, decl, mutable),
+            Pat::Assign(assign) => {
+                self.emit_default(&assign.right);
+                self.generate_pat_store(&assign.left, decl, mutable);
+            }
+            Pat::Array(array) => self.generate_array_pat_store(array, decl, mutable),
+            Pat::Object(object) => self.generate_object_pat_store(object, decl, mutable),
+            Pat::Expr(e) => match &**e {
+                Expr::Member(member) => {
+                    self.emit_member_store(member);
                 }
+                _ => todo!(),
+            },
+            Pat::Rest(_) | Pat::Invalid(_) => unreachable!(),
+        }
+    }
 
-                if !decl {
-                    self.builder.emit(Op::OP_SET_VAR, &[name], true);
+    fn emit_binding_store(&mut self, id: &Ident, decl: bool, mutable: bool) {
+        let name = self.get_ident(id);
+        if decl && mutable {
+            self.builder.emit(Op::OP_DECL_LET, &[name], true);
+        } else if decl && !mutable {
+            self.builder.emit(Op::OP_DECL_IMMUTABLE, &[name], true);
+        }
+
+        if !decl {
+            self.builder.emit(Op::OP_SET_VAR, &[name], true);
+        }
+    }
+
+    /// Replace `undefined` on top of the stack with the value of `default`.
+    fn emit_default(&mut self, default: &Expr) {
+        self.builder.emit(Op::OP_DUP, &[], false);
+        self.builder.emit(Op::OP_PUSH_UNDEFINED, &[], false);
+        self.builder.emit(Op::OP_EQ_EQ, &[], false);
+        let jdefined = self.cjmp(false);
+        self.builder.emit(Op::OP_DROP, &[], false);
+        self.emit(default, true);
+        jdefined(self);
+    }
+
+    /// Destructure value on top of the stack by array pattern `array` using the iterator
+    /// protocol. The iterator is closed unless it is done, also when an element store throws.
+    fn generate_array_pat_store(&mut self, array: &ArrayPat, decl: bool, mutable: bool) {
+        self.builder.emit(Op::OP_GET_ITERATOR, &[], false);
+        let try_push = self.try_finally();
+        for elem in array.elems.iter() {
+            match elem {
+                Some(Pat::Rest(rest)) => {
+                    self.builder.emit(Op::OP_ITERATOR_REST, &[], false);
+                    self.generate_pat_store(&rest.arg, decl, mutable);
+                }
+                Some(pat) => {
+                    self.builder.emit(Op::OP_ITERATOR_VALUE, &[], false);
+                    self.generate_pat_store(pat, decl, mutable);
+                }
+                None => {
+                    self.builder.emit(Op::OP_ITERATOR_VALUE, &[], false);
+                    self.builder.emit(Op::OP_DROP, &[], false);
                 }
             }
-            Pat::Expr(e) => match &**e {
-                Expr::Member(member) => {
-                    let name = if let Expr::Ident(id) = &*member.prop {
-                        let s: &str = &id.sym;
-                        let name = self.vm.intern_or_known_symbol(s);
-                        Some(self.builder.get_sym(name))
-                    } else {
-                        self.emit(&member.prop, true);
-                        None
-                    };
-                    match member.obj {
-                        ExprOrSuper::Expr(ref expr) => {
-                            self.emit(expr, true);
-                        }
-                        ExprOrSuper::Super(_super) => {
-                            todo!()
-                        }
-                    }
+        }
+        self.try_pop();
+        self.builder.emit(Op::OP_ITERATOR_CLOSE, &[], false);
+        let jend = self.jmp();
+        try_push(self);
+        self.builder.emit(Op::OP_ITERATOR_CLOSE_THROW, &[], false);
+        jend(self);
+    }
 
-                    if let Some(ix) = name {
-                        self.builder.emit(Op::OP_SET_PROP, &[ix], true);
-                    } else {
-                        self.builder.emit(Op::OP_SET, &[], false);
+    /// Destructure value on top of the stack by object pattern `object`.
+    ///
+    /// If the pattern has a rest element, keys of the other properties are collected into an
+    /// array kept above the value, the rest object gets the remaining properties.
+    fn generate_object_pat_store(&mut self, object: &ObjectPat, decl: bool, mutable: bool) {
+        self.builder
+            .emit(Op::OP_REQUIRE_OBJECT_COERCIBLE, &[], false);
+        let has_rest = object
+            .props
+            .iter()
+            .any(|prop| matches!(prop, ObjectPatProp::Rest(_)));
+        if has_rest {
+            self.builder.emit(Op::OP_PUSH_EMPTY, &[], false);
+            self.builder.emit(Op::OP_CREATE_ARRN, &[0], false);
+        }
+        for prop in object.props.iter() {
+            match prop {
+                ObjectPatProp::KeyValue(prop) => {
+                    self.emit_pat_prop_get(&prop.key, has_rest);
+                    self.generate_pat_store(&prop.value, decl, mutable);
+                }
+                ObjectPatProp::Assign(prop) => {
+                    self.emit_pat_prop_get(&PropName::Ident(prop.key.clone()), has_rest);
+                    if let Some(ref default) = prop.value {
+                        self.emit_default(default);
                     }
+                    self.emit_binding_store(&prop.key, decl, mutable);
                 }
-                _ => todo!(),
-            },
-            _ => todo!(),
+                ObjectPatProp::Rest(rest) => {
+                    // rest element is the last one, the value and the keys are consumed.
+                    self.builder.emit(Op::OP_OBJECT_REST, &[], false);
+                    self.generate_pat_store(&rest.arg, decl, mutable);
+                    return;
+                }
+            }
+        }
+        self.builder.emit(Op::OP_DROP, &[], false);
+    }
+
+    /// Push value of property `key` of the object being destructured, see
+    /// `generate_object_pat_store`.
+    fn emit_pat_prop_get(&mut self, key: &PropName, has_rest: bool) {
+        if has_rest {
+            self.emit_prop_key(key);
+            self.builder.emit(Op::OP_GET_AND_EXCLUDE, &[], false);
+            return;
+        }
+        self.builder.emit(Op::OP_DUP, &[], false);
+        match self.prop_name(key) {
+            Some(name) => {
+                let ix = self.builder.get_sym(name);
+                self.builder.emit(Op::OP_GET_PROP, &[ix], true);
+            }
+            None => {
+                self.emit_prop_key(key);
+                self.builder.emit(Op::OP_SWAP, &[], false);
+                self.builder.emit(Op::OP_GET, &[], false);
+            }
+        }
+    }
+
+    /// Push property key `key`, computed keys are evaluated.
+    fn emit_prop_key(&mut self, key: &PropName) {
+        let mut vm = self.vm;
+        match key {
+            PropName::Ident(id) => {
+                let ix = self.builder.get_val(&mut vm, Val::Str(id.sym.to_string()));
+                self.builder.emit(Op::OP_PUSH_LIT, &[ix], false);
+            }
+            PropName::Str(s) => {
+                let ix = self.builder.get_val(&mut vm, Val::Str(s.value.to_string()));
+                self.builder.emit(Op::OP_PUSH_LIT, &[ix], false);
+            }
+            PropName::Num(n) => self.emit_lit(&Lit::Num(*n)),
+            PropName::Computed(key) => self.emit(&key.expr, true),
+            PropName::BigInt(_) => {
+                self.syntax_error("BigInt property keys are not supported");
+                self.builder.emit(Op::OP_PUSH_UNDEFINED, &[], false);
+            }
         }
     }
+
+    /// Emit `OP_TRY_PUSH_CATCH`. Returned closure places the handler at the current position.
     pub fn try_(&mut self) -> impl FnOnce(&mut Self) {
+        self.try_push(Op::OP_TRY_PUSH_CATCH)
+    }
+    /// Emit `OP_TRY_PUSH_FINALLY`. Returned closure places the handler at the current position.
+    pub fn try_finally(&mut self) -> impl FnOnce(&mut Self) {
+        self.try_push(Op::OP_TRY_PUSH_FINALLY)
+    }
+    fn try_push(&mut self, ins: Op) -> impl FnOnce(&mut Self) {
         let p = self.builder.code.code.len();
-        self.builder.emit(Op::OP_TRY_PUSH_CATCH, &[0], false);
+        self.builder.emit(ins, &[0], false);
+        self.try_depth += 1;
 
         move |this: &mut Self| {
             let to = this.builder.code.code.len() - (p + 5);
-            let ins = Op::OP_TRY_PUSH_CATCH;
             let bytes = (to as u32).to_le_bytes();
             this.builder.code.code[p] = ins as u8;
             this.builder.code.code[p + 1] = bytes[0];
@@ -966,6 +1432,11 @@ impl Compiler {
             this.builder.code.code[p + 4] = bytes[3];
         }
     }
+    /// Emit `OP_TRY_POP` when control leaves a `try` block normally.
+    pub fn try_pop(&mut self) {
+        self.builder.emit(Op::OP_TRY_POP, &[], false);
+        self.try_depth -= 1;
+    }
     pub fn cjmp(&mut self, cond: bool) -> impl FnOnce(&mut Self) {
         let p = self.builder.code.code.len();
         self.builder.emit(Op::OP_PLACEHOLDER, &[0], false);
@@ -1007,6 +1478,266 @@ impl Compiler {
             //this.builder.code.code[p] = ins as u8;
         }
     }
+    /// Push `this` value and the function of a call to `callee` on the stack.
+    fn emit_callee(&mut self, callee: &Expr) {
+        match callee {
+            Expr::Member(member) => match member.obj {
+                ExprOrSuper::Expr(ref expr) => {
+                    self.emit(expr, true);
+                    self.builder.emit(Op::OP_DUP, &[], false);
+                    self.emit_member_access(member);
+                }
+                ExprOrSuper::Super(_) => {
+                    self.builder.emit(Op::OP_PUSH_THIS, &[], false);
+                    if let (Expr::Ident(id), false) = (&*member.prop, member.computed) {
+                        let name = self.intern(id);
+                        let name = self.builder.get_sym(name);
+                        self.emit_home_object();
+                        self.builder.emit(Op::OP_GET_SUPER_PROP, &[name], false);
+                    } else {
+                        self.emit(&member.prop, true);
+                        self.emit_home_object();
+                        self.builder.emit(Op::OP_GET_SUPER, &[], false);
+                    }
+                }
+            },
+            _ => {
+                self.builder.emit(Op::OP_PUSH_EMPTY, &[], false);
+                self.emit(callee, true);
+            }
+        }
+    }
+
+    /// Push arguments `args` of a call preceded by the empty value that ends them, see
+    /// `OP_CALL`.
+    fn emit_args(&mut self, args: &[ExprOrSpread]) {
+        self.builder.emit(Op::OP_PUSH_EMPTY, &[], false);
+        for arg in args.iter().rev() {
+            self.emit(&arg.expr, true);
+            if arg.spread.is_some() {
+                self.builder.emit(Op::OP_SPREAD_ARR, &[], false);
+            }
+        }
+    }
+
+    /// Store value on top of the stack into the property referenced by `member`.
+    fn emit_member_store(&mut self, member: &MemberExpr) {
+        let name = if let (Expr::Ident(id), false) = (&*member.prop, member.computed) {
+            let name = self.intern(id);
+            Some(self.builder.get_sym(name))
+        } else {
+            self.emit(&member.prop, true);
+            None
+        };
+        let is_super = match member.obj {
+            ExprOrSuper::Expr(ref expr) => {
+                self.emit(expr, true);
+                false
+            }
+            ExprOrSuper::Super(_) => {
+                self.emit_home_object();
+                true
+            }
+        };
+
+        match name {
+            Some(ix) if is_super => self.builder.emit(Op::OP_SET_SUPER_PROP, &[ix], false),
+            Some(ix) => self.builder.emit(Op::OP_SET_PROP, &[ix], true),
+            None if is_super => self.builder.emit(Op::OP_SET_SUPER, &[], false),
+            None => self.builder.emit(Op::OP_SET, &[], false),
+        }
+    }
+
+    /// Replace the object on top of the stack with the value of its property `member.prop`.
+    fn emit_member_access(&mut self, member: &MemberExpr) {
+        if let (Expr::Ident(id), false) = (&*member.prop, member.computed) {
+            let name = self.intern(id);
+            let name = self.builder.get_sym(name);
+            self.builder.emit(Op::OP_GET_PROP, &[name], true);
+        } else {
+            self.emit(&member.prop, true);
+            self.builder.emit(Op::OP_SWAP, &[], false);
+            self.builder.emit(Op::OP_GET, &[], false);
+        }
+    }
+
+    /// Emit member and call chain `expr` containing `?.` links. A link whose object or callee
+    /// is `undefined` or `null` skips the rest of the chain, which evaluates to `undefined`.
+    fn emit_opt_chain(&mut self, expr: &Expr, used: bool) {
+        let mut jumps = vec![];
+        self.emit_chain(expr, 0, &mut jumps);
+        let jend = self.jmp();
+        // skipping links leave the values of the chain pushed so far on the stack, landing
+        // points with more values fall through to the ones with less.
+        let max = jumps.iter().map(|(_, depth)| *depth).max().unwrap_or(0);
+        for depth in (1..=max).rev() {
+            let (here, rest): (ChainJumps, ChainJumps) =
+                jumps.into_iter().partition(|(_, d)| *d == depth);
+            jumps = rest;
+            for (jump, _) in here {
+                jump(self);
+            }
+            self.builder.emit(Op::OP_DROP, &[], false);
+        }
+        self.builder.emit(Op::OP_PUSH_UNDEFINED, &[], false);
+        jend(self);
+        if !used {
+            self.builder.emit(Op::OP_DROP, &[], false);
+        }
+    }
+
+    /// Push the value of `expr`, a link of an optional chain. `depth` values of the chain are
+    /// on the stack below it, jumps skipping the rest of the chain are added to `jumps` with
+    /// the number of values they leave on the stack.
+    fn emit_chain(&mut self, expr: &Expr, depth: u32, jumps: &mut ChainJumps) {
+        match expr {
+            Expr::OptChain(opt) => match &*opt.expr {
+                Expr::Member(member) => self.emit_chain_member(member, true, false, depth, jumps),
+                Expr::Call(call) => self.emit_chain_call(call, true, depth, jumps),
+                expr => unreachable!("{:?}", expr),
+            },
+            Expr::Member(member) if !matches!(member.obj, ExprOrSuper::Super(_)) => {
+                self.emit_chain_member(member, false, false, depth, jumps);
+            }
+            Expr::Call(call) if !matches!(call.callee, ExprOrSuper::Super(_)) => {
+                self.emit_chain_call(call, false, depth, jumps);
+            }
+            expr => self.emit(expr, true),
+        }
+    }
+
+    /// Push property `member.prop` of its object, `optional` for `a?.b` links. With `keep` the
+    /// object stays below the property value as `this` of a call.
+    fn emit_chain_member(
+        &mut self,
+        member: &MemberExpr,
+        optional: bool,
+        keep: bool,
+        depth: u32,
+        jumps: &mut ChainJumps,
+    ) {
+        match member.obj {
+            ExprOrSuper::Expr(ref obj) => self.emit_chain(obj, depth, jumps),
+            ExprOrSuper::Super(_) => unreachable!(),
+        }
+        if optional {
+            self.emit_nullish_jump(depth + 1, jumps);
+        }
+        if keep {
+            self.builder.emit(Op::OP_DUP, &[], false);
+        }
+        self.emit_member_access(member);
+    }
+
+    /// Push the result of call `call`, `optional` for `f?.()` links.
+    fn emit_chain_call(
+        &mut self,
+        call: &CallExpr,
+        optional: bool,
+        depth: u32,
+        jumps: &mut ChainJumps,
+    ) {
+        let callee = match call.callee {
+            ExprOrSuper::Expr(ref callee) => callee,
+            ExprOrSuper::Super(_) => unreachable!(),
+        };
+        // `this` and the function, like `emit_callee`
+        match &**callee {
+            Expr::OptChain(opt) if matches!(*opt.expr, Expr::Member(_)) => {
+                if let Expr::Member(member) = &*opt.expr {
+                    self.emit_chain_member(member, true, true, depth, jumps);
+                }
+            }
+            Expr::Member(member) if !matches!(member.obj, ExprOrSuper::Super(_)) => {
+                self.emit_chain_member(member, false, true, depth, jumps);
+            }
+            Expr::Member(_) => self.emit_callee(callee),
+            callee => {
+                self.builder.emit(Op::OP_PUSH_EMPTY, &[], false);
+                self.emit_chain(callee, depth + 1, jumps);
+            }
+        }
+        if optional {
+            self.emit_nullish_jump(depth + 2, jumps);
+        }
+        self.emit_args(&call.args);
+        self.builder
+            .emit(Op::OP_CALL, &[call.args.len() as u32], false);
+    }
+
+    /// Add a jump taken if the value on top of the stack is `undefined` or `null` to `jumps`.
+    /// `depth` values stay on the stack if the jump is taken.
+    fn emit_nullish_jump(&mut self, depth: u32, jumps: &mut ChainJumps) {
+        self.builder.emit(Op::OP_DUP, &[], false);
+        self.builder.emit(Op::OP_PUSH_NULL, &[], false);
+        self.builder.emit(Op::OP_EQ, &[], false);
+        let jump = self.cjmp(true);
+        jumps.push((Box::new(jump), depth));
+    }
+
+    /// 13.2.8.3 GetTemplateObject
+    ///
+    /// Creates the frozen strings array of a tagged template and returns its literal index.
+    /// The array is created once per template site, every evaluation of the site passes the
+    /// same object.
+    fn template_object(&mut self, quasis: &[TplElement]) -> u32 {
+        let mut vm = self.vm;
+        let cooked = Self::frozen_array(
+            &mut vm,
+            quasis
+                .iter()
+                .map(|quasi| quasi.cooked.as_ref().map(|cooked| cooked.value.to_string())),
+        )
+        .root(vm.space());
+        let mut raw = Self::frozen_array(
+            &mut vm,
+            quasis.iter().map(|quasi| Some(quasi.raw.value.to_string())),
+        )
+        .root(vm.space());
+        raw.prevent_extensions();
+        let mut cooked = *cooked;
+        let name = vm.intern("raw");
+        let _ = cooked.define_own_property(
+            &mut vm,
+            name,
+            &*DataDescriptor::new(JsValue::new(*raw), NONE),
+            false,
+        );
+        cooked.prevent_extensions();
+        let ix = self.builder.code.literals.len();
+        self.builder.code.literals.push(JsValue::new(cooked));
+        ix as _
+    }
+
+    /// Array of `strings` with read-only elements and `length`. `None` elements are
+    /// `undefined`. The array is still extensible.
+    fn frozen_array(
+        vm: &mut VirtualMachineRef,
+        strings: impl ExactSizeIterator<Item = Option<String>>,
+    ) -> Gc<JsObject> {
+        let mut array = JsArray::new(vm, strings.len() as u32).root(vm.space());
+        for (ix, string) in strings.enumerate() {
+            let val = match string {
+                Some(string) => JsValue::new(JsString::new(vm, string)),
+                None => JsValue::undefined(),
+            };
+            let _ = array.define_own_property(
+                vm,
+                Symbol::Indexed(ix as _),
+                &*DataDescriptor::new(val, E),
+                false,
+            );
+        }
+        let len = JsValue::new(array.elements.length());
+        let _ = array.define_own_property(
+            vm,
+            Symbol::length(),
+            &*DataDescriptor::new(len, NONE),
+            false,
+        );
+        *array
+    }
+
     pub fn emit_lit(&mut self, lit: &Lit) {
         match lit {
             Lit::Null(_) => self.builder.emit(Op::OP_PUSH_NULL, &[], false),
@@ -1038,47 +1769,1012 @@ impl Compiler {
             _ => todo!("Other literals"),
         }
     }
+    /// Declare `let`, `const` and `class` bindings of `stmts` in the current scope. The bindings
+    /// hold the empty value until their declaration runs, accessing them before that throws a
+    /// ReferenceError.
+    fn declare_lexical_bindings(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            match stmt {
+                Stmt::Decl(Decl::Var(var)) => self.declare_uninitialized(var),
+                Stmt::Decl(Decl::Class(class)) => {
+                    let ix = self.get_ident(&class.ident);
+                    self.builder.emit(Op::OP_PUSH_EMPTY, &[], false);
+                    self.builder.emit(Op::OP_DECL_LET, &[ix], true);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Declare names bound by `let` or `const` declaration `var` as uninitialized bindings.
+    fn declare_uninitialized(&mut self, var: &VarDecl) {
+        if var.kind == VarDeclKind::Var {
+            return;
+        }
+        for name in self.bound_names(var) {
+            let ix = self.builder.get_sym(name);
+            self.builder.emit(Op::OP_PUSH_EMPTY, &[], false);
+            self.builder.emit(Op::OP_DECL_LET, &[ix], true);
+        }
+    }
+
+    /// Names bound by all declarators of `var`.
+    fn bound_names(&mut self, var: &VarDecl) -> Vec<Symbol> {
+        let mut names = vec![];
+        for decl in var.decls.iter() {
+            let ids: Vec<Id> = find_ids(&decl.name);
+            names.extend(ids.iter().map(|id| self.intern_str(&id.0)));
+        }
+        names
+    }
+
+    /// Replace the current scope with a new one holding copies of bindings `names`. Used for
+    /// per-iteration bindings of `for (let ...)` loops.
+    fn emit_copy_bindings(&mut self, names: &[Symbol]) {
+        if names.is_empty() {
+            return;
+        }
+        for &name in names.iter() {
+            let ix = self.builder.get_sym(name);
+            self.builder.emit(Op::OP_GET_VAR, &[ix], true);
+        }
+        self.builder.emit(Op::OP_POP_SCOPE, &[], false);
+        self.builder.emit(Op::OP_PUSH_SCOPE, &[], false);
+        for &name in names.iter().rev() {
+            let ix = self.builder.get_sym(name);
+            self.builder.emit(Op::OP_DECL_LET, &[ix], true);
+        }
+    }
+
     pub fn emit_var_decl(&mut self, var: &VarDecl) {
         for decl in var.decls.iter() {
-            match &decl.name {
-                Pat::Ident(name) => match decl.init {
-                    Some(ref init) => {
-                        let s: &str = &name.id.sym;
-                        let name = self.vm.intern_or_known_symbol(s);
+            match decl.init {
+                Some(ref init) => self.emit(init, true),
+                // `var x;` keeps the current value of `x`
+                None if var.kind == VarDeclKind::Var => continue,
+                None => self.builder.emit(Op::OP_PUSH_UNDEFINED, &[], false),
+            }
+            self.generate_pat_store(
+                &decl.name,
+                var.kind != VarDeclKind::Var,
+                var.kind == VarDeclKind::Let,
+            );
+        }
+    }
+
+    /// Push the home object of the method being compiled, see `Compiler::home`.
+    fn emit_home_object(&mut self) {
+        let home = self.home.unwrap_or_else(Symbol::home_object);
+        let ix = self.builder.get_sym(home);
+        self.builder.emit(Op::OP_GET_VAR, &[ix], true);
+    }
+
+    /// Names of plain and rest parameters of a function.
+    ///
+    /// Parameters with a pattern or a default value are bound to a hidden name first. These
+    /// are returned with their patterns, `emit_param_patterns` destructures them in the
+    /// function prologue.
+    #[allow(clippy::type_complexity)]
+    fn function_params<'a>(
+        &mut self,
+        pats: &[&'a Pat],
+    ) -> (Vec<Symbol>, Option<Symbol>, Vec<(Symbol, &'a Pat)>) {
+        let mut rest = None;
+        let mut params = vec![];
+        let mut patterns = vec![];
+        for (ix, &pat) in pats.iter().enumerate() {
+            match pat {
+                Pat::Ident(ref x) => params.push(self.intern(&x.id)),
+                Pat::Rest(ref r) => match &*r.arg {
+                    Pat::Ident(ref id) => {
+                        rest = Some(self.intern(&id.id));
+                    }
+                    pat => {
+                        let name = self.vm.intern(format!("[[Parameter{}]]", ix));
+                        rest = Some(name);
+                        patterns.push((name, pat));
+                    }
+                },
+                pat => {
+                    // not a valid identifier so user code can not refer to it.
+                    let name = self.vm.intern(format!("[[Parameter{}]]", ix));
+                    params.push(name);
+                    patterns.push((name, pat));
+                }
+            }
+        }
+        (params, rest, patterns)
+    }
+
+    /// Bind parameters returned in patterns of `function_params`. Names bound by the patterns
+    /// are declared as variables of the function.
+    fn emit_param_patterns(&mut self, patterns: &[(Symbol, &Pat)]) {
+        for (param, pat) in patterns.iter() {
+            let ids: Vec<Id> = find_ids(*pat);
+            for id in ids {
+                let name = self.intern_str(&id.0);
+                if !self.builder.code.var_names.contains(&name) {
+                    self.builder.code.var_names.push(name);
+                }
+            }
+            let ix = self.builder.get_sym(*param);
+            self.builder.emit(Op::OP_GET_VAR, &[ix], true);
+            self.generate_pat_store(pat, false, false);
+        }
+    }
+
+    /// Name of property key `key` or `None` if it is computed.
+    fn prop_name(&mut self, key: &PropName) -> Option<Symbol> {
+        match key {
+            PropName::Ident(ref id) => Some(self.intern(id)),
+            PropName::Str(ref s) => Some(self.intern_str(&s.value)),
+            PropName::Num(n) => {
+                let val = n.value;
+                if val as u32 as f64 == val {
+                    Some(Symbol::Indexed(val as u32))
+                } else {
+                    Some(self.intern_str(&val.to_string()))
+                }
+            }
+            PropName::Computed(_) => None,
+            PropName::BigInt(_) => {
+                self.syntax_error("BigInt property keys are not supported");
+                None
+            }
+        }
+    }
+
+    /// Compile a class constructor, a method or an accessor into a code block of the current
+    /// code and return its index for `OP_GET_FUNCTION`. `body` emits the function body, class
+    /// code is always strict.
+    fn compile_method(
+        &mut self,
+        name: Symbol,
+        params: &[Symbol],
+        rest: Option<Symbol>,
+        home: Symbol,
+        body: impl FnOnce(&mut Compiler),
+    ) -> u32 {
+        let code = ByteCode::new(&mut self.vm, name, params, true);
+        let mut code = Handle::new(self.vm.space(), code);
+        code.rest_param = rest;
+        let mut compiler = Compiler {
+            lci: Vec::new(),
+            builder: ByteCodeBuilder {
+                code: *code,
+                val_map: Default::default(),
+                name_map: Default::default(),
+            },
+            fmap: Default::default(),
+            vm: self.vm,
+            scope_depth: 0,
+            try_depth: 0,
+            labels: vec![],
+            home: Some(home),
+            error: None,
+        };
+        body(&mut compiler);
+        compiler.builder.emit(Op::OP_PUSH_UNDEFINED, &[], false);
+        compiler.builder.emit(Op::OP_RET, &[], false);
+        compiler.builder.finish(&mut self.vm);
+        self.take_error(compiler);
+        let ix = self.builder.code.codes.len();
+        self.builder.code.codes.push(*code);
+        ix as _
+    }
+
+    /// Compile method, getter or setter with key `key` like `compile_method`. Members with
+    /// a computed key get their name when they are defined, see `emit_define_member`.
+    fn compile_member(
+        &mut self,
+        key: &PropName,
+        kind: MethodKind,
+        pats: &[&Pat],
+        body: Option<&BlockStmt>,
+        home: Symbol,
+    ) -> u32 {
+        let name = match (self.prop_name(key), kind) {
+            (None, _) => self.intern_str("<anonymous>"),
+            (Some(name), MethodKind::Method) => name,
+            (Some(name), MethodKind::Getter) => {
+                let name = format!("get {}", self.vm.description(name));
+                self.intern_str(&name)
+            }
+            (Some(name), MethodKind::Setter) => {
+                let name = format!("set {}", self.vm.description(name));
+                self.intern_str(&name)
+            }
+        };
+        let (params, rest, patterns) = self.function_params(pats);
+        let ix = self.compile_method(name, &params, rest, home, |compiler| {
+            compiler.emit_param_patterns(&patterns);
+            if let Some(body) = body {
+                compiler.compile(&body.stmts);
+            }
+        });
+        let mut code = self.builder.code.codes[ix as usize];
+        code.is_method = true;
+        ix
+    }
+
+    /// Emit code defining member `ix` compiled by `compile_member` on the object bound to
+    /// `home`.
+    fn emit_define_member(
+        &mut self,
+        ix: u32,
+        key: &PropName,
+        kind: MethodKind,
+        home: Symbol,
+        enumerable: bool,
+    ) {
+        match kind {
+            MethodKind::Method => self.builder.emit(Op::OP_GET_FUNCTION, &[ix], false),
+            MethodKind::Getter => {
+                self.builder.emit(Op::OP_GET_FUNCTION, &[ix], false);
+                self.builder.emit(Op::OP_PUSH_UNDEFINED, &[], false);
+            }
+            MethodKind::Setter => {
+                self.builder.emit(Op::OP_PUSH_UNDEFINED, &[], false);
+                self.builder.emit(Op::OP_GET_FUNCTION, &[ix], false);
+            }
+        }
+        let name = self.prop_name(key);
+        if let PropName::Computed(ref key) = key {
+            self.emit(&key.expr, true);
+        }
+        let target = self.builder.get_sym(home);
+        self.builder.emit(Op::OP_GET_VAR, &[target], true);
+        let enumerable = enumerable as u32;
+        match (kind, name) {
+            (MethodKind::Method, Some(name)) => {
+                let ix = self.builder.get_sym(name);
+                self.builder
+                    .emit(Op::OP_DEFINE_METHOD_BY_ID, &[ix, enumerable], false);
+            }
+            (MethodKind::Method, None) => {
+                self.builder
+                    .emit(Op::OP_DEFINE_METHOD, &[enumerable], false)
+            }
+            (_, Some(name)) => {
+                let ix = self.builder.get_sym(name);
+                self.builder
+                    .emit(Op::OP_SET_GETTER_SETTER_BY_ID, &[ix, enumerable], false);
+            }
+            (_, None) => self
+                .builder
+                .emit(Op::OP_SET_GETTER_SETTER, &[enumerable], false),
+        }
+    }
+
+    /// Emit code defining property `prop` of an object literal. The object is on top of the
+    /// stack and stays there. Unlike class members, methods and accessors are strict only in
+    /// strict code.
+    fn emit_object_prop(&mut self, prop: &Prop) {
+        let home = Symbol::home_object();
+        match prop {
+            Prop::Shorthand(ident) => {
+                self.builder.emit(Op::OP_DUP, &[], false);
+                let ix = self.intern(ident);
+                let sym = self.builder.get_sym(ix);
+                self.builder.emit(Op::OP_GET_VAR, &[sym], true);
+                self.builder.emit(Op::OP_SWAP, &[], false);
+                self.builder.emit(Op::OP_SET_PROP, &[sym], true);
+            }
+            Prop::KeyValue(prop) => {
+                self.builder.emit(Op::OP_DUP, &[], false);
+                match self.prop_name(&prop.key) {
+                    // `__proto__: value` sets the prototype unless the key is computed
+                    Some(name) if self.vm.description(name) == "__proto__" => {
+                        self.emit(&prop.value, true);
+                        self.builder.emit(Op::OP_SET_PROTO, &[], false);
+                    }
+                    Some(name) => {
+                        self.emit_named(&prop.value, name);
+                        self.builder.emit(Op::OP_SWAP, &[], false);
                         let ix = self.builder.get_sym(name);
-                        self.emit(init, true);
-                        match var.kind {
-                            VarDeclKind::Let => self.builder.emit(Op::OP_DECL_LET, &[ix], true),
-                            VarDeclKind::Const => {
-                                self.builder.emit(Op::OP_DECL_IMMUTABLE, &[ix], true)
-                            }
-                            VarDeclKind::Var => {
-                                self.builder.emit(Op::OP_SET_VAR, &[ix], true);
-                            }
-                        }
+                        self.builder.emit(Op::OP_SET_PROP, &[ix], true);
                     }
                     None => {
-                        let s: &str = &name.id.sym;
-                        let name = self.vm.intern_or_known_symbol(s);
-                        let ix = self.builder.get_sym(name);
-                        self.builder.emit(Op::OP_PUSH_UNDEFINED, &[], false);
-                        match var.kind {
-                            VarDeclKind::Let => self.builder.emit(Op::OP_DECL_LET, &[ix], true),
-                            VarDeclKind::Const => {
-                                self.builder.emit(Op::OP_DECL_IMMUTABLE, &[ix], true)
-                            }
-                            VarDeclKind::Var => {}
-                        }
-                        self.builder.emit(Op::OP_SET_VAR, &[ix], true);
+                        self.emit_prop_key(&prop.key);
+                        self.emit(&prop.value, true);
+                        self.builder.emit(Op::OP_DEFINE_PROP, &[], false);
                     }
-                },
-                _ => todo!(),
+                }
+            }
+            Prop::Method(method) => {
+                let pats = method
+                    .function
+                    .params
+                    .iter()
+                    .map(|param| &param.pat)
+                    .collect::<Vec<_>>();
+                let ix = self.compile_member(
+                    &method.key,
+                    MethodKind::Method,
+                    &pats,
+                    method.function.body.as_ref(),
+                    home,
+                );
+                let mut code = self.builder.code.codes[ix as usize];
+                code.strict =
+                    self.builder.code.strict || is_strict_body(method.function.body.as_ref());
+                self.set_function_kind(
+                    code,
+                    method.function.is_generator,
+                    method.function.is_async,
+                );
+                self.emit_define_member(ix, &method.key, MethodKind::Method, home, true);
+            }
+            Prop::Getter(getter) => {
+                let ix = self.compile_member(
+                    &getter.key,
+                    MethodKind::Getter,
+                    &[],
+                    getter.body.as_ref(),
+                    home,
+                );
+                let mut code = self.builder.code.codes[ix as usize];
+                code.strict = self.builder.code.strict || is_strict_body(getter.body.as_ref());
+                self.emit_define_member(ix, &getter.key, MethodKind::Getter, home, true);
             }
+            Prop::Setter(setter) => {
+                let ix = self.compile_member(
+                    &setter.key,
+                    MethodKind::Setter,
+                    &[&setter.param],
+                    setter.body.as_ref(),
+                    home,
+                );
+                let mut code = self.builder.code.codes[ix as usize];
+                code.strict = self.builder.code.strict || is_strict_body(setter.body.as_ref());
+                self.emit_define_member(ix, &setter.key, MethodKind::Setter, home, true);
+            }
+            Prop::Assign(_) => unreachable!("assignment property outside of a pattern"),
+        }
+    }
+
+    /// Emit `expr` giving anonymous functions the name `name`, like `f` in `{ f: () => {} }`.
+    fn emit_named(&mut self, expr: &Expr, name: Symbol) {
+        let anonymous = match expr {
+            Expr::Fn(fun) => fun.ident.is_none(),
+            Expr::Arrow(_) => true,
+            _ => false,
+        };
+        let ix = self.builder.code.codes.len();
+        self.emit(expr, true);
+        if anonymous {
+            let mut code = self.builder.code.codes[ix];
+            code.name = name;
+        }
+    }
+
+    /// Emit code creating class `class` and leaving its constructor on the stack.
+    ///
+    /// Class body is compiled in its own scope holding the class name `ident`, the constructor
+    /// and the prototype object. The latter two are hidden bindings that `super` and member
+    /// definitions look up.
+    pub fn emit_class(&mut self, class: &Class, ident: Option<&Ident>) {
+        let name = match ident {
+            Some(ident) => self.intern(ident),
+            None => self.intern_str("<anonymous>"),
+        };
+        let derived = class.super_class.is_some();
+        self.push_scope();
+        match class.super_class {
+            Some(ref super_class) => self.emit(super_class, true),
+            None => self.builder.emit(Op::OP_PUSH_EMPTY, &[], false),
+        }
+
+        let ctor = class.body.iter().find_map(|member| match member {
+            ClassMember::Constructor(ctor) => Some(ctor),
+            _ => None,
+        });
+        let ix = match ctor {
+            Some(ctor) => {
+                let pats = ctor
+                    .params
+                    .iter()
+                    .map(|param| match param {
+                        ParamOrTsParamProp::Param(param) => &param.pat,
+                        ParamOrTsParamProp::TsParamProp(_) => unreachable!(),
+                    })
+                    .collect::<Vec<_>>();
+                let (params, rest, patterns) = self.function_params(&pats);
+                self.compile_method(name, &params, rest, Symbol::home_object(), |compiler| {
+                    compiler.emit_param_patterns(&patterns);
+                    if let Some(ref body) = ctor.body {
+                        compiler.compile(&body.stmts);
+                    }
+                })
+            }
+            None if derived => {
+                // constructor(...args) { super(...args); }
+                let args = self.intern_str("args");
+                self.compile_method(name, &[], Some(args), Symbol::home_object(), |compiler| {
+                    compiler.builder.emit(Op::OP_PUSH_EMPTY, &[], false);
+                    let args = compiler.builder.get_sym(args);
+                    compiler.builder.emit(Op::OP_GET_VAR, &[args], true);
+                    compiler.builder.emit(Op::OP_SPREAD_ARR, &[], false);
+                    let ctor = compiler.builder.get_sym(Symbol::class_constructor());
+                    compiler.builder.emit(Op::OP_GET_VAR, &[ctor], true);
+                    compiler.builder.emit(Op::OP_SUPER_CALL, &[1], false);
+                    compiler.builder.emit(Op::OP_DROP, &[], false);
+                })
+            }
+            None => self.compile_method(name, &[], None, Symbol::home_object(), |_| {}),
+        };
+        let mut code = self.builder.code.codes[ix as usize];
+        code.class_constructor = true;
+        code.derived = derived;
+
+        self.builder.emit(Op::OP_GET_FUNCTION, &[ix], false);
+        self.builder.emit(Op::OP_CREATE_CLASS, &[], false);
+        let proto = self.builder.get_sym(Symbol::home_object());
+        self.builder.emit(Op::OP_DECL_IMMUTABLE, &[proto], true);
+        if let Some(ident) = ident {
+            let ix = self.get_ident(ident);
+            self.builder.emit(Op::OP_DUP, &[], false);
+            self.builder.emit(Op::OP_DECL_IMMUTABLE, &[ix], true);
         }
+        let ctor = self.builder.get_sym(Symbol::class_constructor());
+        self.builder.emit(Op::OP_DECL_IMMUTABLE, &[ctor], true);
+
+        for member in class.body.iter() {
+            let method = match member {
+                ClassMember::Method(method) => method,
+                ClassMember::Constructor(_) | ClassMember::Empty(_) => continue,
+                _ => {
+                    // fields, private members and static blocks
+                    self.syntax_error("unsupported class member");
+                    continue;
+                }
+            };
+            // static methods are defined on the constructor which is also their home object
+            let home = if method.is_static {
+                Symbol::class_constructor()
+            } else {
+                Symbol::home_object()
+            };
+            let pats = method
+                .function
+                .params
+                .iter()
+                .map(|param| &param.pat)
+                .collect::<Vec<_>>();
+            let ix = self.compile_member(
+                &method.key,
+                method.kind,
+                &pats,
+                method.function.body.as_ref(),
+                home,
+            );
+            let code = self.builder.code.codes[ix as usize];
+            self.set_function_kind(code, method.function.is_generator, method.function.is_async);
+            self.emit_define_member(ix, &method.key, method.kind, home, false);
+        }
+
+        self.builder.emit(Op::OP_GET_VAR, &[ctor], true);
+        self.pop_scope();
+    }
+}
+/// Jumps skipping the rest of an optional chain, see `Compiler::emit_chain`.
+type ChainJumps = Vec<(Box<dyn FnOnce(&mut Compiler)>, u32)>;
+
+/// Whether `expr` is a member or call chain with an optional `?.` link.
+fn has_optional(expr: &Expr) -> bool {
+    match expr {
+        Expr::OptChain(_) => true,
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(obj),
+            ..
+        }) => has_optional(obj),
+        Expr::Call(CallExpr {
+            callee: ExprOrSuper::Expr(callee),
+            ..
+        }) => has_optional(callee),
+        _ => false,
     }
 }
+
+/// Whether function body `body` starts with a `"use strict"` directive.
+fn is_strict_body(body: Option<&BlockStmt>) -> bool {
+    body.and_then(|body| body.stmts.first())
+        .is_some_and(|stmt| stmt.is_use_strict())
+}
+
+/// Early error message for a `break` or `continue` statement `keyword` without a target.
+fn jump_error(keyword: &str, label: &Option<Ident>) -> String {
+    match label {
+        Some(label) => format!("Undefined label '{}'", label.sym),
+        None => format!("Illegal {} statement", keyword),
+    }
+}
+
 unsafe impl Trace for Compiler {
     fn trace(&self, tracer: &mut dyn Tracer) {
         self.builder.trace(tracer);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{eval, eval_ok};
+
+    const ITERABLE: &str = "
+        var closed = 0;
+        var iterable = {};
+        iterable[Symbol.iterator] = function () {
+            var i = 0;
+            return {
+                next: function () { i = i + 1; return { value: i, done: i > 3 }; },
+                return: function () { closed = closed + 1; return {}; }
+            };
+        };";
+
+    #[test]
+    fn test_for_of_closes_iterator_on_throw() {
+        let source = format!(
+            "{} var seen = 0;
+            try {{ for (var x of iterable) {{ seen = x; throw 'boom'; }} }} catch (e) {{ seen += e; }}
+            result = seen + ',' + closed;",
+            ITERABLE
+        );
+        assert_eq!(eval_ok(&source), "1boom,1");
+    }
+
+    #[test]
+    fn test_for_of_closes_iterator_on_break() {
+        let source = format!(
+            "{} var sum = 0;
+            for (var x of iterable) {{ sum += x; if (x == 2) break; }}
+            result = sum + ',' + closed;",
+            ITERABLE
+        );
+        assert_eq!(eval_ok(&source), "3,1");
+    }
+
+    #[test]
+    fn test_for_of_closes_iterator_on_return() {
+        let source = format!(
+            "{} function f() {{
+                switch (1) {{ case 1: for (var x of iterable) {{ if (x == 2) return x * 10; }} }}
+            }}
+            result = f() + ',' + closed;",
+            ITERABLE
+        );
+        assert_eq!(eval_ok(&source), "20,1");
+    }
+
+    #[test]
+    fn test_for_of_does_not_close_exhausted_iterator() {
+        let source = format!(
+            "{} var sum = 0;
+            for (var x of iterable) sum += x;
+            result = sum + ',' + closed;",
+            ITERABLE
+        );
+        assert_eq!(eval_ok(&source), "6,0");
+    }
+
+    #[test]
+    fn test_async_generator_is_syntax_error() {
+        let error = eval("async function* g() { yield 1; }").unwrap_err();
+        assert_eq!(error, "SyntaxError: async generators are not supported");
+        let error = eval("var o = { async *m() {} };").unwrap_err();
+        assert_eq!(error, "SyntaxError: async generators are not supported");
+    }
+
+    #[test]
+    fn test_unsupported_class_member_is_syntax_error() {
+        for source in &[
+            "class A { x = 1; }",
+            "class A { static y; }",
+            "class A { #p = 1; }",
+            "class A { #m() {} }",
+        ] {
+            let error = eval(source).unwrap_err();
+            assert_eq!(error, "SyntaxError: unsupported class member", "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_bigint_key_is_syntax_error() {
+        for source in &[
+            "var o = { 1n: 2 };",
+            "var { 1n: a } = {};",
+            "class A { 1n() {} }",
+        ] {
+            let error = eval(source).unwrap_err();
+            assert_eq!(
+                error, "SyntaxError: BigInt property keys are not supported",
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_for_of_rethrows_body_exception() {
+        let source = format!("{} for (var x of iterable) throw 'boom';", ITERABLE);
+        assert_eq!(eval(&source), Err("boom".to_string()));
+    }
+
+    #[test]
+    fn test_switch_falls_through_until_break() {
+        let source = "var r = '';
+            for (var i = 0; i < 5; i = i + 1) {
+                switch (i) {
+                    case 0: r += 'a';
+                    case 1: r += 'b'; break;
+                    case 3: r += 'd'; break;
+                    default: r += 'x';
+                }
+            }
+            result = r;";
+        assert_eq!(eval_ok(source), "abbxdx");
+    }
+
+    #[test]
+    fn test_switch_default_before_cases() {
+        let source = "var r = '';
+            switch ('k') { default: r += 'd'; case 'z': r += 'z'; break; case 'y': r += 'y'; }
+            switch (2) { case 1: r += 1; }
+            result = r;";
+        assert_eq!(eval_ok(source), "dz");
+    }
+
+    #[test]
+    fn test_switch_compares_strictly_and_evaluates_cases_in_order() {
+        let source = "var calls = '';
+            function k(x) { calls += x; return x; }
+            function f(v) { switch (v) { case 1: return 'one'; case '1': return 'str'; } return 'none'; }
+            switch (2) { case k(1): break; case k(2): break; case k(3): break; }
+            result = f(1) + f('1') + f(2) + calls;";
+        assert_eq!(eval_ok(source), "onestrnone12");
+    }
+
+    #[test]
+    fn test_continue_inside_switch_continues_loop() {
+        let source = "var r = '';
+            for (var i = 0; i < 3; i = i + 1) { switch (i) { case 1: continue; } r += i; }
+            result = r;";
+        assert_eq!(eval_ok(source), "02");
+    }
+
+    #[test]
+    fn test_do_while_runs_body_first() {
+        let source = "var i = 0, r = '';
+            do { r += i; i = i + 1; } while (i < 3);
+            do { r += 'once'; } while (false);
+            do { i = i + 1; if (i == 5) continue; r += i; } while (i < 6);
+            result = r;";
+        assert_eq!(eval_ok(source), "012once46");
+    }
+
+    #[test]
+    fn test_labelled_break_and_continue() {
+        let source = "var r = '';
+            outer: for (var i = 0; i < 3; i = i + 1) {
+                for (var j = 0; j < 3; j = j + 1) {
+                    if (j == 1) continue outer;
+                    if (i == 2) break outer;
+                    r += i + '' + j;
+                }
+            }
+            block: { r += 'a'; break block; r += 'b'; }
+            var n = 0;
+            loop: do { n = n + 1; while (true) { if (n < 3) continue loop; break loop; } } while (true);
+            result = r + n;";
+        assert_eq!(eval_ok(source), "0010a3");
+    }
+
+    #[test]
+    fn test_jump_without_target_is_syntax_error() {
+        let cases = [
+            ("l: while (true) { break m; }", "Undefined label 'm'"),
+            ("l: { continue l; }", "Undefined label 'l'"),
+            ("function f() { break; }", "Illegal break statement"),
+            (
+                "for (;;) { (function () { continue; }); break; }",
+                "Illegal continue statement",
+            ),
+        ];
+        for (source, message) in cases.iter() {
+            assert_eq!(eval(source), Err(format!("SyntaxError: {}", message)));
+        }
+    }
+
+    #[test]
+    fn test_class_members() {
+        let source = "class A {
+                constructor(x) { this.x = x; }
+                get dbl() { return this.x * 2; }
+                set v(x) { this.x = x + 1; }
+                m() { return 'A' + this.x; }
+                static make() { return new A(5); }
+                static get g() { return 3; }
+            }
+            var a = new A(1);
+            a.v = 1;
+            result = A.make().dbl + ' ' + a.m() + ' ' + A.g + ' ' + typeof class {};";
+        assert_eq!(eval_ok(source), "10 A2 3 function");
+    }
+
+    #[test]
+    fn test_class_extends_and_super() {
+        let source = "class A {
+                constructor(x) { this.x = x; }
+                m() { return 'A'; }
+                static s() { return 'sA'; }
+            }
+            class B extends A {
+                constructor() { super(7); this.y = 1; }
+                m() { return super.m() + 'B'; }
+                static s() { return super.s() + 'B'; }
+            }
+            class C extends A {}
+            var b = new B();
+            result = b.x + b.y + ' ' + b.m() + B.s() + ' ' + new C(4).x + ' ' +
+                (Object.getPrototypeOf(B) === A) + (Object.getPrototypeOf(B.prototype) === A.prototype);";
+        assert_eq!(eval_ok(source), "8 ABsAB 4 truetrue");
+    }
+
+    #[test]
+    fn test_class_expression_name_is_local() {
+        let source = "var C = class Named { who() { return typeof Named; } };
+            result = new C().who();
+            try { result += Named; } catch (e) { result += e.name; }";
+        assert_eq!(eval_ok(source), "functionReferenceError");
+    }
+
+    #[test]
+    fn test_class_errors() {
+        let cases = [
+            "class A {} A();",
+            "class B extends 5 {}",
+            "class B extends Object { constructor() { this.x = 1; } } new B();",
+        ];
+        let names = ["TypeError", "TypeError", "ReferenceError"];
+        for (source, name) in cases.iter().zip(names.iter()) {
+            let source = format!("try {{ {} }} catch (e) {{ result = e.name; }}", source);
+            assert_eq!(eval_ok(&source), *name, "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_class_extends_native_constructors() {
+        let source = "class E extends Error {
+                constructor(m) { super(m); this.name = 'E'; }
+                who() { return 'e'; }
+            }
+            class L extends Array { sum() { return this[0] + this[1]; } }
+            class P extends Promise {}
+            var e = new E('boom');
+            var l = new L(2, 3);
+            var p = new P(function (r) { r(1); });
+            result = [E.prototype.isPrototypeOf(e), e instanceof Error, String(e), e.who(),
+                l instanceof L, l.sum(), l.length, Array.isArray(l),
+                p instanceof P, typeof p.then].join();";
+        assert_eq!(
+            eval_ok(source),
+            "true,true,E: boom,e,true,5,2,true,true,function"
+        );
+    }
+
+    #[test]
+    fn test_super_property_assignment() {
+        let source = "var log = [];
+            class A {
+                set s(v) { log.push('setter ' + v + ' ' + (this instanceof B)); }
+            }
+            class B extends A {
+                set plain(v) { log.push('own setter'); }
+                m() {
+                    super.s = 1;
+                    super.plain = 2;
+                    super['x'] = 3;
+                    super.x = this.x + (super.x === undefined);
+                    return this.plain === 2 && Object.getOwnPropertyNames(this).join();
+                }
+            }
+            var b = new B();
+            var r = b.m();
+            result = log.join('|') + ' ' + r + ' ' + b.x + ' ' + A.prototype.hasOwnProperty('x');";
+        assert_eq!(eval_ok(source), "setter 1 true plain,x 4 false");
+    }
+
+    #[test]
+    fn test_template_literals() {
+        let source = "var a = 1, b = 'x';
+            var o = { toString: function () { return 'obj'; } };
+            result = `a${a + 1}b${b}c` + `` + `${o}${null}${undefined}${[1, 2]}` +
+                `${`nested ${1 + 1}`}` + `line1
+line2`.length;";
+        assert_eq!(eval_ok(source), "a2bxcobjnullundefined1,2nested 211");
+        assert_eq!(
+            eval_ok("try { `${Symbol()}`; } catch (e) { result = e.name; }"),
+            "TypeError"
+        );
+    }
+
+    #[test]
+    fn test_tagged_template_strings() {
+        let source = "function tag(strings, x, y) {
+                return strings.length + ':' + strings.join('|') + ':' + x + y + ':' +
+                    strings.raw.join('|');
+            }
+            result = tag`p\\n${1}q${2}r`;";
+        assert_eq!(eval_ok(source), "3:p\n|q|r:12:p\\n|q|r");
+    }
+
+    #[test]
+    fn test_tagged_template_object_is_frozen_and_cached() {
+        let source = "function tag(s) { return s; }
+            function f() { return tag`a${1}b`; }
+            var s = f();
+            s.x = 1;
+            s.raw[0] = 'z';
+            result = (f() === s) + ' ' + Object.isFrozen(s) + Object.isFrozen(s.raw) + ' ' +
+                s.x + s.raw[0];";
+        assert_eq!(eval_ok(source), "true truetrue undefineda");
+    }
+
+    #[test]
+    fn test_tagged_template_member_tag_gets_this() {
+        let source = "var o = { m: function (s, v) { return this.k + s[0] + v; }, k: 'K' };
+            result = o.m`a${1}`;";
+        assert_eq!(eval_ok(source), "Ka1");
+    }
+
+    #[test]
+    fn test_tagged_template_substitution_order() {
+        let source = "var log = '';
+            function f(x) { log += x; return x; }
+            function tag(s, a, b, c) { return [s.length, a, b, c].join(); }
+            function one(s, a) { return a; }
+            result = tag`${f(1)}-${f(2)}-${f(3)}` + ' ' + log + ' ' + one`x${f(4)}y` + ' ' + log;";
+        assert_eq!(eval_ok(source), "4,1,2,3 123 4 1234");
+    }
+
+    #[test]
+    fn test_array_destructuring() {
+        let source = "var [a, , b = 5, ...rest] = [1, 2, undefined, 4, 6];
+            let [p, q] = 'hi';
+            var [first, ...[second]] = [1, 2, 3];
+            result = a + ',' + b + ',' + rest.join('|') + ',' + p + q + ',' + second;";
+        assert_eq!(eval_ok(source), "1,5,4|6,hi,2");
+    }
+
+    #[test]
+    fn test_object_destructuring() {
+        let source = "var key = 'dyn';
+            var { x, y: { z }, w = 3, [key]: d, ...others } = { x: 1, y: { z: 2 }, dyn: 'D', p: 4 };
+            const { length } = 'abc';
+            var { a: { b } = { b: 'def' } } = {};
+            result = x + ',' + z + ',' + w + ',' + d + ',' + Object.keys(others).join('|') + ',' +
+                length + ',' + b;";
+        assert_eq!(eval_ok(source), "1,2,3,D,p,3,def");
+    }
+
+    #[test]
+    fn test_destructuring_parameters_and_assignment() {
+        let source = "function f({ a, b = 2 }, [c, d] = [3, 4]) { return a + b + c + d; }
+            var a = 1, b = 2;
+            [a, b] = [b, a];
+            var o = {};
+            ({ k: o.k, m: a } = { k: 'K', m: 9 });
+            var s = '';
+            for (var [k, v] of [[1, 2], [3, 4]]) s += k + v;
+            result = f({ a: 1 }) + ',' + a + ',' + b + ',' + o.k + ',' + s;";
+        assert_eq!(eval_ok(source), "10,9,1,K,37");
+    }
+
+    #[test]
+    fn test_array_destructuring_closes_iterator() {
+        let source = "var calls = 0;
+            var it = {};
+            it[Symbol.iterator] = function () {
+                return {
+                    next: function () { calls = calls + 1; return { value: calls, done: false }; },
+                    return: function () { result = 'closed' + calls; return {}; }
+                };
+            };
+            var [m, n] = it;";
+        assert_eq!(eval_ok(source), "closed2");
+    }
+
+    #[test]
+    fn test_destructuring_null_is_type_error() {
+        assert_eq!(
+            eval_ok("try { var { a } = null; } catch (e) { result = e.name; }"),
+            "TypeError"
+        );
+    }
+
+    #[test]
+    fn test_object_literal_keys() {
+        let source = "var k = 'dyn', x = 1, y = 2, s = Symbol('s');
+            var o = { [k + 1]: 1, ['a' + 'b']: 2, x, y, [s]: 5, 1: 'a', b: 'b', 0: 'z', b: 'c' };
+            result = o.dyn1 + o.ab + o.x + o.y + ' ' + o[s] + ' ' + Object.keys(o).join() + ' ' + o.b;";
+        assert_eq!(eval_ok(source), "6 5 0,1,dyn1,ab,x,y,b c");
+    }
+
+    #[test]
+    fn test_object_literal_methods_and_accessors() {
+        let source = "var base = { hi() { return 'base'; } };
+            var o = {
+                __proto__: base,
+                _v: 1,
+                get v() { return this._v * 10; },
+                set v(n) { this._v = n; },
+                hi() { return super.hi() + '!'; },
+                *gm() { yield 2; }
+            };
+            o.v = 3;
+            result = o.v + ' ' + o.hi() + ' ' + o.gm().next().value;";
+        assert_eq!(eval_ok(source), "30 base! 2");
+    }
+
+    #[test]
+    fn test_methods_are_not_constructors() {
+        let source = "var o = { m() {}, get g() { return 1; } };
+            class A { m() {} static s() {} }
+            var d = Object.getOwnPropertyDescriptor(o, 'g');
+            result = '';
+            var fns = [o.m, d.get, A.prototype.m, A.s];
+            for (var f of fns) {
+                try { new f(); } catch (e) { result += e.name + f.prototype + ' '; }
+            }";
+        assert_eq!(
+            eval_ok(source),
+            "TypeErrorundefined TypeErrorundefined TypeErrorundefined TypeErrorundefined "
+        );
+    }
+
+    #[test]
+    fn test_object_spread() {
+        let source = "var a = { p: 1, q: 2 };
+            var src = { get g() { return 'G'; } };
+            var b = { ...a, q: 3, ...null, ...undefined, ...'hi', ...src };
+            result = JSON.stringify(b) + ' ' + Object.getOwnPropertyDescriptor(b, 'g').value;";
+        assert_eq!(
+            eval_ok(source),
+            "{\"0\":\"h\",\"1\":\"i\",\"p\":1,\"q\":3,\"g\":\"G\"} G"
+        );
+    }
+
+    #[test]
+    fn test_optional_member_and_call() {
+        let source = "var o = { a: { b: 1 }, f() { return this.k; }, k: 'K' };
+            var n = null, calls = 0;
+            function side() { calls = calls + 1; return 1; }
+            result = o?.a?.b + ',' + o.x?.b + ',' + o.x?.b.c.d + ',' + n?.[side()] + ',' + calls +
+                ',' + o.f?.() + ',' + o.g?.() + ',' + o?.f() + ',' + n?.();";
+        assert_eq!(
+            eval_ok(source),
+            "1,undefined,undefined,undefined,0,K,undefined,K,undefined"
+        );
+    }
+
+    #[test]
+    fn test_parenthesized_chain_is_not_short_circuited() {
+        assert_eq!(
+            eval("var o = {}; result = (o?.a).b;"),
+            Err("TypeError: null or undefined has no properties".to_string())
+        );
+    }
+
+    #[test]
+    fn test_nullish_coalescing() {
+        let source = "var calls = 0;
+            function side() { calls = calls + 1; return 1; }
+            var o = { a: null };
+            var x = 0, y;
+            x ??= 5;
+            y ??= 6;
+            result = (null ?? 'd') + ',' + (0 ?? 'd') + ',' + ('' ?? 'd') + ',' +
+                (undefined ?? false) + ',' + (1 ?? side()) + calls + ',' + (o.a?.b ?? 'f') + ',' +
+                x + y;";
+        assert_eq!(eval_ok(source), "d,0,,false,10,f,06");
+    }
+}
@@ -3,8 +3,8 @@ use std::collections::HashMap;
 use swc_atoms::JsWord;
 use swc_common::DUMMY_SP;
 use swc_ecmascript::ast::{
-    ArrowExpr, BlockStmt, BlockStmtOrExpr, CatchClause, ClassDecl, ClassExpr, DoWhileStmt, Expr,
-    FnDecl, FnExpr, ForInStmt, ForOfStmt, ForStmt, Function, Ident, ImportDefaultSpecifier,
+    ArrowExpr, BlockStmt, BlockStmtOrExpr, CatchClause, Class, ClassDecl, ClassExpr, DoWhileStmt,
+    Expr, FnDecl, FnExpr, ForInStmt, ForOfStmt, ForStmt, Function, Ident, ImportDefaultSpecifier,
     ImportNamedSpecifier, ImportStarAsSpecifier, Invalid, Param, Pat, Program, Stmt, SwitchStmt,
     VarDecl, VarDeclKind, WhileStmt, WithStmt,
 };
@@ -325,4 +325,10 @@ impl<'a> Visit for VisitFnDecl<'a> {
     fn visit_fn_decl(&mut self, n: &FnDecl, _: &dyn Node) {
         (self.cb)(n);
     }
+
+    // Declarations inside function expressions, arrows and class bodies are hoisted when
+    // those are compiled.
+    fn visit_function(&mut self, _: &Function, _: &dyn Node) {}
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr, _: &dyn Node) {}
+    fn visit_class(&mut self, _: &Class, _: &dyn Node) {}
 }
@@ -53,7 +53,7 @@ pub unsafe trait Trace: Any {
     ///   - `Tracer::trace` already verifies that it owns the data, so you don't need to do that
     /// - Panicking
     ///   - This should be reserved for cases where you are seriously screwed up,
-    ///       and can't fulfill your contract to trace your interior properly.
+    ///     and can't fulfill your contract to trace your interior properly.
     ///   - This rule may change in future versions, depending on how we deal with multi-threading.
     /// ## Never Permitted Behavior
     /// - Forgetting a element of a collection, or field of a structure
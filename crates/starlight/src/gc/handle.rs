@@ -44,6 +44,7 @@ impl<T: Trace> Handle<T> {
             Self { inner: mem }
         }
     }
+    #[allow(clippy::mut_from_ref)]
     fn inner(&self) -> &mut HandleInner<T> {
         unsafe { &mut *self.inner }
     }
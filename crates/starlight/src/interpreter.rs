@@ -5,12 +5,20 @@ use crate::{
         arguments::Arguments,
         array::JsArray,
         attributes::*,
-        env::Env,
-        error::{JsError, JsTypeError},
+        env::{uninitialized_error, Env},
+        error::{JsError, JsReferenceError, JsSyntaxError, JsTypeError},
+        for_in_iterator::ForInIterator,
         function::JsVMFunction,
+        gc_array::GcArray,
+        generator::{GeneratorState, JsGenerator, ResumeMode},
+        iterator::{create_iter_result_object, get_iterator, iterator_close, iterator_step},
         js_arguments::JsArguments,
-        object::{JsHint, JsObject, ObjectTag},
-        property_descriptor::DataDescriptor,
+        object::{EnumerationMode, JsHint, JsObject, ObjectTag},
+        promise::{
+            call, closure, perform_promise_then, promise_resolve, reject_promise, resolve_promise,
+            JsPromise,
+        },
+        property_descriptor::{DataDescriptor, PropertyDescriptor},
         slot::Slot,
         string::JsString,
         structure::Structure,
@@ -18,13 +26,159 @@ use crate::{
         value::{CMP_FALSE, CMP_TRUE},
     },
 };
-use frame::FrameBase;
+use frame::{FrameBase, SuspendKind};
 use std::ptr::null_mut;
 
-use crate::{bytecode::ByteCode, gc::cell::Gc, runtime::value::JsValue, vm::VirtualMachine};
+use crate::{
+    bytecode::ByteCode,
+    gc::cell::{Cell, Gc},
+    runtime::value::JsValue,
+    vm::VirtualMachine,
+};
 
 pub mod frame;
 const LOG: bool = false;
+
+/// Error thrown when `this` of a derived class constructor is used before `super(...)`.
+fn this_uninitialized_error(vm: &mut VirtualMachine) -> JsValue {
+    let msg = JsString::new(
+        vm,
+        "Must call super constructor in derived class before accessing 'this' or returning from derived constructor",
+    );
+    JsValue::new(JsReferenceError::new(vm, msg, None))
+}
+
+/// 7.3.25 CopyDataProperties
+///
+/// Defines own enumerable properties of `source` except `excluded` ones on `target`.
+fn copy_data_properties(
+    vm: &mut VirtualMachine,
+    target: Gc<JsObject>,
+    source: JsValue,
+    excluded: &[Symbol],
+) -> Result<(), JsValue> {
+    let mut target = target.root(vm.space());
+    let source = source.to_object(vm)?.root(vm.space());
+    for key in source.get_own_property_keys(vm, EnumerationMode::Default) {
+        if excluded.contains(&key) {
+            continue;
+        }
+        let val = source.get(vm, key)?;
+        target.define_own_property(vm, key, &*DataDescriptor::new(val, W | C | E), false)?;
+    }
+    Ok(())
+}
+
+fn type_error(vm: &mut VirtualMachine, msg: &str) -> JsValue {
+    let msg = JsString::new(vm, msg).root(vm.space());
+    JsValue::new(JsTypeError::new(vm, *msg, None))
+}
+
+/// 12.10.4 InstanceofOperator ( V, target )
+fn instance_of(vm: &mut VirtualMachine, value: JsValue, target: JsValue) -> Result<bool, JsValue> {
+    if !target.is_object() {
+        return Err(type_error(
+            vm,
+            "Right-hand side of 'instanceof' is not an object",
+        ));
+    }
+    let value = Handle::new(vm.space(), value);
+    let target = target.as_object().root(vm.space());
+    let handler = target.get(vm, Symbol::hasInstance())?;
+    if !handler.is_undefined_or_null() {
+        let result = call(vm, handler, JsValue::new(*target), &[*value])?;
+        return Ok(result.to_boolean());
+    }
+    if !JsValue::new(*target).is_callable() {
+        return Err(type_error(
+            vm,
+            "Right-hand side of 'instanceof' is not callable",
+        ));
+    }
+    ordinary_has_instance(vm, *target, *value)
+}
+
+/// 7.3.21 OrdinaryHasInstance ( C, O )
+fn ordinary_has_instance(
+    vm: &mut VirtualMachine,
+    ctor: Gc<JsObject>,
+    value: JsValue,
+) -> Result<bool, JsValue> {
+    if ctor.as_function().is_bound() {
+        let target = ctor.as_function().as_bound().target;
+        return instance_of(vm, value, JsValue::new(target));
+    }
+    if !value.is_object() {
+        return Ok(false);
+    }
+    let value = Handle::new(vm.space(), value);
+    let proto = ctor.get(vm, Symbol::prototype())?;
+    if !proto.is_object() {
+        return Err(type_error(
+            vm,
+            "Function has non-object prototype in instanceof check",
+        ));
+    }
+    let proto = proto.as_object();
+    let mut current = value.as_object().prototype();
+    while let Some(object) = current {
+        if Gc::ptr_eq(object, proto) {
+            return Ok(true);
+        }
+        current = object.prototype();
+    }
+    Ok(false)
+}
+
+/// Values produced by a spread element, pushed by `OP_SPREAD_ARR` as a single stack value.
+fn as_spread(val: JsValue) -> Option<Gc<GcArray<JsValue>>> {
+    if val.is_cell() && !val.is_empty() {
+        val.as_cell().downcast::<GcArray<JsValue>>()
+    } else {
+        None
+    }
+}
+
+/// Number of arguments on the stack down to the empty value ending them, spread values count
+/// as many arguments as they hold.
+unsafe fn count_args(vm: &VirtualMachine) -> usize {
+    let mut count = 0;
+    let mut scan = vm.stack;
+    loop {
+        scan = scan.sub(1);
+        let val = scan.read();
+        if val.is_empty() {
+            return count;
+        }
+        count += as_spread(val).map_or(1, |spread| spread.len());
+    }
+}
+
+/// Pop arguments counted by `count_args` into `args`, the value on top of the stack is the
+/// first one. `args` is allocated while the arguments are still on the stack, where they
+/// are traced.
+unsafe fn pop_args(vm: &mut VirtualMachine, args: &mut Arguments) {
+    let mut i = 0;
+    loop {
+        let val = vm.upop();
+        if val.is_empty() {
+            break;
+        }
+        match as_spread(val) {
+            Some(spread) => {
+                for ix in 0..spread.len() {
+                    *args.at_mut(i) = spread[ix];
+                    i += 1;
+                }
+            }
+            None => {
+                *args.at_mut(i) = val;
+                i += 1;
+            }
+        }
+    }
+}
+
 unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<JsValue, JsValue> {
     //let mut pc = (*frame).code;
     if LOG {
@@ -71,6 +225,19 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
                 vm.upop();
                 vm.upush(v1);
             }
+            Op::OP_ROT => {
+                let v3 = vm.upop();
+                let v2 = vm.upop();
+                let v1 = vm.upop();
+                vm.upush(v2);
+                vm.upush(v3);
+                vm.upush(v1);
+            }
+            Op::OP_REVERSE => {
+                let n = pc.cast::<u32>().read_unaligned() as usize;
+                pc = pc.add(4);
+                std::slice::from_raw_parts_mut(vm.stack.sub(n), n).reverse();
+            }
             Op::OP_PUSH_UNDEFINED => {
                 vm.upush(JsValue::undefined());
             }
@@ -79,6 +246,9 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
             }
             Op::OP_PUSH_THIS => {
                 let this = vm.get_this();
+                if this.is_empty() {
+                    return Err(this_uninitialized_error(vm));
+                }
                 vm.upush(this);
             }
             Op::OP_PUSH_TRUE => {
@@ -119,13 +289,20 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
             }
             Op::OP_NEG => {
                 let v1 = vm.upop();
-                if v1.is_int32() {
+                // negating int32 0 gives -0 which is a double
+                if v1.is_int32() && v1.as_int32() != 0 && v1.as_int32() != i32::MIN {
                     vm.upush(JsValue::new(-v1.as_int32()));
                 } else {
                     let n = v1.to_number(vm)?;
                     vm.upush(JsValue::new(-n));
                 }
             }
+            Op::OP_INSTANCEOF => {
+                let target = vm.upop();
+                let val = vm.upop();
+                let res = instance_of(vm, val, target)?;
+                vm.upush(JsValue::new(res));
+            }
             Op::OP_IN => {
                 let obj = vm.upop();
                 let val = vm.upop();
@@ -142,12 +319,189 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
                 let res = JsValue::new(obj.has_property(vm, sym));
                 vm.upush(res);
             }
+            Op::OP_NEXT_PROP => {
+                let handle = vm.upop();
+                let obj = vm.upop();
+                let iter = if handle.is_null() {
+                    if obj.is_undefined_or_null() {
+                        vm.upush(JsValue::new(false));
+                        continue;
+                    }
+                    let object = obj.to_object(vm)?;
+                    ForInIterator::new(vm, object)
+                } else {
+                    handle
+                        .as_cell()
+                        .downcast::<ForInIterator>()
+                        .expect("for-in iterator expected")
+                };
+                let mut iter = iter.root(vm.space());
+                match iter.next(vm) {
+                    Some(key) => {
+                        let key = vm.key_string(key);
+                        vm.upush(obj);
+                        vm.upush(JsValue::new(*iter));
+                        vm.upush(JsValue::new(key));
+                        vm.upush(JsValue::new(true));
+                    }
+                    None => vm.upush(JsValue::new(false)),
+                }
+            }
+            Op::OP_GET_ITERATOR => {
+                let obj = vm.upop();
+                let (iterator, next) = get_iterator(vm, obj)?;
+                vm.upush(iterator);
+                vm.upush(next);
+            }
+            Op::OP_ITERATOR_NEXT => {
+                let next = vm.upop();
+                let iterator = vm.upop();
+                let iterator = Handle::new(vm.space(), iterator);
+                let next = Handle::new(vm.space(), next);
+                match iterator_step(vm, *iterator, *next)? {
+                    Some(value) => {
+                        vm.upush(*iterator);
+                        vm.upush(*next);
+                        vm.upush(value);
+                        vm.upush(JsValue::new(true));
+                    }
+                    None => vm.upush(JsValue::new(false)),
+                }
+            }
+            Op::OP_ITERATOR_CLOSE => {
+                let next = vm.upop();
+                let iterator = vm.upop();
+                if !next.is_empty() {
+                    iterator_close(vm, iterator)?;
+                }
+            }
+            Op::OP_ITERATOR_CLOSE_THROW => {
+                let error = vm.upop();
+                let next = vm.upop();
+                let iterator = vm.upop();
+                let error = Handle::new(vm.space(), error);
+                if next.is_empty() {
+                    // iterator is done.
+                } else if error.is_empty() {
+                    // generator is closed by its `return` method, errors thrown by `return`
+                    // propagate.
+                    iterator_close(vm, iterator)?;
+                } else {
+                    // errors thrown by `return` are ignored, the original exception wins.
+                    let _ = iterator_close(vm, iterator);
+                }
+                return Err(*error);
+            }
+            Op::OP_ITERATOR_VALUE => {
+                let next = vm.upop();
+                let iterator = vm.upop();
+                let iterator = Handle::new(vm.space(), iterator);
+                let next = Handle::new(vm.space(), next);
+                // the iterator is done if `next` throws.
+                vm.upush(*iterator);
+                vm.upush(JsValue::empty());
+                let value = if next.is_empty() {
+                    None
+                } else {
+                    iterator_step(vm, *iterator, *next)?
+                };
+                match value {
+                    Some(value) => {
+                        vm.upop();
+                        vm.upush(*next);
+                        vm.upush(value);
+                    }
+                    None => vm.upush(JsValue::undefined()),
+                }
+            }
+            Op::OP_ITERATOR_REST => {
+                let next = vm.upop();
+                let iterator = vm.upop();
+                let iterator = Handle::new(vm.space(), iterator);
+                let next = Handle::new(vm.space(), next);
+                vm.upush(*iterator);
+                vm.upush(JsValue::empty());
+                let mut array = JsArray::new(vm, 0).root(vm.space());
+                if !next.is_empty() {
+                    let mut ix = 0;
+                    while let Some(value) = iterator_step(vm, *iterator, *next)? {
+                        array.put(vm, Symbol::Indexed(ix), value, false)?;
+                        ix += 1;
+                    }
+                }
+                vm.upush(JsValue::new(*array));
+            }
+            Op::OP_REQUIRE_OBJECT_COERCIBLE => {
+                let val = vm.upop();
+                if val.is_undefined_or_null() {
+                    let msg = format!("Cannot destructure '{}'", val.to_string(vm)?);
+                    let msg = JsString::new(vm, msg);
+                    return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
+                }
+                vm.upush(val);
+            }
+            Op::OP_GET_AND_EXCLUDE => {
+                let key = vm.upop();
+                let excluded = vm.upop();
+                let obj = vm.upop();
+                let obj = Handle::new(vm.space(), obj);
+                let mut excluded = excluded.as_object().root(vm.space());
+                let key = key.to_primitive(vm, JsHint::String)?;
+                let sym = key.to_symbol(vm)?;
+                let len = excluded.elements.length();
+                excluded.put(vm, Symbol::Indexed(len), key, false)?;
+                let val = vm.get_(*obj, sym)?;
+                vm.upush(*obj);
+                vm.upush(JsValue::new(*excluded));
+                vm.upush(val);
+            }
+            Op::OP_OBJECT_REST => {
+                let excluded = vm.upop();
+                let obj = vm.upop();
+                let obj = Handle::new(vm.space(), obj);
+                let excluded = excluded.as_object().root(vm.space());
+                let mut keys = vec![];
+                for ix in 0..excluded.elements.length() {
+                    let key = excluded.get(vm, Symbol::Indexed(ix))?;
+                    keys.push(key.to_symbol(vm)?);
+                }
+                let rest = JsObject::new_empty(vm).root(vm.space());
+                copy_data_properties(vm, *rest, *obj, &keys)?;
+                vm.upush(JsValue::new(*rest));
+            }
+            Op::OP_YIELD => {
+                let val = vm.upop();
+                (*frame).suspended = Some(SuspendKind::Yield);
+                return Ok(val);
+            }
+            Op::OP_YIELD_STAR => {
+                let obj = vm.upop();
+                let (iterator, next) = get_iterator(vm, obj)?;
+                vm.upush(iterator);
+                vm.upush(next);
+                (*frame).suspended = Some(SuspendKind::Delegate);
+                return Ok(JsValue::undefined());
+            }
+            Op::OP_AWAIT => {
+                let val = vm.upop();
+                (*frame).suspended = Some(SuspendKind::Await);
+                return Ok(val);
+            }
             Op::OP_NOP => {}
             Op::OP_POS => {
                 let v1 = vm.upop();
                 let n = v1.to_number(vm)?;
                 vm.upush(JsValue::new(n));
             }
+            Op::OP_TO_STRING => {
+                let v1 = vm.upop();
+                if v1.is_string() {
+                    vm.upush(v1);
+                    continue;
+                }
+                let s = v1.to_js_string(vm)?;
+                vm.upush(JsValue::new(s));
+            }
 
             Op::OP_ADD => {
                 let mut v2 = vm.upop();
@@ -167,15 +521,15 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
                 // middle path
                 if v1.is_number() && v2.is_number() {
                     let x = v1.number();
-                    let y = v1.number();
+                    let y = v2.number();
                     vm.upush(JsValue::new(x + y));
                     /* everything other down there is slow path */
                 } else if (v1.is_cell() && v1.as_cell().is::<JsString>())
                     || (v2.is_cell() && v2.as_cell().is::<JsString>())
                 {
-                    let s1 = v1.to_string(vm)?;
-                    let s2 = v2.to_string(vm)?;
-                    let res = JsString::new(vm, format!("{}{}", s1, s2));
+                    let s1 = v1.to_js_string(vm)?;
+                    let s2 = v2.to_js_string(vm)?;
+                    let res = JsString::concat(vm, s1, s2);
                     vm.upush(JsValue::new(res));
                 } else {
                     let v1 = v1.to_number(vm)?;
@@ -201,9 +555,13 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
                 let v2 = vm.upop();
                 let v1 = vm.upop();
                 if v1.is_int32() && v2.is_int32() {
-                    if let Some(result) = v1.as_int32().checked_mul(v2.as_int32()) {
-                        vm.upush(JsValue::new(result));
-                        continue;
+                    // a zero product may be -0, the double path below handles its sign
+                    match v1.as_int32().checked_mul(v2.as_int32()) {
+                        Some(result) if result != 0 => {
+                            vm.upush(JsValue::new(result));
+                            continue;
+                        }
+                        _ => (),
                     }
                 }
 
@@ -468,7 +826,9 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
                 pc = pc.add(4);
                 let name = bcode.names[ix as usize];
                 let var = vm.bcode_get_var(name, (*frame).scope.as_object(), nix, bcode)?;
-                assert!(!var.is_empty());
+                if var.is_empty() {
+                    return Err(uninitialized_error(vm, name));
+                }
                 vm.upush(var);
             }
             Op::OP_SET_VAR => {
@@ -499,7 +859,17 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
 
             Op::OP_RET => {
                 let val = vm.upop();
-                if (*frame).is_ctor != 0 && val.is_undefined() {
+                if (*frame).is_ctor != 0 && !val.is_object() {
+                    if (*frame).ctor_structure.is_some() && !val.is_undefined() {
+                        let msg = JsString::new(
+                            vm,
+                            "Derived constructors may only return object or undefined",
+                        );
+                        return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
+                    }
+                    if (*frame).this_obj.is_empty() {
+                        return Err(this_uninitialized_error(vm));
+                    }
                     return Ok((*frame).this_obj);
                 }
                 if LOG {
@@ -511,7 +881,17 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
                 let offset = pc.cast::<i32>().read_unaligned();
                 pc = pc.add(4);
                 let to = pc.offset(offset as _);
-                (*frame).try_stack.push(((*frame).scope.as_object(), to));
+                (*frame)
+                    .try_stack
+                    .push(((*frame).scope.as_object(), to, vm.stack, false));
+            }
+            Op::OP_TRY_PUSH_FINALLY => {
+                let offset = pc.cast::<i32>().read_unaligned();
+                pc = pc.add(4);
+                let to = pc.offset(offset as _);
+                (*frame)
+                    .try_stack
+                    .push(((*frame).scope.as_object(), to, vm.stack, true));
             }
             Op::OP_TRY_POP => {
                 (*frame).try_stack.pop();
@@ -526,9 +906,20 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
                 //vm.space().defer_gc();
                 let ix = pc.cast::<u32>().read_unaligned();
                 pc = pc.add(4);
-                let func =
-                    JsVMFunction::new(vm, bcode.codes[ix as usize], (*frame).scope.as_object())
-                        .root(vm.space());
+                let code = bcode.codes[ix as usize];
+                let func = JsVMFunction::new(vm, code, (*frame).scope.as_object()).root(vm.space());
+                // the arrow captures `this` in its own scope. Before `super(...)` of a derived
+                // constructor it is not bound yet and the arrow finds the binding of the
+                // constructor that `super(...)` sets.
+                if code.is_arrow && !(*frame).this_obj.is_empty() {
+                    let mut scope = func.as_function().as_vm().scope;
+                    scope.define_own_property(
+                        vm,
+                        Symbol::lexical_this(),
+                        &*DataDescriptor::new((*frame).this_obj, NONE),
+                        false,
+                    )?;
+                }
                 vm.upush(JsValue::new(*func));
                 // vm.space().undefer_gc();
             }
@@ -542,38 +933,56 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
                     if val.is_empty() {
                         break;
                     }
-                    arr.put(vm, Symbol::Indexed(i), val, false)?;
-                    i += 1;
+                    match as_spread(val) {
+                        Some(spread) => {
+                            let spread = spread.root(vm.space());
+                            for ix in 0..spread.len() {
+                                arr.put(vm, Symbol::Indexed(i), spread[ix], false)?;
+                                i += 1;
+                            }
+                        }
+                        None => {
+                            arr.put(vm, Symbol::Indexed(i), val, false)?;
+                            i += 1;
+                        }
+                    }
                 }
                 assert!(arr.tag() == ObjectTag::Array);
                 vm.upush(JsValue::new(*arr));
             }
+            Op::OP_SPREAD_OBJ => {
+                let source = vm.upop();
+                let target = vm.upop();
+                if !source.is_undefined_or_null() {
+                    copy_data_properties(vm, target.as_object(), source, &[])?;
+                }
+            }
             Op::OP_SPREAD_ARR => {
-                let arr = vm.upop();
-                if !arr.is_object() || !arr.is_array() {
-                    let tag = format!("{}", arr.to_string(vm)?);
-
-                    let msg = JsString::new(
-                        vm,
-                        format!(
-                            "Spread operator applied to non-array object (tag {:?})",
-                            tag
-                        ),
-                    )
-                    .root(vm.space());
-                    return Err(JsValue::new(JsTypeError::new(vm, *msg, None)));
+                let iterable = vm.upop();
+                let (iterator, next) = get_iterator(vm, iterable)?;
+                let iterator = Handle::new(vm.space(), iterator);
+                let next = Handle::new(vm.space(), next);
+                // values are kept off the operand stack which has a fixed size, the
+                // instruction consuming them expands the spread value.
+                let mut values = JsArray::new(vm, 0).root(vm.space());
+                let mut len = 0;
+                while let Some(value) = iterator_step(vm, *iterator, *next)? {
+                    values.put(vm, Symbol::Indexed(len), value, false)?;
+                    len += 1;
                 }
-                let arr = arr.as_object().root(vm.space());
-                let len = arr.get(vm, Symbol::length())?.number() as u32;
+                let mut spread = GcArray::new(vm, len as usize, JsValue::undefined());
                 for i in 0..len {
-                    let val = arr.get(vm, Symbol::Indexed(len - i - 1))?;
-                    vm.upush(val);
+                    spread[i as usize] = values.get(vm, Symbol::Indexed(i))?;
                 }
+                vm.upush(JsValue::new(spread));
             }
             Op::OP_CALL | Op::OP_NEW => {
                 let mut argc = pc.cast::<u32>().read_unaligned();
                 pc = pc.add(4);
                 let is_ctor = op == Op::OP_NEW;
+                let args = Arguments::new(vm, JsValue::undefined(), count_args(vm));
+                let mut args = Handle::new(vm.space(), args);
+                pop_args(vm, &mut args);
                 let v1 = vm.upop(); // func
                 let v1 = Handle::new(vm.space(), v1);
                 let mut v3 = vm.upop(); // this
@@ -581,27 +990,12 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
                 if v3.is_empty() {
                     v3 = JsValue::new(JsValue::undefined());
                 }
-                let mut v3 = Handle::new(vm.space(), v3);
+                args.this = v3;
                 if !v1.is_callable() {
                     let msg = JsString::new(vm, "tried to call non function object");
                     return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
                 }
-                let mut args_ = vec![];
-
-                loop {
-                    let val = vm.upop();
-                    if val.is_empty() {
-                        break;
-                    }
-
-                    args_.push(val);
-                }
 
-                let args = Arguments::new(vm, *v3, args_.len() as _);
-                let mut args = Handle::new(vm.space(), args);
-                for i in 0..args_.len() {
-                    *args.at_mut(i as _) = args_[i];
-                }
                 (*frame).saved_stack = vm.stack;
 
                 args.ctor_call = is_ctor;
@@ -628,6 +1022,91 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
                 drop(args);
                 vm.upush(result);
             }
+            Op::OP_SUPER_CALL => {
+                let _argc = pc.cast::<u32>().read_unaligned();
+                pc = pc.add(4);
+                let ctor = vm.upop();
+                let args = Arguments::new(vm, JsValue::undefined(), count_args(vm));
+                let mut args = Handle::new(vm.space(), args);
+                pop_args(vm, &mut args);
+                let structure = match (*frame).ctor_structure {
+                    Some(structure) => structure,
+                    None => {
+                        let msg = JsString::new(vm, "'super' keyword unexpected here");
+                        return Err(JsValue::new(JsSyntaxError::new(vm, msg, None)));
+                    }
+                };
+                let mut parent = match ctor.as_object().prototype() {
+                    Some(parent) if parent.is_callable() => parent.root(vm.space()),
+                    _ => {
+                        let msg = JsString::new(vm, "Super constructor is not a constructor");
+                        return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
+                    }
+                };
+
+                (*frame).saved_stack = vm.stack;
+                let result = parent
+                    .as_function_mut()
+                    .construct(vm, &mut args, Some(structure))?;
+                if !(*frame).this_obj.is_empty() {
+                    let msg = JsString::new(vm, "Super constructor may only be called once");
+                    return Err(JsValue::new(JsReferenceError::new(vm, msg, None)));
+                }
+                (*frame).this_obj = result;
+                let mut scope = (*frame).scope.as_object();
+                while !scope.has_own_property(vm, Symbol::lexical_this()) {
+                    scope = scope.prototype().unwrap();
+                }
+                scope.put(vm, Symbol::lexical_this(), result, false)?;
+                vm.upush(result);
+            }
+            Op::OP_CREATE_CLASS => {
+                let mut ctor = vm.upop().as_object().root(vm.space());
+                let heritage = vm.upop();
+                let mut proto = ctor
+                    .get(vm, Symbol::prototype())?
+                    .as_object()
+                    .root(vm.space());
+                if !heritage.is_empty() {
+                    let (proto_parent, ctor_parent) = if heritage.is_null() {
+                        (None, vm.global_data().func_prototype.unwrap())
+                    } else if heritage.is_callable() {
+                        let parent = heritage.as_object().root(vm.space());
+                        let proto_parent = parent.get(vm, Symbol::prototype())?;
+                        if proto_parent.is_null() {
+                            (None, *parent)
+                        } else if proto_parent.is_object() {
+                            (Some(proto_parent.as_object()), *parent)
+                        } else {
+                            let msg = JsString::new(
+                                vm,
+                                "Class extends value does not have valid prototype property",
+                            );
+                            return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
+                        }
+                    } else {
+                        let msg =
+                            JsString::new(vm, "Class extends value is not a constructor or null");
+                        return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
+                    };
+                    let s = proto
+                        .structure()
+                        .change_prototype_transition(vm, proto_parent);
+                    proto.set_structure(vm, s);
+                    let s = ctor
+                        .structure()
+                        .change_prototype_transition(vm, Some(ctor_parent));
+                    ctor.set_structure(vm, s);
+                }
+                ctor.define_own_property(
+                    vm,
+                    Symbol::prototype(),
+                    &*DataDescriptor::new(JsValue::new(*proto), NONE),
+                    true,
+                )?;
+                vm.upush(JsValue::new(*ctor));
+                vm.upush(JsValue::new(*proto));
+            }
             Op::OP_PUSH_EMPTY => {
                 vm.upush(JsValue::empty());
             }
@@ -655,6 +1134,101 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
                 let val = vm.upop();
                 vm.set_prop(obj, name, val, fix, bcode.strict, bcode)?;
             }
+            Op::OP_DEFINE_METHOD_BY_ID => {
+                let ix = pc.cast::<u32>().read_unaligned();
+                pc = pc.add(4);
+                let enumerable = pc.cast::<u32>().read_unaligned();
+                pc = pc.add(4);
+                let name = bcode.names[ix as usize];
+                let mut obj = vm.upop().as_object();
+                let func = vm.upop();
+                let attrs = if enumerable != 0 { W | C | E } else { W | C };
+                obj.define_own_property(vm, name, &*DataDescriptor::new(func, attrs), true)?;
+            }
+            Op::OP_DEFINE_METHOD => {
+                let enumerable = pc.cast::<u32>().read_unaligned();
+                pc = pc.add(4);
+                let mut obj = vm.upop().as_object();
+                let name = vm.upop();
+                let func = vm.upop();
+                let sym = name.to_symbol(vm)?;
+                vm.set_function_name(func, sym, "")?;
+                let attrs = if enumerable != 0 { W | C | E } else { W | C };
+                obj.define_own_property(vm, sym, &*DataDescriptor::new(func, attrs), true)?;
+            }
+            Op::OP_DEFINE_PROP => {
+                let val = vm.upop();
+                let name = vm.upop();
+                let mut obj = vm.upop().as_object();
+                let sym = name.to_symbol(vm)?;
+                obj.define_own_property(vm, sym, &*DataDescriptor::new(val, W | C | E), true)?;
+            }
+            Op::OP_SET_PROTO => {
+                let proto = vm.upop();
+                let mut obj = vm.upop().as_object();
+                if proto.is_object() {
+                    obj.set_prototype(vm, Some(proto.as_object()));
+                } else if proto.is_null() {
+                    obj.set_prototype(vm, None);
+                }
+            }
+            Op::OP_SET_GETTER_SETTER_BY_ID => {
+                let ix = pc.cast::<u32>().read_unaligned();
+                pc = pc.add(4);
+                let enumerable = pc.cast::<u32>().read_unaligned();
+                pc = pc.add(4);
+                let name = bcode.names[ix as usize];
+                let obj = vm.upop();
+                let setter = vm.upop();
+                let getter = vm.upop();
+                vm.define_accessor(obj, name, getter, setter, enumerable != 0)?;
+            }
+            Op::OP_SET_GETTER_SETTER => {
+                let enumerable = pc.cast::<u32>().read_unaligned();
+                pc = pc.add(4);
+                let obj = vm.upop();
+                let name = vm.upop();
+                let setter = vm.upop();
+                let getter = vm.upop();
+                let sym = name.to_symbol(vm)?;
+                if getter.is_undefined() {
+                    vm.set_function_name(setter, sym, "set ")?;
+                } else {
+                    vm.set_function_name(getter, sym, "get ")?;
+                }
+                vm.define_accessor(obj, sym, getter, setter, enumerable != 0)?;
+            }
+            Op::OP_GET_SUPER_PROP => {
+                let ix = pc.cast::<u32>().read_unaligned();
+                pc = pc.add(4);
+                let name = bcode.names[ix as usize];
+                let home = vm.upop();
+                let val = vm.get_super(home, name)?;
+                vm.upush(val);
+            }
+            Op::OP_GET_SUPER => {
+                let home = vm.upop();
+                let name = vm.upop();
+                let sym = name.to_symbol(vm)?;
+                let val = vm.get_super(home, sym)?;
+                vm.upush(val);
+            }
+            Op::OP_SET_SUPER_PROP => {
+                let ix = pc.cast::<u32>().read_unaligned();
+                pc = pc.add(4);
+                let name = bcode.names[ix as usize];
+                let home = vm.upop();
+                let val = vm.upop();
+                vm.set_super(home, name, val)?;
+            }
+            Op::OP_SET_SUPER => {
+                let home = vm.upop();
+                let name = vm.upop();
+                let val = vm.upop();
+                let val = Handle::new(vm.space(), val);
+                let sym = name.to_symbol(vm)?;
+                vm.set_super(home, sym, *val)?;
+            }
             Op::OP_GET_PROP => {
                 let ix = pc.cast::<u32>().read_unaligned();
                 pc = pc.add(4);
@@ -709,138 +1283,477 @@ unsafe fn eval_bcode(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<J
     }
 }
 
+/// Transfer control of `frame` to the innermost `try` handler of `error`. Returns false if
+/// there is no such handler.
+///
+/// Empty `error` is a return forced by generator `return` method, only `finally` handlers
+/// run for it.
+unsafe fn enter_handler(vm: &mut VirtualMachine, frame: *mut FrameBase, error: JsValue) -> bool {
+    while let Some((scope, addr, stack, finally)) = (*frame).try_stack.pop() {
+        if error.is_empty() && !finally {
+            continue;
+        }
+        (*frame).code = addr as *mut u8;
+        (*frame).scope = JsValue::new(scope);
+        vm.stack = stack;
+        vm.upush(error);
+        return true;
+    }
+    false
+}
+
+/// Run `frame` until it returns, throws an uncaught exception or is suspended.
+unsafe fn run_frame(vm: &mut VirtualMachine, frame: *mut FrameBase) -> Result<JsValue, JsValue> {
+    loop {
+        match eval_bcode(vm, frame) {
+            Ok(val) => return Ok(val),
+            Err(e) => {
+                if enter_handler(vm, frame, e) {
+                    continue;
+                }
+                if !e.is_empty() {
+                    let desc = if let Some(code) = (*frame).bcode {
+                        vm.description(code.name)
+                    } else {
+                        "<unknown>".to_string()
+                    };
+                    vm.append_stacktrace(&format!("  ->{}", desc));
+                }
+                return Err(e);
+            }
+        }
+    }
+}
+
+/// Pop `frame` and restore operand stack pointer `stack` it was entered with.
+unsafe fn leave_frame(vm: &mut VirtualMachine, frame: *mut FrameBase, stack: *mut JsValue) {
+    let frame = Box::from_raw(frame);
+    vm.frame = frame.prev;
+    vm.stack = stack;
+}
+
 unsafe fn eval_internal(
     vm: &mut VirtualMachine,
     bcode: Gc<ByteCode>,
     pc: *mut u8,
     this: JsValue,
-    mut scope: Gc<JsObject>,
+    scope: Gc<JsObject>,
     ctor: bool,
+    ctor_structure: Option<Gc<Structure>>,
 ) -> Result<JsValue, JsValue> {
-    let mut frame = vm.init_call_frame_bcode(bcode, JsValue::new(scope), this, pc, ctor);
+    let stack = vm.stack;
+    let frame = vm.init_call_frame_bcode(bcode, JsValue::new(scope), this, pc, ctor);
+    (*frame).ctor_structure = ctor_structure;
+
+    let result = run_frame(vm, frame);
+    leave_frame(vm, frame, stack);
+    result
+}
 
-    (*frame).code = bcode.code_start;
+/// Resume generator `gen` with `value` as the result of the `yield` it is suspended at.
+/// Returns the next iterator result object.
+unsafe fn resume_generator(
+    vm: &mut VirtualMachine,
+    mut gen: Gc<JsObject>,
+    mut value: JsValue,
+    mut mode: ResumeMode,
+) -> Result<JsValue, JsValue> {
     loop {
-        match eval_bcode(vm, frame) {
-            Ok(val) => {
-                let frame = Box::from_raw(frame);
-                vm.frame = frame.prev;
-                if !vm.frame.is_null() {
-                    vm.stack = (*vm.frame).saved_stack;
-                } else {
-                    vm.stack = vm.stack_start;
-                }
-                return Ok(val);
+        match gen.as_generator().state {
+            GeneratorState::Executing => {
+                let msg = JsString::new(vm, "Generator is already running");
+                return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
+            }
+            GeneratorState::Completed => {
+                return match mode {
+                    ResumeMode::Throw => Err(value),
+                    ResumeMode::Return => {
+                        Ok(JsValue::new(create_iter_result_object(vm, value, true)))
+                    }
+                    ResumeMode::Next => Ok(JsValue::new(create_iter_result_object(
+                        vm,
+                        JsValue::undefined(),
+                        true,
+                    ))),
+                };
+            }
+            GeneratorState::SuspendedStart if mode != ResumeMode::Next => {
+                gen.as_generator_mut().complete();
+                continue;
             }
-            Err(e) => match (*frame).try_stack.pop() {
-                Some(addr) => {
-                    (*frame).code = addr.1 as *mut u8;
-                    (*frame).scope = JsValue::new(addr.0);
+            _ => (),
+        }
 
-                    vm.upush(e);
-                    continue;
+        if let Some((iterator, next)) = gen.as_generator().delegate {
+            // `yield*` forwards `next`, `throw` and `return` to the inner iterator.
+            gen.as_generator_mut().state = GeneratorState::Executing;
+            let result = vm.delegate_resume(iterator, next, value, mode);
+            gen.as_generator_mut().state = GeneratorState::SuspendedYield;
+            match result {
+                Ok(Some((result, false))) => return Ok(result),
+                Ok(Some((result, true))) => {
+                    // inner iterator is done, `yield*` evaluates to its value.
+                    gen.as_generator_mut().delegate = None;
+                    value = result;
+                    if mode != ResumeMode::Return {
+                        mode = ResumeMode::Next;
+                    }
                 }
-                None => {
-                    let desc = if let Some(code) = (*frame).bcode {
-                        vm.description(code.name)
-                    } else {
-                        format!("<unknown>")
-                    };
-                    vm.append_stacktrace(&format!("  ->{}", desc));
-                    let frame = Box::from_raw(frame);
-                    vm.frame = frame.prev;
-                    if !vm.frame.is_null() {
-                        vm.stack = (*vm.frame).saved_stack;
-                    } else {
-                        vm.stack = vm.stack_start;
+                Ok(None) => {
+                    // inner iterator has no `return` method.
+                    gen.as_generator_mut().delegate = None;
+                }
+                Err(e) => {
+                    gen.as_generator_mut().delegate = None;
+                    value = e;
+                    mode = ResumeMode::Throw;
+                }
+            }
+            continue;
+        }
+
+        match resume_frame(vm, gen, value, mode) {
+            (Some(SuspendKind::Delegate), _) => {
+                let generator = gen.as_generator_mut();
+                let next = generator.stack.pop().unwrap();
+                let iterator = generator.stack.pop().unwrap();
+                generator.delegate = Some((iterator, next));
+                value = JsValue::undefined();
+                mode = ResumeMode::Next;
+            }
+            (Some(_), Ok(val)) => {
+                return Ok(JsValue::new(create_iter_result_object(vm, val, false)));
+            }
+            (_, Ok(val)) => return Ok(JsValue::new(create_iter_result_object(vm, val, true))),
+            (_, Err(e)) => return Err(e),
+        }
+    }
+}
+
+/// Run the frame saved in `gen` until it completes or is suspended again. The frame is
+/// saved back to `gen` when it is suspended. A frame unwound by `ResumeMode::Return`
+/// completes with the value passed to `return`.
+unsafe fn resume_frame(
+    vm: &mut VirtualMachine,
+    mut gen: Gc<JsObject>,
+    value: JsValue,
+    mode: ResumeMode,
+) -> (Option<SuspendKind>, Result<JsValue, JsValue>) {
+    let generator = gen.as_generator_mut();
+    let started = generator.state != GeneratorState::SuspendedStart;
+    generator.state = GeneratorState::Executing;
+    let stack = vm.stack;
+    let frame = vm.init_call_frame_bcode(
+        generator.code,
+        generator.scope,
+        generator.this,
+        generator.pc,
+        false,
+    );
+    for val in generator.stack.drain(..) {
+        vm.upush(val);
+    }
+    for (scope, addr, offset, finally) in generator.try_stack.drain(..) {
+        (*frame)
+            .try_stack
+            .push((scope, addr, stack.add(offset), finally));
+    }
+    let result = match mode {
+        ResumeMode::Next => {
+            if started {
+                vm.upush(value);
+            }
+            run_frame(vm, frame)
+        }
+        // empty exception unwinds the frame through `finally` blocks only.
+        ResumeMode::Throw | ResumeMode::Return => {
+            let error = if mode == ResumeMode::Throw {
+                value
+            } else {
+                generator.return_value = value;
+                JsValue::empty()
+            };
+            if enter_handler(vm, frame, error) {
+                run_frame(vm, frame)
+            } else {
+                Err(error)
+            }
+        }
+    };
+
+    let generator = gen.as_generator_mut();
+    let return_value = generator.return_value;
+    let suspended = (*frame).suspended;
+    if suspended.is_some() {
+        let mut scan = stack;
+        while scan < vm.stack {
+            generator.stack.push(scan.read());
+            scan = scan.add(1);
+        }
+        generator.try_stack = (*frame)
+            .try_stack
+            .iter()
+            .map(|&(scope, addr, sp, finally)| {
+                (scope, addr, sp.offset_from(stack) as usize, finally)
+            })
+            .collect();
+        generator.scope = (*frame).scope;
+        generator.pc = (*frame).code;
+        generator.state = GeneratorState::SuspendedYield;
+    } else {
+        generator.complete();
+    }
+    leave_frame(vm, frame, stack);
+    match result {
+        Err(e) if e.is_empty() => (suspended, Ok(return_value)),
+        result => (suspended, result),
+    }
+}
+
+/// Resume async function state `gen` with `value`, the result of the awaited promise or its
+/// rejection reason for `ResumeMode::Throw`. Settles `promise` once the function completes,
+/// otherwise waits for the next awaited value.
+unsafe fn async_resume(
+    vm: &mut VirtualMachine,
+    gen: Gc<JsObject>,
+    promise: Gc<JsObject>,
+    mut value: JsValue,
+    mut mode: ResumeMode,
+) {
+    let gen = gen.root(vm.space());
+    let promise = promise.root(vm.space());
+    loop {
+        match resume_frame(vm, *gen, value, mode) {
+            (Some(_), Ok(val)) => {
+                let ctor = JsValue::new(vm.global_data().promise_constructor.unwrap());
+                match promise_resolve(vm, ctor, val) {
+                    Ok(awaited) => {
+                        let awaited = Handle::new(vm.space(), awaited);
+                        let data = [JsValue::new(*gen), JsValue::new(*promise)];
+                        let on_fulfilled = closure(vm, async_fulfilled, &data, 1);
+                        let on_fulfilled = on_fulfilled.root(vm.space());
+                        let on_rejected = closure(vm, async_rejected, &data, 1);
+                        let on_rejected = on_rejected.root(vm.space());
+                        perform_promise_then(
+                            vm,
+                            awaited.as_object(),
+                            JsValue::new(*on_fulfilled),
+                            JsValue::new(*on_rejected),
+                            None,
+                        );
+                        return;
+                    }
+                    Err(error) => {
+                        value = error;
+                        mode = ResumeMode::Throw;
                     }
-                    return Err(e);
                 }
-            },
+            }
+            (_, Ok(val)) => return resolve_promise(vm, *promise, val),
+            (_, Err(e)) => return reject_promise(vm, *promise, e),
         }
     }
 }
 
+/// Continuation of `await` on fulfillment, captures `[state, promise]`.
+fn async_fulfilled(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    data: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue> {
+    unsafe {
+        async_resume(
+            vm,
+            data[0].as_object(),
+            data[1].as_object(),
+            args.at(0),
+            ResumeMode::Next,
+        );
+    }
+    Ok(JsValue::undefined())
+}
+
+/// Continuation of `await` on rejection, captures `[state, promise]`.
+fn async_rejected(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    data: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue> {
+    unsafe {
+        async_resume(
+            vm,
+            data[0].as_object(),
+            data[1].as_object(),
+            args.at(0),
+            ResumeMode::Throw,
+        );
+    }
+    Ok(JsValue::undefined())
+}
+
 impl VirtualMachine {
-    #[allow(clippy::explicit_counter_loop)]
+    /// Call `func` with closure scope `env`. `ctor_structure` is set when `func` is a derived
+    /// class constructor called as a constructor, see `FrameBase::ctor_structure`.
     pub(crate) fn perform_vm_call(
         &mut self,
         func: &JsVMFunction,
         env: JsValue,
         args_: &Arguments,
+        ctor_structure: Option<Gc<Structure>>,
+    ) -> Result<JsValue, JsValue> {
+        let (scope, this) = self.prepare_vm_call(func, env, args_, ctor_structure.is_some())?;
+        unsafe {
+            eval_internal(
+                self,
+                func.code,
+                func.code.code_start,
+                this,
+                scope,
+                args_.ctor_call,
+                ctor_structure,
+            )
+        }
+    }
+
+    /// Call generator function `func` with closure scope `env`. Binds arguments and returns a
+    /// new generator object with structure `structure`, the body runs when it is resumed.
+    pub(crate) fn create_generator(
+        &mut self,
+        func: &JsVMFunction,
+        env: JsValue,
+        args_: &Arguments,
+        structure: Gc<Structure>,
+    ) -> Result<JsValue, JsValue> {
+        let (scope, this) = self.prepare_vm_call(func, env, args_, false)?;
+        let scope = scope.root(self.space());
+        let generator = JsGenerator::new(self, structure, func.code, JsValue::new(*scope), this);
+        Ok(JsValue::new(generator))
+    }
+
+    /// Call async function `func` with closure scope `env`. The body runs until its first
+    /// `await`, returns a promise of its result.
+    pub(crate) fn call_async(
+        &mut self,
+        func: &JsVMFunction,
+        env: JsValue,
+        args_: &Arguments,
     ) -> Result<JsValue, JsValue> {
+        let (scope, this) = self.prepare_vm_call(func, env, args_, false)?;
+        let scope = scope.root(self.space());
+        let promise = JsPromise::new(self, None).root(self.space());
+        // the suspended frame is kept in a generator object that is never exposed.
+        let structure = self.global_data().generator_structure.unwrap();
+        let state = JsGenerator::new(self, structure, func.code, JsValue::new(*scope), this)
+            .root(self.space());
         unsafe {
-            let f = func;
-            let scope = env.as_object().root(self.space());
-            let mut structure =
-                Structure::new_indexed(self, Some(env.as_object()), false).root(self.space());
-            let mut nscope =
-                JsObject::new(self, *structure, JsObject::get_class(), ObjectTag::Ordinary)
-                    .root(self.space());
-            let mut i = 0;
-
-            for p in f.code.params.iter() {
-                let _ = nscope
-                    .put(self, *p, args_.at(i), false)
-                    .unwrap_or_else(|_| panic!());
+            async_resume(
+                self,
+                *state,
+                *promise,
+                JsValue::undefined(),
+                ResumeMode::Next,
+            );
+        }
+        Ok(JsValue::new(*promise))
+    }
 
-                i += 1;
-            }
-            if let Some(rest) = f.code.rest_param {
-                let mut args_arr =
-                    JsArray::new(self, args_.size() as u32 - i as u32).root(self.space());
-                let mut ix = 0;
-                for _ in i..args_.size() {
-                    args_arr.put_indexed_slot(
-                        self,
-                        ix,
-                        args_.at(ix as usize),
-                        &mut Slot::new(),
-                        false,
-                    )?;
-                    ix += 1;
-                }
+    /// Resume generator object `generator` by its `next`, `throw` or `return` method.
+    pub(crate) fn resume_generator(
+        &mut self,
+        generator: Gc<JsObject>,
+        value: JsValue,
+        mode: ResumeMode,
+    ) -> Result<JsValue, JsValue> {
+        let generator = generator.root(self.space());
+        unsafe { resume_generator(self, *generator, value, mode) }
+    }
 
-                nscope.put(self, rest, JsValue::new(*args_arr), false)?;
-            }
-            for val in func.code.var_names.iter() {
-                nscope.define_own_property(
+    /// Create function scope of `func` with arguments `args_` bound and compute `this` value
+    /// of the call. `this` is empty if it is bound later by `super(...)`.
+    #[allow(clippy::explicit_counter_loop)]
+    fn prepare_vm_call(
+        &mut self,
+        func: &JsVMFunction,
+        env: JsValue,
+        args_: &Arguments,
+        derived: bool,
+    ) -> Result<(Gc<JsObject>, JsValue), JsValue> {
+        let f = func;
+        let scope = env.as_object().root(self.space());
+        let mut structure =
+            Structure::new_indexed(self, Some(env.as_object()), false).root(self.space());
+        let mut nscope =
+            JsObject::new(self, *structure, JsObject::get_class(), ObjectTag::Ordinary)
+                .root(self.space());
+        let mut i = 0;
+
+        for p in f.code.params.iter() {
+            nscope
+                .put(self, *p, args_.at(i), false)
+                .unwrap_or_else(|_| panic!());
+
+            i += 1;
+        }
+        if let Some(rest) = f.code.rest_param {
+            let count = args_.size().saturating_sub(i);
+            let mut args_arr = JsArray::new(self, count as u32).root(self.space());
+            for ix in 0..count {
+                args_arr.put_indexed_slot(
                     self,
-                    *val,
-                    &*DataDescriptor::new(JsValue::undefined(), W | C | E),
+                    ix as u32,
+                    args_.at(i + ix),
+                    &mut Slot::new(),
                     false,
                 )?;
             }
 
-            let mut args = JsArguments::new(self, *nscope, &f.code.params, args_.size() as _)
-                .root(self.space());
-
-            for k in i..args_.size() {
-                args.put(self, Symbol::Indexed(k as _), args_.at(k), false)?;
-            }
-            let _ = nscope.put(self, Symbol::arguments(), JsValue::new(*args), false);
-            let mut slot = Slot::new();
-            let _slot = nscope
-                .get_slot(self, Symbol::arguments(), &mut slot)
-                .unwrap_or_else(|_| panic!());
-            let this = if f.code.strict && !args_.this.is_object() {
-                JsValue::undefined()
-            } else {
-                if args_.this.is_undefined() {
-                    JsValue::new(self.global_object())
-                } else {
-                    args_.this
-                }
-            };
-            eval_internal(
+            nscope.put(self, rest, JsValue::new(*args_arr), false)?;
+        }
+        for val in func.code.var_names.iter() {
+            nscope.define_own_property(
                 self,
-                f.code,
-                f.code.code_start,
-                this,
-                *nscope,
-                args_.ctor_call,
-            )
+                *val,
+                &*DataDescriptor::new(JsValue::undefined(), W | C | E),
+                false,
+            )?;
+        }
+        if f.code.is_arrow {
+            // `arguments` is looked up in the enclosing scopes.
+            let this = f.scope.get(self, Symbol::lexical_this())?;
+            return Ok((*nscope, this));
+        }
+
+        let mut args =
+            JsArguments::new(self, *nscope, &f.code.params, args_.size() as _).root(self.space());
+
+        for k in i..args_.size() {
+            args.put(self, Symbol::Indexed(k as _), args_.at(k), false)?;
         }
+        let _ = nscope.put(self, Symbol::arguments(), JsValue::new(*args), false);
+        let mut slot = Slot::new();
+        let _slot = nscope
+            .get_slot(self, Symbol::arguments(), &mut slot)
+            .unwrap_or_else(|_| panic!());
+        let this = if derived {
+            // bound by `super(...)`, which also sets the binding arrow functions created
+            // before it look up.
+            nscope.define_own_property(
+                self,
+                Symbol::lexical_this(),
+                &*DataDescriptor::new(JsValue::empty(), W),
+                false,
+            )?;
+            JsValue::empty()
+        } else if f.code.strict && !args_.this.is_object() {
+            JsValue::undefined()
+        } else {
+            if args_.this.is_undefined() {
+                JsValue::new(self.global_object())
+            } else {
+                args_.this
+            }
+        };
+        Ok((*nscope, this))
     }
     fn bcode_get_var(
         &mut self,
@@ -912,7 +1825,11 @@ impl VirtualMachine {
                 let count = *count;
                 let structure = *structure;
                 let offset = *offset;
-                if let Some(mut hit) = self.try_cache(structure, *scope) {
+                // bindings in their temporal dead zone are left to `set_variable` to throw.
+                let hit = self
+                    .try_cache(structure, *scope)
+                    .filter(|hit| !hit.direct(offset as _).is_empty());
+                if let Some(mut hit) = hit {
                     *hit.direct_mut(offset as _) = val;
                 } else {
                     let (base, slot) =
@@ -954,6 +1871,8 @@ impl VirtualMachine {
             this_obj: JsValue::undefined(),
             thrown_val: JsValue::undefined(),
             callee: JsValue::undefined(),
+            ctor_structure: None,
+            suspended: None,
         });
 
         let p = Box::into_raw(frame);
@@ -996,12 +1915,171 @@ impl VirtualMachine {
     }
 
     fn get_(&mut self, obj: JsValue, name: Symbol) -> Result<JsValue, JsValue> {
-        let mut obj = if obj.is_object() {
-            obj.as_object()
+        if obj.is_object() {
+            return obj.as_object().get(self, name);
+        }
+        // primitives go through `get_slot` which knows the own properties of strings.
+        let mut slot = Slot::new();
+        obj.get_slot(self, name, &mut slot)
+    }
+
+    fn define_accessor(
+        &mut self,
+        obj: JsValue,
+        name: Symbol,
+        getter: JsValue,
+        setter: JsValue,
+        enumerable: bool,
+    ) -> Result<(), JsValue> {
+        let attrs = if enumerable { E | C } else { C };
+        let desc = if setter.is_undefined() {
+            PropertyDescriptor::accessor_getter(getter, attrs)
+        } else if getter.is_undefined() {
+            PropertyDescriptor::accessor_setter(setter, attrs)
+        } else {
+            PropertyDescriptor::accessor_descriptor(getter, setter, attrs)
+        };
+        let mut obj = obj.as_object().root(self.space());
+        obj.define_own_property(self, name, &desc, true)?;
+        Ok(())
+    }
+
+    /// Set `name` property of function `func` defined with computed key `key`. `prefix` is
+    /// `get ` or `set ` for accessors.
+    fn set_function_name(
+        &mut self,
+        func: JsValue,
+        key: Symbol,
+        prefix: &str,
+    ) -> Result<(), JsValue> {
+        let name = JsString::new(self, format!("{}{}", prefix, self.description(key)));
+        let mut func = func.as_object().root(self.space());
+        func.define_own_property(
+            self,
+            Symbol::name(),
+            &*DataDescriptor::new(JsValue::new(name), W | C),
+            false,
+        )?;
+        Ok(())
+    }
+
+    /// Get property `name` of the prototype of home object `home` with current `this` as the
+    /// receiver.
+    fn get_super(&mut self, home: JsValue, name: Symbol) -> Result<JsValue, JsValue> {
+        let this = self.get_this();
+        if this.is_empty() {
+            return Err(this_uninitialized_error(self));
+        }
+        let proto = match home.as_object().prototype() {
+            Some(proto) => proto.root(self.space()),
+            None => {
+                let desc = self.description(name);
+                let msg = JsString::new(
+                    self,
+                    format!("Cannot read property '{}' of null super", desc),
+                );
+                return Err(JsValue::new(JsTypeError::new(self, msg, None)));
+            }
+        };
+        let mut slot = Slot::new();
+        if proto.get_property_slot(self, name, &mut slot) {
+            return slot.get(self, this);
+        }
+        Ok(JsValue::undefined())
+    }
+
+    fn set_super(&mut self, home: JsValue, name: Symbol, value: JsValue) -> Result<(), JsValue> {
+        let this = self.get_this();
+        if this.is_empty() {
+            return Err(this_uninitialized_error(self));
+        }
+        let value = Handle::new(self.space(), value);
+        let desc = self.description(name);
+        let proto = match home.as_object().prototype() {
+            Some(proto) => proto.root(self.space()),
+            None => {
+                let msg = format!("Cannot set property '{}' of null super", desc);
+                return Err(type_error(self, &msg));
+            }
+        };
+        let mut slot = Slot::new();
+        if proto.get_property_slot(self, name, &mut slot) {
+            if slot.attributes().is_accessor() {
+                let setter = slot.accessor().setter();
+                if !setter.is_callable() {
+                    let msg = format!("Cannot set property '{}' which has only a getter", desc);
+                    return Err(type_error(self, &msg));
+                }
+                return call(self, setter, this, &[*value]).map(|_| ());
+            }
+            if !slot.attributes().is_writable() {
+                let msg = format!("Cannot assign to read only property '{}'", desc);
+                return Err(type_error(self, &msg));
+            }
+        }
+        if !this.is_object() {
+            let msg = format!("Cannot create property '{}' on a primitive value", desc);
+            return Err(type_error(self, &msg));
+        }
+        let mut receiver = this.as_object().root(self.space());
+        // the property of `this` is updated or created without consulting its own prototype
+        // chain, whose setters `super` skips.
+        if receiver.get_own_property_slot(self, name, &mut Slot::new()) {
+            receiver.put(self, name, *value, true)
         } else {
-            obj.get_primitive_proto(self)
+            receiver
+                .define_own_property(self, name, &*DataDescriptor::new(*value, W | C | E), true)
+                .map(|_| ())
+        }
+    }
+
+    /// Forward generator resumption to iterator `iterator` a `yield*` delegates to. Returns
+    /// the inner result object and false, or the value the iterator completed with and true.
+    /// Returns `None` when the generator is closed and the iterator has no `return` method.
+    fn delegate_resume(
+        &mut self,
+        iterator: JsValue,
+        next: JsValue,
+        value: JsValue,
+        mode: ResumeMode,
+    ) -> Result<Option<(JsValue, bool)>, JsValue> {
+        let method = match mode {
+            ResumeMode::Next => next,
+            ResumeMode::Throw | ResumeMode::Return => {
+                let name = self.intern(if mode == ResumeMode::Throw {
+                    "throw"
+                } else {
+                    "return"
+                });
+                iterator.as_object().get(self, name)?
+            }
         };
-        obj.get(self, name)
+        if method.is_undefined_or_null() && mode == ResumeMode::Return {
+            return Ok(None);
+        }
+        if method.is_undefined_or_null() && mode == ResumeMode::Throw {
+            iterator_close(self, iterator)?;
+            let msg = JsString::new(self, "The iterator does not provide a 'throw' method");
+            return Err(JsValue::new(JsTypeError::new(self, msg, None)));
+        }
+        if !method.is_callable() {
+            let msg = JsString::new(self, "iterator method is not a function");
+            return Err(JsValue::new(JsTypeError::new(self, msg, None)));
+        }
+        let args = Arguments::new(self, iterator, 1);
+        let mut args = Handle::new(self.space(), args);
+        *args.at_mut(0) = value;
+        let result = method.as_object().as_function_mut().call(self, &mut args)?;
+        if !result.is_object() {
+            let msg = JsString::new(self, "Iterator result is not an object");
+            return Err(JsValue::new(JsTypeError::new(self, msg, None)));
+        }
+        let result = result.as_object().root(self.space());
+        if result.get(self, Symbol::done())?.to_boolean() {
+            let value = result.get(self, Symbol::value())?;
+            return Ok(Some((value, true)));
+        }
+        Ok(Some((JsValue::new(*result), false)))
     }
 
     fn get_prop(
@@ -1140,3 +2218,132 @@ impl VirtualMachine {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::eval_ok;
+
+    #[test]
+    fn test_spread_arguments() {
+        let source = "
+            function f(a, b, c, d) { return [a, b, c, d].join(); }
+            function count() { return arguments.length; }
+            result = f(...[1, 2], ...[3], 4) + ';' + count(1, ...'ab', ...[], 2);";
+        assert_eq!(eval_ok(source), "1,2,3,4;4");
+    }
+
+    #[test]
+    fn test_spread_larger_than_operand_stack() {
+        let source = "
+            function count() { return arguments.length; }
+            class A { constructor(...xs) { this.n = xs.length; } }
+            class B extends A { constructor(xs) { super(...xs); } }
+            var big = new Array(9000);
+            result = [count(...big), new A(...big).n, new B(big).n, [0, ...big, 1].length].join();";
+        assert_eq!(eval_ok(source), "9000,9000,9000,9002");
+    }
+
+    #[test]
+    fn test_spread_survives_collection() {
+        // the spread values are the top of the operand stack while the array is allocated
+        let source = "
+            var big = new Array(9000);
+            var lengths = [];
+            for (var i = 0; i < 8; i = i + 1) lengths.push([...big, i].length);
+            result = lengths.every(function (n) { return n == 9001; });";
+        assert_eq!(eval_ok(source), "true");
+    }
+
+    #[test]
+    fn test_arrow_captures_this_of_method() {
+        let source = "
+            var o = { m() { var f = () => this; var g = () => this; return [f(), g()]; } };
+            var r = o.m();
+            result = (r[0] === o) + ',' + (r[1] === o);";
+        assert_eq!(eval_ok(source), "true,true");
+    }
+
+    #[test]
+    fn test_sibling_arrows_capture_their_own_this() {
+        let source = "
+            function make() { return () => this; }
+            var a = { make: make }, b = { make: make };
+            var fa = a.make(), fb = b.make();
+            result = (fa() === a) + ',' + (fb() === b);";
+        assert_eq!(eval_ok(source), "true,true");
+    }
+
+    #[test]
+    fn test_arrow_created_before_super_sees_bound_this() {
+        let source = "
+            class A { constructor() { this.x = 1; } }
+            class B extends A {
+                constructor() {
+                    var f = () => this;
+                    var g = () => (() => this)();
+                    var early;
+                    try { f(); } catch (e) { early = e.name; }
+                    super();
+                    this.r = early + ',' + f().x + ',' + g().x + ',' + (f() === this);
+                }
+            }
+            result = new B().r;";
+        assert_eq!(eval_ok(source), "ReferenceError,1,1,true");
+    }
+
+    #[test]
+    fn test_arrow_uses_enclosing_arguments() {
+        let source =
+            "function f() { var g = () => arguments[0] + arguments.length; return g(9, 9, 9); }
+            result = f(1, 2);";
+        assert_eq!(eval_ok(source), "3");
+    }
+
+    #[test]
+    fn test_arrow_bodies_and_parameters() {
+        let source = "var sq = x => x * x;
+            var obj = () => ({ k: 1 });
+            var none = () => {};
+            var add = (a, b = 2, ...r) => a + b + r.length;
+            result = sq(3) + ',' + obj().k + ',' + none() + ',' + add(1) + ',' + add(1, 1, 1, 1);";
+        assert_eq!(eval_ok(source), "9,1,undefined,3,4");
+    }
+
+    #[test]
+    fn test_arrow_is_not_constructor() {
+        let source = "var f = () => 1;
+            result = f.prototype;
+            try { new f(); } catch (e) { result += ' ' + e.name; }";
+        assert_eq!(eval_ok(source), "undefined TypeError");
+    }
+
+    #[test]
+    fn test_double_arithmetic() {
+        let source = "result = [0.1 + 0.2, 1.5 + 1, 1 + 1.5, 2.5 - 0.5, 1 - 2.5, 1.5 * 2, 7.5 / 2,
+                2.5 < 3, 3 < 2.5, 2 > 1.5].join('|');";
+        assert_eq!(
+            eval_ok(source),
+            "0.30000000000000004|2.5|2.5|2|-1.5|3|3.75|true|false|true"
+        );
+    }
+
+    #[test]
+    fn test_instanceof() {
+        let source = "
+            function A() {}
+            function B() {}
+            B.prototype = Object.create(A.prototype);
+            var b = new B();
+            var odd = {};
+            odd[Symbol.hasInstance] = function (v) { return v === 1; };
+            result = [b instanceof B, b instanceof A, b instanceof Object, new A() instanceof B,
+                1 instanceof A, [] instanceof Array, A.bind(null) instanceof Function,
+                b instanceof A.bind(null), 1 instanceof odd, new RangeError('r') instanceof Error,
+                new RangeError('r') instanceof RangeError].join();
+            try { b instanceof {}; } catch (e) { result += ' ' + e.name; }";
+        assert_eq!(
+            eval_ok(source),
+            "true,true,true,false,false,true,true,true,true,true,true TypeError"
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     bytecode::ByteCode,
     gc::cell::{Cell, Gc, Trace, Tracer},
-    runtime::{object::JsObject, value::JsValue},
+    runtime::{object::JsObject, structure::Structure, value::JsValue},
 };
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -11,10 +11,22 @@ pub enum BlockType {
     Catch,
     Loop,
     Switch,
+    /// Labelled statement that is not a loop or `switch`.
+    Label,
 }
 
-#[repr(C)]
+/// Why a generator or async function frame returned from `eval_bcode` without completing.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SuspendKind {
+    /// `yield`, the returned value is the yielded value.
+    Yield,
+    /// `yield*`, the iterator to delegate to and its `next` method are on top of the stack.
+    Delegate,
+    /// `await`, the returned value is the awaited value.
+    Await,
+}
 
+#[repr(C)]
 pub struct FrameBase {
     pub prev: *mut Self,
 
@@ -27,13 +39,20 @@ pub struct FrameBase {
     pub stack_size: usize,
     pub scope: JsValue,
 
-    pub try_stack: Vec<(Gc<JsObject>, *const u8)>,
+    /// Handlers of active `try` blocks: scope, handler address, operand stack pointer
+    /// to restore when an exception is caught and whether the handler is a `finally` block.
+    pub try_stack: Vec<(Gc<JsObject>, *const u8, *mut JsValue, bool)>,
     pub this_obj: JsValue,
     pub thrown_val: JsValue,
     pub bcode: Option<Gc<ByteCode>>,
 
     pub code: *mut u8,
     pub callee: JsValue,
+    /// Structure of the object `super(...)` creates when this frame runs a derived class
+    /// constructor. `this_obj` is empty until then.
+    pub ctor_structure: Option<Gc<Structure>>,
+    /// Set when a generator frame is suspended by `yield` or `yield*`.
+    pub suspended: Option<SuspendKind>,
 }
 unsafe impl Trace for FrameBase {
     fn trace(&self, tracer: &mut dyn Tracer) {
@@ -42,9 +61,10 @@ unsafe impl Trace for FrameBase {
         self.thrown_val.trace(tracer);
         self.bcode.trace(tracer);
         self.callee.trace(tracer);
+        self.ctor_structure.trace(tracer);
         self.try_stack
             .iter()
-            .for_each(|(scope, _)| scope.trace(tracer));
+            .for_each(|(scope, _, _, _)| scope.trace(tracer));
     }
 }
 impl Cell for FrameBase {}
@@ -3,9 +3,18 @@ use crate::{
     vm::VirtualMachine,
 };
 pub mod array;
+pub mod boolean;
 pub mod error;
 pub mod function;
+pub mod generator;
+pub mod iterator;
+pub mod json;
+pub mod math;
+pub mod number;
 pub mod object;
+pub mod promise;
+pub mod string;
+pub mod symbol;
 pub fn print(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
     for ix in 0..args.size() {
         let val = args.at(ix);
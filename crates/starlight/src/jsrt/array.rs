@@ -1,26 +1,47 @@
 use crate::{
+    gc::{cell::Gc, handle::Handle},
     runtime::{
-        arguments::Arguments, array::JsArray, attributes::*, error::JsRangeError,
-        object::ObjectTag, property_descriptor::DataDescriptor, string::JsString, symbol::Symbol,
+        arguments::Arguments,
+        array::JsArray,
+        attributes::*,
+        error::JsRangeError,
+        indexed_elements::MAX_VECTOR_SIZE,
+        iterator::{get_iterator, iterator_close, iterator_step, IterationKind, JsArrayIterator},
+        object::{JsObject, ObjectTag},
+        promise::{call, type_error},
+        property_descriptor::DataDescriptor,
+        string::JsString,
+        symbol::Symbol,
         value::JsValue,
     },
     vm::VirtualMachine,
 };
 
-use super::object::object_to_string;
+use super::{object::object_to_string, string::range_error};
+use std::cmp::Ordering;
 
 pub fn array_ctor(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let mut ary = construct_array(vm, args)?.root(vm.space());
+    // `new` allocates `this` with the prototype of the invoked constructor, which is not
+    // Array.prototype for subclasses.
+    if let Some(structure) = args.ctor_structure() {
+        ary.set_prototype(vm, structure.prototype());
+    }
+    Ok(JsValue::new(*ary))
+}
+
+fn construct_array(vm: &mut VirtualMachine, args: &Arguments) -> Result<Gc<JsObject>, JsValue> {
     let size = args.size();
     if size == 0 {
-        return Ok(JsValue::new(JsArray::new(vm, 0)));
+        return Ok(JsArray::new(vm, 0));
     }
     if size == 1 {
-        let first = args.at(1);
+        let first = args.at(0);
         if first.is_number() {
             let val = first.to_number(vm)?;
             let len = val as u32;
             if len as f64 == val {
-                return Ok(JsValue::new(JsArray::new(vm, len)));
+                return Ok(JsArray::new(vm, len));
             } else {
                 let msg =
                     JsString::new(vm, format!("invalid array length '{}", len)).root(vm.space());
@@ -31,7 +52,7 @@ pub fn array_ctor(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue,
             let mut ary = JsArray::new(vm, 1);
             ary.put(vm, Symbol::Indexed(0), first, false)?;
 
-            Ok(JsValue::new(ary))
+            Ok(ary)
         }
     } else {
         let mut ary = JsArray::new(vm, size as _).root(vm.space());
@@ -43,7 +64,7 @@ pub fn array_ctor(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue,
                 false,
             )?;
         }
-        Ok(JsValue::new(*ary))
+        Ok(*ary)
     }
 }
 
@@ -66,27 +87,57 @@ pub fn array_of(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, Js
     Ok(JsValue::new(*ary))
 }
 
+/// Array.from ( items [ , mapfn [ , thisArg ] ] )
 pub fn array_from(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
-    let mut arg1 = args.at(0).to_object(vm)?.root(vm.space());
-    let len = arg1.get(vm, Symbol::length())?;
-    let len = if len.is_number() {
-        let n = len.to_number(vm)?;
-        if n as u32 as f64 == n {
-            n as u32
+    let items = args.at(0);
+    let map_fn = args.at(1);
+    let this_arg = args.at(2);
+    if !map_fn.is_undefined() && !map_fn.is_callable() {
+        return Err(type_error(vm, "Array.from: mapFn is not a function"));
+    }
+    let map = |vm: &mut VirtualMachine, value: JsValue, k: u32| {
+        if map_fn.is_undefined() {
+            Ok(value)
         } else {
-            0
+            call(vm, map_fn, this_arg, &[value, JsValue::new(k as f64)])
         }
+    };
+
+    let method = if items.is_undefined_or_null() {
+        JsValue::undefined()
     } else {
-        0
+        items.to_object(vm)?.get(vm, Symbol::iterator())?
     };
-    let mut target = JsArray::new(vm, len).root(vm.space());
-    for k in 0..len {
-        if arg1.has_property(vm, Symbol::Indexed(k)) {
-            let value = arg1.get(vm, Symbol::Indexed(k))?;
-            target.put(vm, Symbol::Indexed(k), value, false)?;
+    if !method.is_undefined_or_null() {
+        let (iterator, next) = get_iterator(vm, items)?;
+        let iterator = Handle::new(vm.space(), iterator);
+        let next = Handle::new(vm.space(), next);
+        let target = JsArray::new(vm, 0).root(vm.space());
+        let mut k = 0;
+        while let Some(value) = iterator_step(vm, *iterator, *next)? {
+            let value = match map(vm, value, k) {
+                Ok(value) => Handle::new(vm.space(), value),
+                Err(error) => {
+                    let error = Handle::new(vm.space(), error);
+                    let _ = iterator_close(vm, *iterator);
+                    return Err(*error);
+                }
+            };
+            create_data_property(vm, *target, k, *value)?;
+            k += 1;
         }
+        return Ok(JsValue::new(*target));
     }
 
+    let obj = items.to_object(vm)?.root(vm.space());
+    let len = length_of_array_like(vm, *obj)?;
+    let target = JsArray::new(vm, len).root(vm.space());
+    for k in 0..len {
+        let value = obj.get(vm, Symbol::Indexed(k))?;
+        let value = map(vm, value, k)?;
+        let value = Handle::new(vm.space(), value);
+        create_data_property(vm, *target, k, *value)?;
+    }
     Ok(JsValue::new(*target))
 }
 pub fn array_join(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
@@ -95,7 +146,7 @@ pub fn array_join(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue,
     let len = if len as u32 as f64 == len {
         len as u32
     } else {
-        0 as u32
+        0_u32
     };
     let separator = if !args.at(0).is_undefined() {
         args.at(0).to_string(vm)?
@@ -134,7 +185,7 @@ pub fn array_to_string(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsVa
         return f.call(vm, &mut args);
     }
     let mut args = Arguments::new(vm, args.this, 0);
-    object_to_string(vm, &mut args)
+    object_to_string(vm, &args)
 }
 
 // TODO(playX): Allow to push up to 2^53-1 values
@@ -153,7 +204,7 @@ pub fn array_push(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue,
     let last = args.size();
     if (n + args.size() as u64) <= max {
         while it != last {
-            obj.put(vm, Symbol::Indexed(it as _), args.at(it), false)?;
+            obj.put(vm, Symbol::Indexed(n as _), args.at(it), true)?;
             it += 1;
             n += 1;
         }
@@ -162,7 +213,7 @@ pub fn array_push(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue,
         return Err(JsValue::new(JsRangeError::new(vm, msg, None)));
     }
     let len = n as f64;
-    obj.put(vm, Symbol::length(), JsValue::new(len), false)?;
+    obj.put(vm, Symbol::length(), JsValue::new(len), true)?;
     Ok(JsValue::new(n as f64))
 }
 
@@ -186,3 +237,1079 @@ pub fn array_pop(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, J
         Ok(element)
     }
 }
+
+/// Array.prototype.values ( ), also installed as Array.prototype [ @@iterator ].
+pub fn array_values(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    Ok(JsValue::new(JsArrayIterator::new(
+        vm,
+        obj,
+        IterationKind::Value,
+    )))
+}
+
+/// Array.prototype.keys ( )
+pub fn array_keys(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    Ok(JsValue::new(JsArrayIterator::new(
+        vm,
+        obj,
+        IterationKind::Key,
+    )))
+}
+
+/// Array.prototype.entries ( )
+pub fn array_entries(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    Ok(JsValue::new(JsArrayIterator::new(
+        vm,
+        obj,
+        IterationKind::KeyValue,
+    )))
+}
+
+/// LengthOfArrayLike ( obj ), clamped to the indices the object model supports.
+fn length_of_array_like(vm: &mut VirtualMachine, obj: Gc<JsObject>) -> Result<u32, JsValue> {
+    if obj.tag() == ObjectTag::Array {
+        return Ok(obj.elements.length());
+    }
+    let len = obj.get(vm, Symbol::length())?.to_integer_or_infinity(vm)?;
+    Ok(len.max(0.0).min(u32::MAX as f64) as u32)
+}
+
+/// Resolve a relative start or end argument against `len`, `default` is used for undefined.
+fn relative_index(
+    vm: &mut VirtualMachine,
+    value: JsValue,
+    len: u32,
+    default: u32,
+) -> Result<u32, JsValue> {
+    if value.is_undefined() {
+        return Ok(default);
+    }
+    let relative = value.to_integer_or_infinity(vm)?;
+    Ok(if relative < 0.0 {
+        (len as f64 + relative).max(0.0) as u32
+    } else {
+        relative.min(len as f64) as u32
+    })
+}
+
+fn is_dense_array(obj: Gc<JsObject>) -> bool {
+    obj.tag() == ObjectTag::Array && obj.elements.dense()
+}
+
+/// The elements `start..end` of `obj` when it is a dense array without holes in that range.
+/// Such elements are plain writable data properties, so they can be used without going through
+/// [[Get]] and [[Set]].
+fn dense_elements(obj: &Gc<JsObject>, start: u32, end: u32) -> Option<&[JsValue]> {
+    if !is_dense_array(*obj) || end as usize > obj.elements.vector.len() {
+        return None;
+    }
+    let elements = &obj.elements.vector.as_slice()[start as usize..end as usize];
+    if elements.iter().any(|value| value.is_empty()) {
+        return None;
+    }
+    Some(elements)
+}
+
+/// Whether the length of `obj` can change from `len` to `new_len` by editing its vector.
+fn can_resize_dense(obj: Gc<JsObject>, len: u32, new_len: u32) -> bool {
+    obj.elements.writable()
+        && (new_len <= len || obj.is_extensible())
+        && new_len as usize <= MAX_VECTOR_SIZE
+        && dense_elements(&obj, 0, len).is_some()
+}
+
+/// HasProperty ( obj, k ) followed by Get ( obj, k ), `None` when there is no element `k`.
+fn get_element(
+    vm: &mut VirtualMachine,
+    obj: Gc<JsObject>,
+    k: u32,
+) -> Result<Option<JsValue>, JsValue> {
+    if is_dense_array(obj) && (k as usize) < obj.elements.vector.len() {
+        let value = obj.elements.vector[k as usize];
+        if !value.is_empty() {
+            return Ok(Some(value));
+        }
+    }
+    if !obj.has_property(vm, Symbol::Indexed(k)) {
+        return Ok(None);
+    }
+    Ok(Some(obj.get(vm, Symbol::Indexed(k))?))
+}
+
+/// Get ( obj, k )
+fn get_value(vm: &mut VirtualMachine, obj: Gc<JsObject>, k: u32) -> Result<JsValue, JsValue> {
+    Ok(get_element(vm, obj, k)?.unwrap_or_else(JsValue::undefined))
+}
+
+/// Set ( obj, k, value, true )
+fn set_element(
+    vm: &mut VirtualMachine,
+    mut obj: Gc<JsObject>,
+    k: u32,
+    value: JsValue,
+) -> Result<(), JsValue> {
+    if dense_elements(&obj, k, k + 1).is_some() {
+        obj.elements.vector[k as usize] = value;
+        return Ok(());
+    }
+    obj.put(vm, Symbol::Indexed(k), value, true)
+}
+
+/// Move element `from` to `to`, deleting `to` when there is no element `from`.
+fn move_element(
+    vm: &mut VirtualMachine,
+    mut obj: Gc<JsObject>,
+    from: u32,
+    to: u32,
+) -> Result<(), JsValue> {
+    match get_element(vm, obj, from)? {
+        Some(value) => set_element(vm, obj, to, value),
+        None => obj.delete(vm, Symbol::Indexed(to), true).map(|_| ()),
+    }
+}
+
+fn set_length(vm: &mut VirtualMachine, mut obj: Gc<JsObject>, len: u32) -> Result<(), JsValue> {
+    obj.put(vm, Symbol::length(), JsValue::new(len as f64), true)
+}
+
+/// CreateDataPropertyOrThrow ( obj, k, value )
+fn create_data_property(
+    vm: &mut VirtualMachine,
+    mut obj: Gc<JsObject>,
+    k: u32,
+    value: JsValue,
+) -> Result<(), JsValue> {
+    obj.define_own_property(
+        vm,
+        Symbol::Indexed(k),
+        &*DataDescriptor::new(value, W | C | E),
+        true,
+    )?;
+    Ok(())
+}
+
+fn callback(vm: &mut VirtualMachine, args: &Arguments, method: &str) -> Result<JsValue, JsValue> {
+    let f = args.at(0);
+    if !f.is_callable() {
+        return Err(type_error(
+            vm,
+            &format!("Array.prototype.{}: callback is not a function", method),
+        ));
+    }
+    Ok(f)
+}
+
+/// Array.prototype.at ( index )
+pub fn array_at(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)? as f64;
+    let relative = args.at(0).to_integer_or_infinity(vm)?;
+    let k = if relative >= 0.0 {
+        relative
+    } else {
+        len + relative
+    };
+    if k < 0.0 || k >= len {
+        return Ok(JsValue::undefined());
+    }
+    get_value(vm, obj, k as u32)
+}
+
+/// 23.1.3.2.1 IsConcatSpreadable ( O )
+fn is_concat_spreadable(vm: &mut VirtualMachine, value: JsValue) -> Result<bool, JsValue> {
+    if !value.is_object() {
+        return Ok(false);
+    }
+    let spreadable = value.as_object().get(vm, Symbol::isConcatSpreadable())?;
+    if !spreadable.is_undefined() {
+        return Ok(spreadable.to_boolean());
+    }
+    Ok(value.is_array())
+}
+
+/// Array.prototype.concat ( ...items )
+pub fn array_concat(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let mut values = Handle::new(vm.space(), Vec::<JsValue>::new());
+    for ix in 0..=args.size() {
+        let item = if ix == 0 {
+            JsValue::new(obj)
+        } else {
+            args.at(ix - 1)
+        };
+        if !is_concat_spreadable(vm, item)? {
+            values.push(item);
+            continue;
+        }
+        let item = item.as_object();
+        let len = length_of_array_like(vm, item)?;
+        if let Some(elements) = dense_elements(&item, 0, len) {
+            values.extend_from_slice(elements);
+            continue;
+        }
+        for k in 0..len {
+            let value = get_element(vm, item, k)?.unwrap_or_else(JsValue::empty);
+            values.push(value);
+        }
+    }
+    if values.len() > u32::MAX as usize {
+        return Err(range_error(vm, "array size exceeded".to_string()));
+    }
+    Ok(JsValue::new(JsArray::from_slice(vm, &values)))
+}
+
+/// Array.prototype.copyWithin ( target, start [ , end ] )
+pub fn array_copy_within(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let mut obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    let to = relative_index(vm, args.at(0), len, 0)?;
+    let from = relative_index(vm, args.at(1), len, 0)?;
+    let end = relative_index(vm, args.at(2), len, len)?;
+    let count = end.saturating_sub(from).min(len - to);
+    if count == 0 {
+        return Ok(JsValue::new(obj));
+    }
+    if dense_elements(&obj, from, from + count).is_some()
+        && dense_elements(&obj, to, to + count).is_some()
+    {
+        let range = from as usize..(from + count) as usize;
+        obj.elements
+            .vector
+            .as_mut_slice()
+            .copy_within(range, to as usize);
+        return Ok(JsValue::new(obj));
+    }
+    if from < to && to < from + count {
+        for ix in (0..count).rev() {
+            move_element(vm, obj, from + ix, to + ix)?;
+        }
+    } else {
+        for ix in 0..count {
+            move_element(vm, obj, from + ix, to + ix)?;
+        }
+    }
+    Ok(JsValue::new(obj))
+}
+
+/// Array.prototype.every ( callbackfn [ , thisArg ] )
+pub fn array_every(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    let f = callback(vm, args, "every")?;
+    for k in 0..len {
+        if let Some(value) = get_element(vm, obj, k)? {
+            let result = call(
+                vm,
+                f,
+                args.at(1),
+                &[value, JsValue::new(k as f64), JsValue::new(obj)],
+            )?;
+            if !result.to_boolean() {
+                return Ok(JsValue::new(false));
+            }
+        }
+    }
+    Ok(JsValue::new(true))
+}
+
+/// Array.prototype.fill ( value [ , start [ , end ] ] )
+pub fn array_fill(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let mut obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    let value = args.at(0);
+    let start = relative_index(vm, args.at(1), len, 0)?;
+    let end = relative_index(vm, args.at(2), len, len)?;
+    if start >= end {
+        return Ok(JsValue::new(obj));
+    }
+    if dense_elements(&obj, start, end).is_some() {
+        obj.elements.vector.as_mut_slice()[start as usize..end as usize].fill(value);
+        return Ok(JsValue::new(obj));
+    }
+    for k in start..end {
+        set_element(vm, obj, k, value)?;
+    }
+    Ok(JsValue::new(obj))
+}
+
+/// Array.prototype.filter ( callbackfn [ , thisArg ] )
+pub fn array_filter(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    let f = callback(vm, args, "filter")?;
+    let mut values = Handle::new(vm.space(), Vec::<JsValue>::new());
+    for k in 0..len {
+        if let Some(value) = get_element(vm, obj, k)? {
+            let selected = call(
+                vm,
+                f,
+                args.at(1),
+                &[value, JsValue::new(k as f64), JsValue::new(obj)],
+            )?;
+            if selected.to_boolean() {
+                values.push(value);
+            }
+        }
+    }
+    Ok(JsValue::new(JsArray::from_slice(vm, &values)))
+}
+
+fn find(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    method: &str,
+) -> Result<Option<(u32, JsValue)>, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    let f = callback(vm, args, method)?;
+    for k in 0..len {
+        let value = get_value(vm, obj, k)?;
+        let found = call(
+            vm,
+            f,
+            args.at(1),
+            &[value, JsValue::new(k as f64), JsValue::new(obj)],
+        )?;
+        if found.to_boolean() {
+            return Ok(Some((k, value)));
+        }
+    }
+    Ok(None)
+}
+
+/// Array.prototype.find ( predicate [ , thisArg ] )
+pub fn array_find(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    Ok(find(vm, args, "find")?
+        .map(|(_, value)| value)
+        .unwrap_or_else(JsValue::undefined))
+}
+
+/// Array.prototype.findIndex ( predicate [ , thisArg ] )
+pub fn array_find_index(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    Ok(match find(vm, args, "findIndex")? {
+        Some((k, _)) => JsValue::new(k as f64),
+        None => JsValue::new(-1),
+    })
+}
+
+/// Nested arrays deeper than this are rejected by flat and flatMap instead of overflowing the
+/// native stack, `[].flat(Infinity)` on an array containing itself would never end otherwise.
+const MAX_FLATTEN_DEPTH: u32 = 4096;
+
+/// FlattenIntoArray ( target, source, sourceLen, start, depth [ , mapperFunction, thisArg ] )
+fn flatten_into_array(
+    vm: &mut VirtualMachine,
+    target: &mut Vec<JsValue>,
+    source: Gc<JsObject>,
+    depth: f64,
+    mapper: Option<(JsValue, JsValue)>,
+    nesting: u32,
+) -> Result<(), JsValue> {
+    if nesting > MAX_FLATTEN_DEPTH {
+        return Err(range_error(
+            vm,
+            "Maximum call stack size exceeded".to_string(),
+        ));
+    }
+    let len = length_of_array_like(vm, source)?;
+    for k in 0..len {
+        let mut element = match get_element(vm, source, k)? {
+            Some(element) => element,
+            None => continue,
+        };
+        if let Some((f, this)) = mapper {
+            element = call(
+                vm,
+                f,
+                this,
+                &[element, JsValue::new(k as f64), JsValue::new(source)],
+            )?;
+        }
+        if depth > 0.0 && element.is_array() {
+            flatten_into_array(
+                vm,
+                target,
+                element.as_object(),
+                depth - 1.0,
+                None,
+                nesting + 1,
+            )?;
+        } else {
+            target.push(element);
+        }
+    }
+    Ok(())
+}
+
+/// Array.prototype.flat ( [ depth ] )
+pub fn array_flat(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let depth = if args.at(0).is_undefined() {
+        1.0
+    } else {
+        args.at(0).to_integer_or_infinity(vm)?.max(0.0)
+    };
+    let mut values = Handle::new(vm.space(), Vec::<JsValue>::new());
+    flatten_into_array(vm, &mut values, obj, depth, None, 0)?;
+    Ok(JsValue::new(JsArray::from_slice(vm, &values)))
+}
+
+/// Array.prototype.flatMap ( mapperFunction [ , thisArg ] )
+pub fn array_flat_map(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let f = callback(vm, args, "flatMap")?;
+    let mut values = Handle::new(vm.space(), Vec::<JsValue>::new());
+    flatten_into_array(vm, &mut values, obj, 1.0, Some((f, args.at(1))), 0)?;
+    Ok(JsValue::new(JsArray::from_slice(vm, &values)))
+}
+
+/// Array.prototype.forEach ( callbackfn [ , thisArg ] )
+pub fn array_for_each(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    let f = callback(vm, args, "forEach")?;
+    for k in 0..len {
+        if let Some(value) = get_element(vm, obj, k)? {
+            call(
+                vm,
+                f,
+                args.at(1),
+                &[value, JsValue::new(k as f64), JsValue::new(obj)],
+            )?;
+        }
+    }
+    Ok(JsValue::undefined())
+}
+
+/// Array.prototype.includes ( searchElement [ , fromIndex ] )
+pub fn array_includes(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    if len == 0 {
+        return Ok(JsValue::new(false));
+    }
+    let start = relative_index(vm, args.at(1), len, 0)?;
+    let search = args.at(0);
+    for k in start..len {
+        let value = get_value(vm, obj, k)?;
+        if JsValue::same_value_zero(value, search) {
+            return Ok(JsValue::new(true));
+        }
+    }
+    Ok(JsValue::new(false))
+}
+
+/// Array.prototype.indexOf ( searchElement [ , fromIndex ] )
+pub fn array_index_of(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    if len == 0 {
+        return Ok(JsValue::new(-1));
+    }
+    let start = relative_index(vm, args.at(1), len, 0)?;
+    let search = args.at(0);
+    for k in start..len {
+        if let Some(value) = get_element(vm, obj, k)? {
+            if value.strict_equal(search) {
+                return Ok(JsValue::new(k as f64));
+            }
+        }
+    }
+    Ok(JsValue::new(-1))
+}
+
+/// Array.prototype.lastIndexOf ( searchElement [ , fromIndex ] )
+pub fn array_last_index_of(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    if len == 0 {
+        return Ok(JsValue::new(-1));
+    }
+    let n = if args.size() > 1 {
+        args.at(1).to_integer_or_infinity(vm)?
+    } else {
+        len as f64 - 1.0
+    };
+    let k = if n >= 0.0 {
+        n.min(len as f64 - 1.0)
+    } else {
+        len as f64 + n
+    };
+    if k < 0.0 {
+        return Ok(JsValue::new(-1));
+    }
+    let search = args.at(0);
+    for k in (0..=k as u32).rev() {
+        if let Some(value) = get_element(vm, obj, k)? {
+            if value.strict_equal(search) {
+                return Ok(JsValue::new(k as f64));
+            }
+        }
+    }
+    Ok(JsValue::new(-1))
+}
+
+/// Array.prototype.map ( callbackfn [ , thisArg ] )
+pub fn array_map(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    let f = callback(vm, args, "map")?;
+    let result = JsArray::new(vm, len).root(vm.space());
+    for k in 0..len {
+        if let Some(value) = get_element(vm, obj, k)? {
+            let mapped = call(
+                vm,
+                f,
+                args.at(1),
+                &[value, JsValue::new(k as f64), JsValue::new(obj)],
+            )?;
+            create_data_property(vm, *result, k, mapped)?;
+        }
+    }
+    Ok(JsValue::new(*result))
+}
+
+fn reduce(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    obj: Gc<JsObject>,
+    method: &str,
+    mut indices: impl Iterator<Item = u32>,
+) -> Result<JsValue, JsValue> {
+    let f = callback(vm, args, method)?;
+    let mut accumulator = if args.size() >= 2 {
+        args.at(1)
+    } else {
+        let mut first = None;
+        for k in &mut indices {
+            first = get_element(vm, obj, k)?;
+            if first.is_some() {
+                break;
+            }
+        }
+        match first {
+            Some(value) => value,
+            None => {
+                return Err(type_error(
+                    vm,
+                    "Reduce of empty array with no initial value",
+                ))
+            }
+        }
+    };
+    for k in indices {
+        if let Some(value) = get_element(vm, obj, k)? {
+            accumulator = call(
+                vm,
+                f,
+                JsValue::undefined(),
+                &[
+                    accumulator,
+                    value,
+                    JsValue::new(k as f64),
+                    JsValue::new(obj),
+                ],
+            )?;
+        }
+    }
+    Ok(accumulator)
+}
+
+/// Array.prototype.reduce ( callbackfn [ , initialValue ] )
+pub fn array_reduce(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    reduce(vm, args, obj, "reduce", 0..len)
+}
+
+/// Array.prototype.reduceRight ( callbackfn [ , initialValue ] )
+pub fn array_reduce_right(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    reduce(vm, args, obj, "reduceRight", (0..len).rev())
+}
+
+/// Array.prototype.reverse ( )
+pub fn array_reverse(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let mut obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    if dense_elements(&obj, 0, len).is_some() {
+        obj.elements.vector.as_mut_slice()[..len as usize].reverse();
+        return Ok(JsValue::new(obj));
+    }
+    for lower in 0..len / 2 {
+        let upper = len - lower - 1;
+        let lower_value = get_element(vm, obj, lower)?;
+        let upper_value = get_element(vm, obj, upper)?;
+        match upper_value {
+            Some(value) => set_element(vm, obj, lower, value)?,
+            None if lower_value.is_some() => {
+                obj.delete(vm, Symbol::Indexed(lower), true)?;
+            }
+            None => continue,
+        }
+        match lower_value {
+            Some(value) => set_element(vm, obj, upper, value)?,
+            None => {
+                obj.delete(vm, Symbol::Indexed(upper), true)?;
+            }
+        }
+    }
+    Ok(JsValue::new(obj))
+}
+
+/// Array.prototype.shift ( )
+pub fn array_shift(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let mut obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    if len == 0 {
+        set_length(vm, obj, 0)?;
+        return Ok(JsValue::undefined());
+    }
+    if can_resize_dense(obj, len, len - 1) {
+        let first = obj.elements.vector[0];
+        obj.elements.vector.splice(vm, 0..1, &[]);
+        obj.elements.set_length(len - 1);
+        return Ok(first);
+    }
+    let first = get_value(vm, obj, 0)?;
+    for k in 1..len {
+        move_element(vm, obj, k, k - 1)?;
+    }
+    obj.delete(vm, Symbol::Indexed(len - 1), true)?;
+    set_length(vm, obj, len - 1)?;
+    Ok(first)
+}
+
+/// Array.prototype.slice ( start, end )
+pub fn array_slice(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    let start = relative_index(vm, args.at(0), len, 0)?;
+    let end = relative_index(vm, args.at(1), len, len)?.max(start);
+    if let Some(elements) = dense_elements(&obj, start, end) {
+        return Ok(JsValue::new(JsArray::from_slice(vm, elements)));
+    }
+    let result = JsArray::new(vm, 0).root(vm.space());
+    for k in start..end {
+        if let Some(value) = get_element(vm, obj, k)? {
+            create_data_property(vm, *result, k - start, value)?;
+        }
+    }
+    set_length(vm, *result, end - start)?;
+    Ok(JsValue::new(*result))
+}
+
+/// Array.prototype.some ( callbackfn [ , thisArg ] )
+pub fn array_some(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    let f = callback(vm, args, "some")?;
+    for k in 0..len {
+        if let Some(value) = get_element(vm, obj, k)? {
+            let result = call(
+                vm,
+                f,
+                args.at(1),
+                &[value, JsValue::new(k as f64), JsValue::new(obj)],
+            )?;
+            if result.to_boolean() {
+                return Ok(JsValue::new(true));
+            }
+        }
+    }
+    Ok(JsValue::new(false))
+}
+
+/// Array.prototype.splice ( start, deleteCount, ...items )
+pub fn array_splice(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let mut obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    let start = relative_index(vm, args.at(0), len, 0)?;
+    let delete_count = match args.size() {
+        0 => 0,
+        1 => len - start,
+        _ => {
+            let count = args.at(1).to_integer_or_infinity(vm)?;
+            count.max(0.0).min((len - start) as f64) as u32
+        }
+    };
+    let items = (2..args.size()).map(|ix| args.at(ix)).collect::<Vec<_>>();
+    let item_count = items.len() as u32;
+    let new_len = len as u64 - delete_count as u64 + item_count as u64;
+    if new_len > u32::MAX as u64 {
+        return Err(range_error(vm, "array size exceeded".to_string()));
+    }
+    let new_len = new_len as u32;
+
+    if can_resize_dense(obj, len, new_len) {
+        let range = start as usize..(start + delete_count) as usize;
+        let removed = Handle::new(
+            vm.space(),
+            obj.elements.vector.as_slice()[range.clone()].to_vec(),
+        );
+        obj.elements.vector.splice(vm, range, &items);
+        obj.elements.set_length(new_len);
+        return Ok(JsValue::new(JsArray::from_slice(vm, &removed)));
+    }
+
+    let removed = JsArray::new(vm, 0).root(vm.space());
+    for k in 0..delete_count {
+        if let Some(value) = get_element(vm, obj, start + k)? {
+            create_data_property(vm, *removed, k, value)?;
+        }
+    }
+    set_length(vm, *removed, delete_count)?;
+    if item_count < delete_count {
+        for k in start..len - delete_count {
+            move_element(vm, obj, k + delete_count, k + item_count)?;
+        }
+        for k in (new_len..len).rev() {
+            obj.delete(vm, Symbol::Indexed(k), true)?;
+        }
+    } else if item_count > delete_count {
+        for k in (start..len - delete_count).rev() {
+            move_element(vm, obj, k + delete_count, k + item_count)?;
+        }
+    }
+    for (ix, item) in items.iter().enumerate() {
+        set_element(vm, obj, start + ix as u32, *item)?;
+    }
+    set_length(vm, obj, new_len)?;
+    Ok(JsValue::new(*removed))
+}
+
+/// Array.prototype.unshift ( ...items )
+pub fn array_unshift(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let mut obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+    let items = (0..args.size()).map(|ix| args.at(ix)).collect::<Vec<_>>();
+    let new_len = len as u64 + items.len() as u64;
+    if new_len > u32::MAX as u64 {
+        return Err(range_error(vm, "array size exceeded".to_string()));
+    }
+    let new_len = new_len as u32;
+    if !items.is_empty() {
+        if can_resize_dense(obj, len, new_len) {
+            obj.elements.vector.splice(vm, 0..0, &items);
+            obj.elements.set_length(new_len);
+            return Ok(JsValue::new(new_len as f64));
+        }
+        let count = items.len() as u32;
+        for k in (0..len).rev() {
+            move_element(vm, obj, k, k + count)?;
+        }
+        for (ix, item) in items.iter().enumerate() {
+            set_element(vm, obj, ix as u32, *item)?;
+        }
+    }
+    set_length(vm, obj, new_len)?;
+    Ok(JsValue::new(new_len as f64))
+}
+
+/// Runs shorter than this are sorted by insertion instead of being split further.
+const INSERTION_SORT_THRESHOLD: usize = 8;
+
+/// Stable merge sort of `items`, `compare` may fail in which case `items` is left in an
+/// unspecified order and the error is returned.
+fn merge_sort(
+    items: &mut [u32],
+    scratch: &mut [u32],
+    compare: &mut dyn FnMut(u32, u32) -> Result<Ordering, JsValue>,
+) -> Result<(), JsValue> {
+    let len = items.len();
+    if len <= INSERTION_SORT_THRESHOLD {
+        for ix in 1..len {
+            let mut jx = ix;
+            while jx > 0 && compare(items[jx - 1], items[jx])? == Ordering::Greater {
+                items.swap(jx - 1, jx);
+                jx -= 1;
+            }
+        }
+        return Ok(());
+    }
+    let mid = len / 2;
+    merge_sort(&mut items[..mid], &mut scratch[..mid], compare)?;
+    merge_sort(&mut items[mid..], &mut scratch[mid..], compare)?;
+    if compare(items[mid - 1], items[mid])? != Ordering::Greater {
+        return Ok(());
+    }
+    let (mut left, mut right) = (0, mid);
+    for slot in scratch[..len].iter_mut() {
+        // ties take the left element, which is what keeps the sort stable.
+        let take_left = right == len
+            || (left < mid && compare(items[left], items[right])? != Ordering::Greater);
+        if take_left {
+            *slot = items[left];
+            left += 1;
+        } else {
+            *slot = items[right];
+            right += 1;
+        }
+    }
+    items.copy_from_slice(&scratch[..len]);
+    Ok(())
+}
+
+/// Array.prototype.sort ( comparefn )
+pub fn array_sort(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let comparefn = args.at(0);
+    if !comparefn.is_undefined() && !comparefn.is_callable() {
+        return Err(type_error(
+            vm,
+            "Array.prototype.sort: comparator must be a function or undefined",
+        ));
+    }
+    let mut obj = args.this.to_object(vm)?;
+    let len = length_of_array_like(vm, obj)?;
+
+    // holes are dropped and undefined is set aside, neither of them reaches the comparator.
+    let mut values = Handle::new(vm.space(), Vec::<JsValue>::new());
+    let mut undefined_count = 0;
+    for k in 0..len {
+        match get_element(vm, obj, k)? {
+            Some(value) if value.is_undefined() => undefined_count += 1,
+            Some(value) => values.push(value),
+            None => (),
+        }
+    }
+
+    // the default comparator orders by ToString of every value, compute each key only once.
+    let mut keys = Handle::new(vm.space(), Vec::<JsValue>::new());
+    if comparefn.is_undefined() {
+        for ix in 0..values.len() {
+            let key = values[ix].to_js_string(vm)?;
+            keys.push(JsValue::new(key));
+        }
+    }
+
+    // the permutation is sorted instead of the values, so a throwing comparator leaves the
+    // array untouched and values reordered by the comparator itself can't be lost.
+    let mut order = (0..values.len() as u32).collect::<Vec<_>>();
+    let mut scratch = vec![0; order.len()];
+    let mut compare = |x: u32, y: u32| -> Result<Ordering, JsValue> {
+        if comparefn.is_undefined() {
+            let x = keys[x as usize].as_string();
+            let y = keys[y as usize].as_string();
+            return Ok((*x).cmp(&*y));
+        }
+        let (x, y) = (values[x as usize], values[y as usize]);
+        let v = call(vm, comparefn, JsValue::undefined(), &[x, y])?.to_number(vm)?;
+        Ok(if v < 0.0 {
+            Ordering::Less
+        } else if v > 0.0 {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        })
+    };
+    merge_sort(&mut order, &mut scratch, &mut compare)?;
+
+    let item_count = order.len() as u32;
+    for (k, ix) in order.iter().enumerate() {
+        set_element(vm, obj, k as u32, values[*ix as usize])?;
+    }
+    for k in item_count..item_count + undefined_count {
+        set_element(vm, obj, k, JsValue::undefined())?;
+    }
+    for k in item_count + undefined_count..len {
+        obj.delete(vm, Symbol::Indexed(k), true)?;
+    }
+    Ok(JsValue::new(obj))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::eval_ok;
+
+    #[test]
+    fn test_search_and_copy() {
+        let source = "var a = [1, 2, 3, 4, 5];
+            result = [a.slice(1, 3).join(), a.slice(-2).join(), a.concat([6, [7]], 8).length,
+                a.indexOf(3), a.lastIndexOf(5), a.includes(4), [NaN].includes(NaN),
+                [NaN].indexOf(NaN), a.at(-1), a.at(9)].join('|');";
+        assert_eq!(eval_ok(source), "2,3|4,5|8|2|4|true|true|-1|5|");
+    }
+
+    #[test]
+    fn test_mutators() {
+        let source = "var a = [1, 2, 3, 4, 5];
+            var removed = a.splice(1, 2, 'x', 'y', 'z');
+            var b = [1, 2, 3];
+            var first = b.shift();
+            var length = b.unshift(0, -1);
+            var c = [1, 2, 3];
+            c.length = 1;
+            result = [removed.join(), a.join(), first, length, b.reverse().join(), c.join(),
+                [].shift(), [].pop()].join('|');";
+        assert_eq!(eval_ok(source), "2,3|1,x,y,z,4,5|1|4|3,2,-1,0|1||");
+    }
+
+    #[test]
+    fn test_callbacks() {
+        let source = "var a = [1, 2, 3, 4];
+            var seen = [];
+            a.forEach(function (v, i) { seen.push(i + ':' + v); });
+            result = [seen.join(), a.map(function (v) { return v * 2; }).join(),
+                a.filter(function (v) { return v > 2; }).join(),
+                a.reduce(function (s, v) { return s + v; }),
+                a.reduceRight(function (s, v) { return s + v; }, ''),
+                a.some(function (v) { return v > 3; }), a.every(function (v) { return v > 3; }),
+                a.find(function (v) { return v > 2; }), a.findIndex(function (v) { return v > 9; })]
+                .join('|');";
+        assert_eq!(
+            eval_ok(source),
+            "0:1,1:2,2:3,3:4|2,4,6,8|3,4|10|4321|true|false|3|-1"
+        );
+    }
+
+    #[test]
+    fn test_callbacks_skip_holes() {
+        let source = "var a = [1];
+            a[2] = 3;
+            var count = 0;
+            a.forEach(function () { count = count + 1; });
+            var m = a.map(function (v) { return v * 2; });
+            result = [count, m.length, 1 in m, m[2], a.filter(function () { return true; }).length,
+                a.indexOf(undefined), a.includes(undefined), a.reverse().join(), 1 in a].join('|');";
+        assert_eq!(eval_ok(source), "2|3|false|6|2|-1|true|3,,1|false");
+    }
+
+    #[test]
+    fn test_callback_growing_array_visits_original_length() {
+        let source = "var a = [3, 1, 2];
+            var m = a.map(function (v, i, arr) { arr.push(9); return v; });
+            result = m.length + ' ' + a.length;";
+        assert_eq!(eval_ok(source), "3 6");
+    }
+
+    #[test]
+    fn test_fill_copy_within_and_flat() {
+        let source =
+            "result = [[1, 2, 3, 4].fill(0, 1, 3).join(), [1, 2, 3, 4, 5].copyWithin(0, 3).join(),
+                [1, [2, [3, [4]]]].flat().length, [1, [2, [3, [4]]]].flat(Infinity).join(),
+                [1, 2].flatMap(function (v) { return [v, v * 10]; }).join()].join('|');";
+        assert_eq!(eval_ok(source), "1,0,0,4|4,5,3,4,5|3|1,2,3,4|1,10,2,20");
+    }
+
+    #[test]
+    fn test_iterators() {
+        let source = "var a = ['a', 'b'], k = [], v = [], e = [];
+            for (var x of a.keys()) k.push(x);
+            for (var x of a.values()) v.push(x);
+            for (var x of a.entries()) e.push(x.join(':'));
+            result = k.join() + ' ' + v.join() + ' ' + e.join();";
+        assert_eq!(eval_ok(source), "0,1 a,b 0:a,1:b");
+    }
+
+    #[test]
+    fn test_generic_receivers() {
+        let source = "var o = { length: 2, 0: 'a', 1: 'b' };
+            o.slice = Array.prototype.slice;
+            o.map = Array.prototype.map;
+            result = o.slice(0).join() + ' ' + o.map(function (v) { return v + v; }).join();
+            try { [].reduce(function () {}); } catch (e) { result += ' ' + e.name; }";
+        assert_eq!(eval_ok(source), "a,b aa,bb TypeError");
+    }
+
+    #[test]
+    fn test_sort_default_and_comparator() {
+        let source = "var a = ['b', 'a', 'c'];
+            var r = a.sort();
+            result = [10, 9, 1, 100, 25].sort().join() + ' ' +
+                [10, 9, 1, 100, 25].sort(function (a, b) { return a - b; }).join() + ' ' +
+                (r === a) + r.join();";
+        assert_eq!(eval_ok(source), "1,10,100,25,9 1,9,10,25,100 truea,b,c");
+    }
+
+    #[test]
+    fn test_sort_is_stable() {
+        let source = "var a = [];
+            for (var i = 0; i < 50; i = i + 1) a.push({ k: i - Math.floor(i / 5) * 5, i: i });
+            a.sort(function (x, y) { return x.k - y.k; });
+            result = true;
+            for (var i = 1; i < 50; i = i + 1) {
+                if (a[i - 1].k > a[i].k || (a[i - 1].k == a[i].k && a[i - 1].i > a[i].i)) {
+                    result = false;
+                }
+            }";
+        assert_eq!(eval_ok(source), "true");
+    }
+
+    #[test]
+    fn test_sort_moves_undefined_and_holes_last() {
+        let source = "var a = [3, undefined, 1];
+            a[5] = 2;
+            a.sort();
+            result = a.length + ' ' + a.join() + ' ' + (3 in a) + (4 in a) + (5 in a);";
+        assert_eq!(eval_ok(source), "6 1,2,3,,, truefalsefalse");
+    }
+
+    #[test]
+    fn test_sort_comparator_throws_or_mutates() {
+        let source = "var a = [3, 1, 2];
+            try { a.sort(function () { throw 'boom'; }); } catch (e) { result = e; }
+            var b = [5, 4, 3, 2, 1];
+            b.sort(function (x, y) { b.length = 0; b.push(9, 9, 9, 9, 9, 9, 9); return x - y; });
+            result += ' ' + a.slice().sort().join() + ' ' + b.length;
+            try { [1, 2].sort(5); } catch (e) { result += ' ' + e.name; }";
+        assert_eq!(eval_ok(source), "boom 1,2,3 7 TypeError");
+    }
+
+    #[test]
+    fn test_sort_generic_and_large() {
+        let source = "var o = { length: 3, 0: 'c', 1: 'a', 2: 'b', sort: Array.prototype.sort };
+            o.sort();
+            var big = [];
+            for (var i = 0; i < 5000; i = i + 1) big.push(5000 - i);
+            big.sort(function (a, b) { return a - b; });
+            result = o[0] + o[1] + o[2] + ' ' + big[0] + ',' + big[4999];";
+        assert_eq!(eval_ok(source), "abc 1,5000");
+    }
+
+    #[test]
+    fn test_prototype_methods_are_not_enumerable() {
+        let source = "var keys = [];
+            for (var k in [1, 2, 3]) { keys.push(k); }
+            result = keys.join(',') + ' ' + Array.prototype.propertyIsEnumerable('push');";
+        assert_eq!(eval_ok(source), "0,1,2 false");
+    }
+
+    #[test]
+    fn test_from_iterables_and_array_likes() {
+        let source = "function* gen() { yield 1; yield 2; yield 3; }
+            var it = {};
+            it[Symbol.iterator] = function () {
+                var i = 0;
+                return { next: function () { i += 1; return { value: i * 10, done: i > 2 }; } };
+            };
+            var a = Array.from(gen());
+            var like = Array.from({ length: 3, 0: 'a', 2: 'c' });
+            result = [a.join(), Array.from('hi').join(), Array.from(it).join(),
+                like.length, typeof like[1], 1 in like, Array.from([4, , 6]).join()].join('|');";
+        assert_eq!(eval_ok(source), "1,2,3|h,i|10,20|3|undefined|true|4,,6");
+    }
+
+    #[test]
+    fn test_from_map_fn() {
+        let source = "function* gen() { yield 1; yield 2; }
+            var o = { k: 100 };
+            var mapped = Array.from(gen(), function (v, i) { return v * 10 + i + this.k; }, o);
+            var like = Array.from({ length: 2, 0: 3, 1: 4 }, function (v, i) { return v + i; });
+            var closed = false;
+            var it = {};
+            it[Symbol.iterator] = function () {
+                return {
+                    next: function () { return { value: 1, done: false }; },
+                    return: function () { closed = true; return {}; }
+                };
+            };
+            try { Array.from(it, function () { throw 'stop'; }); } catch (e) { closed = closed + e; }
+            try { Array.from([], 5); } catch (e) { closed += ' ' + e.name; }
+            result = [mapped.join(), like.join(), closed].join('|');";
+        assert_eq!(eval_ok(source), "110,121|3,5|truestop TypeError");
+    }
+}
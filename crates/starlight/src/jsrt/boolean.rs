@@ -0,0 +1,75 @@
+use crate::{
+    runtime::{
+        arguments::Arguments, boolean::JsBoolean, object::ObjectTag, promise::type_error,
+        string::JsString, value::JsValue,
+    },
+    vm::VirtualMachine,
+};
+
+/// 20.3.1.1 Boolean ( value )
+pub fn boolean_constructor(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let b = args.at(0).to_boolean();
+    if !args.ctor_call {
+        return Ok(JsValue::new(b));
+    }
+    Ok(JsValue::new(JsBoolean::new(vm, b)))
+}
+
+/// thisBooleanValue ( value )
+fn this_boolean_value(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    method: &str,
+) -> Result<bool, JsValue> {
+    let this = args.this;
+    if this.is_boolean() {
+        return Ok(this.as_boolean());
+    }
+    if this.is_object() && this.as_object().tag() == ObjectTag::Boolean {
+        return Ok(this.as_object().as_boolean().value());
+    }
+    Err(type_error(
+        vm,
+        &format!(
+            "Boolean.prototype.{} requires that 'this' be a Boolean",
+            method
+        ),
+    ))
+}
+
+/// Boolean.prototype.toString ( )
+pub fn boolean_to_string(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let b = this_boolean_value(vm, args, "toString")?;
+    let s = if b { "true" } else { "false" };
+    Ok(JsValue::new(JsString::new(vm, s)))
+}
+
+/// Boolean.prototype.valueOf ( )
+pub fn boolean_value_of(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let b = this_boolean_value(vm, args, "valueOf")?;
+    Ok(JsValue::new(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::eval_ok;
+
+    #[test]
+    fn test_boolean_constructor_and_prototype() {
+        let source = "var b = new Boolean(false);
+            result = [Boolean(''), Boolean('x'), Boolean(0), Boolean(NaN), Boolean({}), typeof b,
+                !!b, b.valueOf(), b.toString(), true.toString(), Boolean.prototype.valueOf.length]
+                .join('|');";
+        assert_eq!(
+            eval_ok(source),
+            "false|true|false|false|true|object|true|false|false|true|0"
+        );
+    }
+
+    #[test]
+    fn test_boolean_methods_check_this() {
+        let source = "var o = { b: Boolean.prototype.valueOf };
+            try { o.b(); } catch (e) { result = e.name; }";
+        assert_eq!(eval_ok(source), "TypeError");
+    }
+}
@@ -1,7 +1,13 @@
 use crate::{
+    gc::handle::Handle,
     runtime::{
         arguments::Arguments,
-        error::{JsError, JsEvalError, JsRangeError, JsReferenceError, JsTypeError},
+        array::JsArray,
+        error::{
+            JsAggregateError, JsError, JsEvalError, JsRangeError, JsReferenceError, JsSyntaxError,
+            JsTypeError,
+        },
+        iterator::{get_iterator, iterator_step},
         object::JsObject,
         slot::Slot,
         string::JsString,
@@ -14,7 +20,7 @@ use crate::{
 pub fn error_constructor(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
     let message = args.at(0).to_string(vm)?;
     let msg = JsString::new(vm, message).root(vm.space());
-    Ok(JsValue::new(JsError::new(vm, *msg, None)))
+    Ok(JsValue::new(JsError::new(vm, *msg, args.ctor_structure())))
 }
 
 pub fn eval_error_constructor(
@@ -24,7 +30,11 @@ pub fn eval_error_constructor(
 ) -> Result<JsValue, JsValue> {
     let message = args.at(0).to_string(vm)?;
     let msg = JsString::new(vm, message).root(vm.space());
-    Ok(JsValue::new(JsEvalError::new(vm, *msg, None)))
+    Ok(JsValue::new(JsEvalError::new(
+        vm,
+        *msg,
+        args.ctor_structure(),
+    )))
 }
 
 pub fn reference_error_constructor(
@@ -34,7 +44,11 @@ pub fn reference_error_constructor(
 ) -> Result<JsValue, JsValue> {
     let message = args.at(0).to_string(vm)?;
     let msg = JsString::new(vm, message).root(vm.space());
-    Ok(JsValue::new(JsReferenceError::new(vm, *msg, None)))
+    Ok(JsValue::new(JsReferenceError::new(
+        vm,
+        *msg,
+        args.ctor_structure(),
+    )))
 }
 
 pub fn type_error_constructor(
@@ -44,7 +58,11 @@ pub fn type_error_constructor(
 ) -> Result<JsValue, JsValue> {
     let message = args.at(0).to_string(vm)?;
     let msg = JsString::new(vm, message).root(vm.space());
-    Ok(JsValue::new(JsTypeError::new(vm, *msg, None)))
+    Ok(JsValue::new(JsTypeError::new(
+        vm,
+        *msg,
+        args.ctor_structure(),
+    )))
 }
 
 pub fn syntax_error_constructor(
@@ -54,7 +72,11 @@ pub fn syntax_error_constructor(
 ) -> Result<JsValue, JsValue> {
     let message = args.at(0).to_string(vm)?;
     let msg = JsString::new(vm, message).root(vm.space());
-    Ok(JsValue::new(JsEvalError::new(vm, *msg, None)))
+    Ok(JsValue::new(JsSyntaxError::new(
+        vm,
+        *msg,
+        args.ctor_structure(),
+    )))
 }
 
 pub fn range_error_constructor(
@@ -64,7 +86,40 @@ pub fn range_error_constructor(
 ) -> Result<JsValue, JsValue> {
     let message = args.at(0).to_string(vm)?;
     let msg = JsString::new(vm, message).root(vm.space());
-    Ok(JsValue::new(JsRangeError::new(vm, *msg, None)))
+    Ok(JsValue::new(JsRangeError::new(
+        vm,
+        *msg,
+        args.ctor_structure(),
+    )))
+}
+
+/// AggregateError ( errors, message )
+pub fn aggregate_error_constructor(
+    vm: &mut VirtualMachine,
+
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let msg = if args.at(1).is_undefined() {
+        String::new()
+    } else {
+        args.at(1).to_string(vm)?
+    };
+    let msg = JsString::new(vm, msg).root(vm.space());
+    let (iterator, next) = get_iterator(vm, args.at(0))?;
+    let iterator = Handle::new(vm.space(), iterator);
+    let next = Handle::new(vm.space(), next);
+    let mut errors = JsArray::new(vm, 0).root(vm.space());
+    let mut index = 0;
+    while let Some(value) = iterator_step(vm, *iterator, *next)? {
+        errors.put(vm, Symbol::Indexed(index), value, false)?;
+        index += 1;
+    }
+    Ok(JsValue::new(JsAggregateError::new(
+        vm,
+        *msg,
+        JsValue::new(*errors),
+        args.ctor_structure(),
+    )))
 }
 
 /// section 15.11.4.4 Error.prototype.toString()
@@ -109,3 +164,32 @@ pub fn error_to_string(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsVa
         return Err(JsValue::new(JsTypeError::new(vm, *msg, None)));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::eval_ok;
+
+    #[test]
+    fn test_syntax_error_constructor() {
+        let source = "
+            var e = new SyntaxError('bad');
+            result = e.toString() + ',' + (e.constructor === SyntaxError);";
+        assert_eq!(eval_ok(source), "SyntaxError: bad,true");
+    }
+
+    #[test]
+    fn test_thrown_syntax_error() {
+        let source = "try { JSON.parse('{'); } catch (e) { result = e.name; }";
+        assert_eq!(eval_ok(source), "SyntaxError");
+    }
+
+    #[test]
+    fn test_aggregate_error_constructor() {
+        let source = "
+            function* gen() { yield 'a'; yield 'b'; }
+            var e = new AggregateError(gen(), 'many');
+            result = [String(e), e.errors.length, e.errors[1], Array.isArray(e.errors),
+                e instanceof AggregateError, e.constructor === AggregateError].join('|');";
+        assert_eq!(eval_ok(source), "AggregateError: many|2|b|true|true|true");
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{
+    runtime::{
+        arguments::Arguments, error::JsTypeError, generator::ResumeMode, object::ObjectTag,
+        string::JsString, value::JsValue,
+    },
+    vm::VirtualMachine,
+};
+
+fn generator_resume(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    mode: ResumeMode,
+    method: &str,
+) -> Result<JsValue, JsValue> {
+    let this = args.this;
+    if !this.is_object() || this.as_object().tag() != ObjectTag::Generator {
+        let msg = JsString::new(vm, format!("Generator.prototype.{} is not generic", method));
+        return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
+    }
+    vm.resume_generator(this.as_object(), args.at(0), mode)
+}
+
+/// %GeneratorPrototype%.next ( value )
+pub fn generator_next(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    generator_resume(vm, args, ResumeMode::Next, "next")
+}
+
+/// %GeneratorPrototype%.return ( value )
+pub fn generator_return(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    generator_resume(vm, args, ResumeMode::Return, "return")
+}
+
+/// %GeneratorPrototype%.throw ( exception )
+pub fn generator_throw(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    generator_resume(vm, args, ResumeMode::Throw, "throw")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::eval_ok;
+
+    #[test]
+    fn test_generator_next_sends_values() {
+        let source = "function* g() { var x = yield 1; yield x + 1; return 'done'; }
+            var it = g();
+            var a = it.next(), b = it.next(5), c = it.next(), d = it.next();
+            result = a.value + ',' + a.done + ' ' + b.value + ' ' + c.value + ',' + c.done + ' ' +
+                d.value + ',' + d.done;";
+        assert_eq!(eval_ok(source), "1,false 6 done,true undefined,true");
+    }
+
+    #[test]
+    fn test_generator_return_runs_finally() {
+        let source = "var log = '';
+            function* g() { try { yield 1; yield 2; } finally { log += 'f'; } }
+            var it = g();
+            it.next();
+            var r = it.return(9);
+            result = r.value + ',' + r.done + log + it.next().done;";
+        assert_eq!(eval_ok(source), "9,trueftrue");
+    }
+
+    #[test]
+    fn test_generator_throw() {
+        let source = "function* g() { try { yield 1; } catch (e) { yield 'caught ' + e; } }
+            function* h() { yield 1; }
+            var it = g();
+            it.next();
+            result = it.throw('x').value;
+            it = h();
+            it.next();
+            try { it.throw('boom'); } catch (e) { result += ' ' + e + it.next().done; }";
+        assert_eq!(eval_ok(source), "caught x boomtrue");
+    }
+
+    #[test]
+    fn test_generators_are_iterable() {
+        let source = "function* inner() { yield 'a'; yield 'b'; return 'r'; }
+            function* outer() { var r = yield* inner(); yield r; }
+            var o = { *m() { yield this.k; }, k: 7 };
+            var s = '';
+            for (var v of outer()) s += v;
+            var it = outer();
+            result = s + (it[Symbol.iterator]() === it) + o.m().next().value;";
+        assert_eq!(eval_ok(source), "abrtrue7");
+    }
+
+    #[test]
+    fn test_running_generator_cannot_be_resumed() {
+        let source = "function* g() { it.next(); }
+            var it = g();
+            try { it.next(); } catch (e) { result = e.name; }";
+        assert_eq!(eval_ok(source), "TypeError");
+    }
+}
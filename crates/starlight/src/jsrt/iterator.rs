@@ -0,0 +1,116 @@
+use crate::{
+    runtime::{
+        arguments::Arguments,
+        array::JsArray,
+        error::JsTypeError,
+        iterator::{create_iter_result_object, IterationKind},
+        object::ObjectTag,
+        string::JsString,
+        symbol::Symbol,
+        value::JsValue,
+    },
+    vm::VirtualMachine,
+};
+
+/// %IteratorPrototype% [ @@iterator ] ( )
+pub fn iterator_prototype_iterator(
+    _vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    Ok(args.this)
+}
+
+/// %ArrayIteratorPrototype%.next ( )
+pub fn array_iterator_next(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let this = args.this;
+    if !this.is_object() || this.as_object().tag() != ObjectTag::ArrayIterator {
+        let msg = JsString::new(vm, "ArrayIterator.prototype.next is not generic");
+        return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
+    }
+    let mut obj = this.as_object().root(vm.space());
+    let iterated = match obj.as_array_iterator().iterated {
+        Some(iterated) => iterated.root(vm.space()),
+        None => {
+            return Ok(JsValue::new(create_iter_result_object(
+                vm,
+                JsValue::undefined(),
+                true,
+            )))
+        }
+    };
+    let index = obj.as_array_iterator().index;
+    let len = iterated.get(vm, Symbol::length())?.to_number(vm)?;
+    if index as f64 >= len || len.is_nan() {
+        obj.as_array_iterator_mut().iterated = None;
+        return Ok(JsValue::new(create_iter_result_object(
+            vm,
+            JsValue::undefined(),
+            true,
+        )));
+    }
+    obj.as_array_iterator_mut().index = index + 1;
+    let result = match obj.as_array_iterator().kind {
+        IterationKind::Key => JsValue::new(index as f64),
+        IterationKind::Value => iterated.get(vm, Symbol::Indexed(index))?,
+        IterationKind::KeyValue => {
+            let value = iterated.get(vm, Symbol::Indexed(index))?;
+            let mut entry = JsArray::new(vm, 2).root(vm.space());
+            entry.put(vm, Symbol::Indexed(0), JsValue::new(index as f64), false)?;
+            entry.put(vm, Symbol::Indexed(1), value, false)?;
+            JsValue::new(*entry)
+        }
+    };
+    Ok(JsValue::new(create_iter_result_object(vm, result, false)))
+}
+
+/// %StringIteratorPrototype%.next ( )
+///
+/// Yields code points, so surrogate pairs are produced as one string.
+pub fn string_iterator_next(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let this = args.this;
+    if !this.is_object() || this.as_object().tag() != ObjectTag::StringIterator {
+        let msg = JsString::new(vm, "StringIterator.prototype.next is not generic");
+        return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
+    }
+    let mut obj = this.as_object().root(vm.space());
+    let string = match obj.as_string_iterator().string {
+        Some(string) => string,
+        None => {
+            return Ok(JsValue::new(create_iter_result_object(
+                vm,
+                JsValue::undefined(),
+                true,
+            )))
+        }
+    };
+    let position = obj.as_string_iterator().position;
+    if position >= string.len() {
+        obj.as_string_iterator_mut().string = None;
+        return Ok(JsValue::new(create_iter_result_object(
+            vm,
+            JsValue::undefined(),
+            true,
+        )));
+    }
+    let first = string.at(position);
+    let size = if (0xd800..=0xdbff).contains(&first)
+        && position + 1 < string.len()
+        && (0xdc00..=0xdfff).contains(&string.at(position + 1))
+    {
+        2
+    } else {
+        1
+    };
+    obj.as_string_iterator_mut().position = position + size;
+    let units = string
+        .iter()
+        .skip(position as usize)
+        .take(size as usize)
+        .collect::<Vec<_>>();
+    let result = JsString::from_utf16(vm, &units);
+    Ok(JsValue::new(create_iter_result_object(
+        vm,
+        JsValue::new(result),
+        false,
+    )))
+}
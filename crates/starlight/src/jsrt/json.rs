@@ -0,0 +1,721 @@
+use std::fmt::Write;
+
+use crate::{
+    gc::{cell::Gc, handle::Handle},
+    runtime::{
+        arguments::Arguments,
+        array::JsArray,
+        attributes::*,
+        error::JsSyntaxError,
+        method_table::*,
+        object::{EnumerationMode, JsObject, ObjectTag},
+        promise::{call, type_error},
+        property_descriptor::DataDescriptor,
+        string::JsString,
+        symbol::Symbol,
+        value::JsValue,
+    },
+    vm::VirtualMachine,
+};
+
+use super::{object::key_to_value, string::range_error};
+
+/// The `JSON` namespace object. It only exists to give the object its class name.
+pub struct JsJSON;
+
+impl JsJSON {
+    crate::define_jsclass_with_symbol!(JsObject, JSON, JSON);
+}
+
+/// Objects and arrays nested deeper than this are rejected by parse and stringify instead of
+/// overflowing the native stack.
+const MAX_JSON_DEPTH: usize = 512;
+
+fn too_deep(vm: &mut VirtualMachine) -> JsValue {
+    range_error(vm, "Maximum call stack size exceeded".to_string())
+}
+
+fn syntax_error(vm: &mut VirtualMachine, msg: String) -> JsValue {
+    let msg = JsString::new(vm, msg).root(vm.space());
+    JsValue::new(JsSyntaxError::new(vm, *msg, None))
+}
+
+/// Recursive descent parser for the JSON grammar of ECMA-404. The text is kept as UTF-16
+/// code units so escaped lone surrogates and error positions are the ones JS code sees.
+struct JsonParser<'a> {
+    units: &'a [u16],
+    pos: usize,
+    /// Number of objects and arrays being parsed.
+    depth: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.units
+            .get(self.pos)
+            .map(|unit| char::from_u32(*unit as u32).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(' ') | Some('\t') | Some('\n') | Some('\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn unexpected(&self, vm: &mut VirtualMachine) -> JsValue {
+        match self.peek() {
+            Some(c) => syntax_error(
+                vm,
+                format!("Unexpected token {} in JSON at position {}", c, self.pos),
+            ),
+            None => syntax_error(vm, "Unexpected end of JSON input".to_owned()),
+        }
+    }
+
+    fn expect(&mut self, vm: &mut VirtualMachine, c: char) -> Result<(), JsValue> {
+        if self.peek() != Some(c) {
+            return Err(self.unexpected(vm));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn parse(&mut self, vm: &mut VirtualMachine) -> Result<JsValue, JsValue> {
+        let value = self.parse_value(vm)?;
+        self.skip_whitespace();
+        if self.pos != self.units.len() {
+            return Err(self.unexpected(vm));
+        }
+        Ok(value)
+    }
+
+    fn parse_value(&mut self, vm: &mut VirtualMachine) -> Result<JsValue, JsValue> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.parse_object(vm),
+            Some('[') => self.parse_array(vm),
+            Some('"') => {
+                let s = self.parse_string(vm)?;
+                Ok(JsValue::new(JsString::from_utf16(vm, &s)))
+            }
+            Some('-') | Some('0'..='9') => self.parse_number(vm),
+            Some('t') => self.parse_literal(vm, "true", JsValue::new(true)),
+            Some('f') => self.parse_literal(vm, "false", JsValue::new(false)),
+            Some('n') => self.parse_literal(vm, "null", JsValue::null()),
+            _ => Err(self.unexpected(vm)),
+        }
+    }
+
+    fn parse_literal(
+        &mut self,
+        vm: &mut VirtualMachine,
+        word: &str,
+        value: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        for c in word.chars() {
+            self.expect(vm, c)?;
+        }
+        Ok(value)
+    }
+
+    fn parse_object(&mut self, vm: &mut VirtualMachine) -> Result<JsValue, JsValue> {
+        self.enter(vm)?;
+        let result = self.parse_object_members(vm);
+        self.depth -= 1;
+        result
+    }
+
+    fn parse_object_members(&mut self, vm: &mut VirtualMachine) -> Result<JsValue, JsValue> {
+        self.pos += 1;
+        let mut obj = JsObject::new_empty(vm).root(vm.space());
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(JsValue::new(*obj));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.unexpected(vm));
+            }
+            let key = self.parse_string(vm)?;
+            let key = vm.intern_utf16(&key);
+            self.skip_whitespace();
+            self.expect(vm, ':')?;
+            let value = self.parse_value(vm)?;
+            obj.define_own_property(vm, key, &*DataDescriptor::new(value, W | C | E), false)?;
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(JsValue::new(*obj));
+                }
+                _ => return Err(self.unexpected(vm)),
+            }
+        }
+    }
+
+    fn parse_array(&mut self, vm: &mut VirtualMachine) -> Result<JsValue, JsValue> {
+        self.enter(vm)?;
+        let result = self.parse_array_elements(vm);
+        self.depth -= 1;
+        result
+    }
+
+    fn enter(&mut self, vm: &mut VirtualMachine) -> Result<(), JsValue> {
+        if self.depth == MAX_JSON_DEPTH {
+            return Err(too_deep(vm));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn parse_array_elements(&mut self, vm: &mut VirtualMachine) -> Result<JsValue, JsValue> {
+        self.pos += 1;
+        let mut values = Handle::new(vm.space(), Vec::<JsValue>::new());
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+        } else {
+            loop {
+                let value = self.parse_value(vm)?;
+                values.push(value);
+                self.skip_whitespace();
+                match self.peek() {
+                    Some(',') => self.pos += 1,
+                    Some(']') => {
+                        self.pos += 1;
+                        break;
+                    }
+                    _ => return Err(self.unexpected(vm)),
+                }
+            }
+        }
+        let mut ary = JsArray::new(vm, values.len() as _).root(vm.space());
+        for (ix, value) in values.iter().enumerate() {
+            ary.put(vm, Symbol::Indexed(ix as _), *value, false)?;
+        }
+        Ok(JsValue::new(*ary))
+    }
+
+    fn parse_string(&mut self, vm: &mut VirtualMachine) -> Result<Vec<u16>, JsValue> {
+        self.pos += 1;
+        let mut result = vec![];
+        loop {
+            let unit = match self.units.get(self.pos) {
+                Some(unit) => *unit,
+                None => return Err(self.unexpected(vm)),
+            };
+            match unit {
+                0x22 => {
+                    self.pos += 1;
+                    return Ok(result);
+                }
+                0x5c => {
+                    self.pos += 1;
+                    let escaped = match self.peek() {
+                        Some('"') => 0x22,
+                        Some('\\') => 0x5c,
+                        Some('/') => 0x2f,
+                        Some('b') => 0x08,
+                        Some('f') => 0x0c,
+                        Some('n') => 0x0a,
+                        Some('r') => 0x0d,
+                        Some('t') => 0x09,
+                        Some('u') => {
+                            let mut code = 0;
+                            for _ in 0..4 {
+                                self.pos += 1;
+                                match self.peek().and_then(|c| c.to_digit(16)) {
+                                    Some(digit) => code = code * 16 + digit as u16,
+                                    None => return Err(self.unexpected(vm)),
+                                }
+                            }
+                            code
+                        }
+                        _ => return Err(self.unexpected(vm)),
+                    };
+                    result.push(escaped);
+                    self.pos += 1;
+                }
+                0x00..=0x1f => return Err(self.unexpected(vm)),
+                _ => {
+                    result.push(unit);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    fn parse_number(&mut self, vm: &mut VirtualMachine) -> Result<JsValue, JsValue> {
+        let start = self.pos;
+        if self.peek() == Some('-') {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some('0') => self.pos += 1,
+            Some('1'..='9') => self.skip_digits(),
+            _ => return Err(self.unexpected(vm)),
+        }
+        if self.peek() == Some('.') {
+            self.pos += 1;
+            if !matches!(self.peek(), Some('0'..='9')) {
+                return Err(self.unexpected(vm));
+            }
+            self.skip_digits();
+        }
+        if let Some('e') | Some('E') = self.peek() {
+            self.pos += 1;
+            if let Some('+') | Some('-') = self.peek() {
+                self.pos += 1;
+            }
+            if !matches!(self.peek(), Some('0'..='9')) {
+                return Err(self.unexpected(vm));
+            }
+            self.skip_digits();
+        }
+        // the number grammar only admits ASCII so the slice converts losslessly.
+        let text = String::from_utf16_lossy(&self.units[start..self.pos]);
+        Ok(JsValue::new(text.parse::<f64>().unwrap()))
+    }
+
+    fn skip_digits(&mut self) {
+        while let Some('0'..='9') = self.peek() {
+            self.pos += 1;
+        }
+    }
+}
+
+/// Own enumerable string keys of `obj` for an array or a plain object, in property order.
+fn json_keys(vm: &mut VirtualMachine, obj: Gc<JsObject>) -> Result<Vec<Symbol>, JsValue> {
+    if obj.tag() == ObjectTag::Array {
+        let len = obj.get(vm, Symbol::length())?.to_number(vm)?;
+        return Ok((0..len as u32).map(Symbol::Indexed).collect());
+    }
+    let mut keys = obj.get_own_property_keys(vm, EnumerationMode::Default);
+    keys.retain(|key| !key.is_unique());
+    Ok(keys)
+}
+
+/// 25.5.1.1 InternalizeJSONProperty ( holder, name, reviver )
+fn internalize_json_property(
+    vm: &mut VirtualMachine,
+    holder: Gc<JsObject>,
+    name: Symbol,
+    reviver: JsValue,
+    depth: usize,
+) -> Result<JsValue, JsValue> {
+    let val = holder.get(vm, name)?;
+    if val.is_object() {
+        if depth == MAX_JSON_DEPTH {
+            return Err(too_deep(vm));
+        }
+        let mut obj = val.as_object().root(vm.space());
+        for key in json_keys(vm, *obj)? {
+            let element = internalize_json_property(vm, *obj, key, reviver, depth + 1)?;
+            if element.is_undefined() {
+                obj.delete(vm, key, false)?;
+            } else {
+                obj.define_own_property(vm, key, &*DataDescriptor::new(element, W | C | E), false)?;
+            }
+        }
+    }
+    let name = key_to_value(vm, name);
+    call(vm, reviver, JsValue::new(holder), &[name, val])
+}
+
+/// JSON.parse ( text [ , reviver ] )
+pub fn json_parse(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let units = args.at(0).to_js_string(vm)?.to_utf16().into_owned();
+    let value = JsonParser {
+        units: &units,
+        pos: 0,
+        depth: 0,
+    }
+    .parse(vm)?;
+    let reviver = args.at(1);
+    if !reviver.is_callable() {
+        return Ok(value);
+    }
+    let mut root = JsObject::new_empty(vm).root(vm.space());
+    let name = vm.intern("");
+    root.define_own_property(vm, name, &*DataDescriptor::new(value, W | C | E), false)?;
+    internalize_json_property(vm, *root, name, reviver, 0)
+}
+
+/// 25.5.2.2 QuoteJSONString ( value ). Lone surrogates are written as `\u` escapes.
+fn quote_json_string(out: &mut String, units: &[u16]) {
+    out.push('"');
+    let mut ix = 0;
+    while ix < units.len() {
+        let unit = units[ix];
+        match unit {
+            0x08 => out.push_str("\\b"),
+            0x09 => out.push_str("\\t"),
+            0x0a => out.push_str("\\n"),
+            0x0c => out.push_str("\\f"),
+            0x0d => out.push_str("\\r"),
+            0x22 => out.push_str("\\\""),
+            0x5c => out.push_str("\\\\"),
+            0xd800..=0xdbff
+                if ix + 1 < units.len() && (0xdc00..=0xdfff).contains(&units[ix + 1]) =>
+            {
+                let code =
+                    0x10000 + (((unit as u32) - 0xd800) << 10) + (units[ix + 1] as u32 - 0xdc00);
+                out.push(char::from_u32(code).unwrap());
+                ix += 1;
+            }
+            0x00..=0x1f | 0xd800..=0xdfff => {
+                let _ = write!(out, "\\u{:04x}", unit);
+            }
+            _ => out.push(char::from_u32(unit as u32).unwrap()),
+        }
+        ix += 1;
+    }
+    out.push('"');
+}
+
+fn quote(units: &[u16]) -> String {
+    let mut out = String::with_capacity(units.len() + 2);
+    quote_json_string(&mut out, units);
+    out
+}
+
+/// State of one `JSON.stringify` call.
+struct JsonStringifier {
+    replacer: Option<JsValue>,
+    property_list: Option<Vec<Symbol>>,
+    gap: String,
+    indent: String,
+    /// Objects being serialized, used to detect cycles.
+    stack: Vec<Gc<JsObject>>,
+}
+
+impl JsonStringifier {
+    /// 25.5.2.1 SerializeJSONProperty ( state, key, holder ). `None` stands for `undefined`.
+    fn serialize_property(
+        &mut self,
+        vm: &mut VirtualMachine,
+        key: Symbol,
+        holder: Gc<JsObject>,
+    ) -> Result<Option<String>, JsValue> {
+        let mut value = holder.get(vm, key)?;
+        if value.is_object() {
+            let to_json = vm.intern_or_known_symbol("toJSON");
+            let to_json = value.as_object().get(vm, to_json)?;
+            if to_json.is_callable() {
+                let key = key_to_value(vm, key);
+                value = call(vm, to_json, value, &[key])?;
+            }
+        }
+        if let Some(replacer) = self.replacer {
+            let key = key_to_value(vm, key);
+            value = call(vm, replacer, JsValue::new(holder), &[key, value])?;
+        }
+        if value.is_object() {
+            match value.as_object().tag() {
+                ObjectTag::Number => value = JsValue::new(value.to_number(vm)?),
+                ObjectTag::String => value = JsValue::new(value.to_js_string(vm)?),
+                ObjectTag::Boolean => value = JsValue::new(value.as_object().as_boolean().value()),
+                _ => (),
+            }
+        }
+        if value.is_null() {
+            return Ok(Some("null".to_owned()));
+        }
+        if value.is_boolean() {
+            return Ok(Some(value.as_boolean().to_string()));
+        }
+        if value.is_string() {
+            return Ok(Some(quote(&value.as_string().to_utf16())));
+        }
+        if value.is_number() {
+            if value.number().is_finite() {
+                return Ok(Some(value.to_string(vm)?));
+            }
+            return Ok(Some("null".to_owned()));
+        }
+        if value.is_object() && !value.is_callable() {
+            let obj = value.as_object().root(vm.space());
+            if obj.tag() == ObjectTag::Array {
+                return self.serialize_array(vm, *obj).map(Some);
+            }
+            return self.serialize_object(vm, *obj).map(Some);
+        }
+        Ok(None)
+    }
+
+    fn enter(&mut self, vm: &mut VirtualMachine, value: Gc<JsObject>) -> Result<String, JsValue> {
+        if self.stack.iter().any(|obj| Gc::ptr_eq(*obj, value)) {
+            return Err(type_error(vm, "Converting circular structure to JSON"));
+        }
+        if self.stack.len() == MAX_JSON_DEPTH {
+            return Err(too_deep(vm));
+        }
+        self.stack.push(value);
+        let stepback = self.indent.clone();
+        self.indent.push_str(&self.gap);
+        Ok(stepback)
+    }
+
+    fn leave(&mut self, stepback: String) {
+        self.stack.pop();
+        self.indent = stepback;
+    }
+
+    /// Join the serialized members of an object or array between `open` and `close`.
+    fn join(&self, partial: &[String], open: char, close: char, stepback: &str) -> String {
+        if partial.is_empty() {
+            return format!("{}{}", open, close);
+        }
+        if self.gap.is_empty() {
+            return format!("{}{}{}", open, partial.join(","), close);
+        }
+        let separator = format!(",\n{}", self.indent);
+        format!(
+            "{}\n{}{}\n{}{}",
+            open,
+            self.indent,
+            partial.join(&separator),
+            stepback,
+            close
+        )
+    }
+
+    /// 25.5.2.4 SerializeJSONObject ( state, value )
+    fn serialize_object(
+        &mut self,
+        vm: &mut VirtualMachine,
+        value: Gc<JsObject>,
+    ) -> Result<String, JsValue> {
+        let stepback = self.enter(vm, value)?;
+        let keys = match self.property_list {
+            Some(ref list) => list.clone(),
+            None => json_keys(vm, value)?,
+        };
+        let mut partial = vec![];
+        for key in keys {
+            if let Some(str_p) = self.serialize_property(vm, key, value)? {
+                let separator = if self.gap.is_empty() { ":" } else { ": " };
+                let member = quote(&vm.key_string(key).to_utf16());
+                partial.push(format!("{}{}{}", member, separator, str_p));
+            }
+        }
+        let result = self.join(&partial, '{', '}', &stepback);
+        self.leave(stepback);
+        Ok(result)
+    }
+
+    /// 25.5.2.5 SerializeJSONArray ( state, value )
+    fn serialize_array(
+        &mut self,
+        vm: &mut VirtualMachine,
+        value: Gc<JsObject>,
+    ) -> Result<String, JsValue> {
+        let stepback = self.enter(vm, value)?;
+        let len = value.get(vm, Symbol::length())?.to_number(vm)? as u32;
+        let mut partial = vec![];
+        for index in 0..len {
+            let str_p = self.serialize_property(vm, Symbol::Indexed(index), value)?;
+            partial.push(str_p.unwrap_or_else(|| "null".to_owned()));
+        }
+        let result = self.join(&partial, '[', ']', &stepback);
+        self.leave(stepback);
+        Ok(result)
+    }
+}
+
+/// JSON.stringify ( value [ , replacer [ , space ] ] )
+pub fn json_stringify(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let value = args.at(0);
+    let replacer = args.at(1);
+    let mut space = args.at(2);
+    let mut stringifier = JsonStringifier {
+        replacer: None,
+        property_list: None,
+        gap: String::new(),
+        indent: String::new(),
+        stack: vec![],
+    };
+    if replacer.is_callable() {
+        stringifier.replacer = Some(replacer);
+    } else if replacer.is_array() {
+        let list = replacer.as_object().root(vm.space());
+        let len = list.get(vm, Symbol::length())?.to_number(vm)? as u32;
+        let mut keys = vec![];
+        for index in 0..len {
+            let v = list.get(vm, Symbol::Indexed(index))?;
+            let item = if v.is_string()
+                || v.is_number()
+                || (v.is_object()
+                    && matches!(v.as_object().tag(), ObjectTag::Number | ObjectTag::String))
+            {
+                v.to_string(vm)?
+            } else {
+                continue;
+            };
+            let key = vm.intern_or_known_symbol(&item);
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        stringifier.property_list = Some(keys);
+    }
+    if space.is_object() {
+        match space.as_object().tag() {
+            ObjectTag::Number => space = JsValue::new(space.to_number(vm)?),
+            ObjectTag::String => space = JsValue::new(space.to_js_string(vm)?),
+            _ => (),
+        }
+    }
+    if space.is_number() {
+        let n = space.number();
+        if n >= 1.0 {
+            stringifier.gap = " ".repeat(n.min(10.0) as usize);
+        }
+    } else if space.is_string() {
+        let space = space.as_string();
+        let units = space.to_utf16();
+        stringifier.gap = String::from_utf16_lossy(&units[..units.len().min(10)]);
+    }
+
+    let mut wrapper = JsObject::new_empty(vm).root(vm.space());
+    let name = vm.intern("");
+    wrapper.define_own_property(vm, name, &*DataDescriptor::new(value, W | C | E), false)?;
+    match stringifier.serialize_property(vm, name, *wrapper)? {
+        Some(s) => Ok(JsValue::new(JsString::new(vm, s))),
+        None => Ok(JsValue::undefined()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{eval, eval_ok};
+
+    const NESTED: &str = "
+        function nested(depth, open, close) {
+            var s = '';
+            for (var i = 0; i < depth; i = i + 1) {
+                s = open + s + close;
+            }
+            return s;
+        }
+    ";
+
+    #[test]
+    fn test_parse_too_deep_is_range_error() {
+        for (open, close) in [("[", "]"), ("{\\\"a\\\":", "}")].iter() {
+            let result = eval(&format!(
+                "{} JSON.parse(nested(513, '{}', '{}'));",
+                NESTED, open, close
+            ));
+            assert_eq!(
+                result,
+                Err("RangeError: Maximum call stack size exceeded".to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_and_stringify_nested_up_to_limit() {
+        let result = eval_ok(&format!(
+            "{} var s = nested(512, '[', ']'); result = JSON.stringify(JSON.parse(s)) === s;",
+            NESTED
+        ));
+        assert_eq!(result, "true");
+    }
+
+    #[test]
+    fn test_stringify_too_deep_is_range_error() {
+        let result = eval(
+            "
+            var o = [];
+            for (var i = 0; i < 512; i = i + 1) {
+                o = [o];
+            }
+            JSON.stringify(o);
+            ",
+        );
+        assert_eq!(
+            result,
+            Err("RangeError: Maximum call stack size exceeded".to_string())
+        );
+    }
+
+    #[test]
+    fn test_stringify_unwraps_primitive_wrappers() {
+        let result = eval_ok(
+            "
+            result = JSON.stringify([new Boolean(false), new Boolean(true), new Number(1),
+                new String('s')]) + ' ' + JSON.stringify(new Boolean(false));
+            ",
+        );
+        assert_eq!(result, "[false,true,1,\"s\"] false");
+    }
+
+    #[test]
+    fn test_parse_values() {
+        let source = r#"var v = JSON.parse(' {"a": [1, 2.5, -3e2, true, false, null], "b": {"c": "x\\ny\\u0041"}} ');
+            result = v.a.join('|') + v.b.c;"#;
+        assert_eq!(eval_ok(source), "1|2.5|-300|true|false|x\nyA");
+    }
+
+    #[test]
+    fn test_parse_reviver() {
+        let source = r#"var v = JSON.parse('[1, {"x": 2, "y": 3}]', function (k, v) {
+                if (k === 'y') return undefined;
+                if (typeof v === 'number') return v * 2;
+                return v;
+            });
+            result = v[0] + ' ' + v[1].x + ' ' + Object.keys(v[1]).join();"#;
+        assert_eq!(eval_ok(source), "2 4 x");
+    }
+
+    #[test]
+    fn test_parse_invalid_is_syntax_error() {
+        let source = r#"var bad = ['{a: 1}', '[1,]', '01', '"\\x"', '', '{"a" 1}', 'tru', '1 2', "'s'"];
+            result = '';
+            for (var s of bad) {
+                try { JSON.parse(s); result += 'ok;'; } catch (e) { result += e.name + ';'; }
+            }"#;
+        assert_eq!(eval_ok(source), "SyntaxError;".repeat(9));
+    }
+
+    #[test]
+    fn test_stringify_values() {
+        let source = r#"result = JSON.stringify({
+                a: [1, 'two', null, undefined, function () {}],
+                b: undefined,
+                d: 'q"\n\u0001'
+            }) + JSON.stringify({ toJSON: function () { return 'custom'; } }) +
+            JSON.stringify(NaN) + JSON.stringify(undefined) +
+            JSON.stringify({ [Symbol('k')]: 1, s: Symbol('v') });"#;
+        assert_eq!(
+            eval_ok(source),
+            r#"{"a":[1,"two",null,null,null],"d":"q\"\n\u0001"}"custom"nullundefined{}"#
+        );
+    }
+
+    #[test]
+    fn test_stringify_replacer_and_indent() {
+        let source = "result = JSON.stringify({ a: 1, b: 2, c: 3 }, ['a', 'c']) +
+            JSON.stringify({ a: 1, b: 'x' }, function (k, v) {
+                if (typeof v === 'number') return v + 1;
+                return v;
+            }) +
+            JSON.stringify({ a: 1, b: [1, 2] }, null, 2) + JSON.stringify({ a: 1 }, null, '--') +
+            JSON.stringify([], null, 2);";
+        assert_eq!(
+            eval_ok(source),
+            "{\"a\":1,\"c\":3}{\"a\":2,\"b\":\"x\"}{\n  \"a\": 1,\n  \"b\": [\n    1,\n    2\n  ]\n}{\n--\"a\": 1\n}[]"
+        );
+    }
+
+    #[test]
+    fn test_stringify_cycle_is_type_error() {
+        let source = "var o = {}; o.self = o;
+            try { JSON.stringify(o); } catch (e) { result = e.name; }";
+        assert_eq!(eval_ok(source), "TypeError");
+    }
+}
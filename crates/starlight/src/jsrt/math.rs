@@ -0,0 +1,322 @@
+use crate::{
+    runtime::{arguments::Arguments, method_table::*, object::JsObject, value::JsValue},
+    vm::VirtualMachine,
+};
+use wtf_rs::pure_nan::pure_nan;
+
+/// The `Math` namespace object. It only exists to give the object its class name.
+pub struct JsMath;
+
+impl JsMath {
+    crate::define_jsclass_with_symbol!(JsObject, Math, Math);
+}
+
+fn math_unary(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    op: fn(f64) -> f64,
+) -> Result<JsValue, JsValue> {
+    let x = args.at(0).to_number(vm)?;
+    Ok(JsValue::new(op(x)))
+}
+
+/// Math.abs ( x )
+pub fn math_abs(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::abs)
+}
+
+/// Math.acos ( x )
+pub fn math_acos(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::acos)
+}
+
+/// Math.acosh ( x )
+pub fn math_acosh(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::acosh)
+}
+
+/// Math.asin ( x )
+pub fn math_asin(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::asin)
+}
+
+/// Math.asinh ( x )
+pub fn math_asinh(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::asinh)
+}
+
+/// Math.atan ( x )
+pub fn math_atan(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::atan)
+}
+
+/// Math.atanh ( x )
+pub fn math_atanh(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::atanh)
+}
+
+/// Math.atan2 ( y, x )
+pub fn math_atan2(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let y = args.at(0).to_number(vm)?;
+    let x = args.at(1).to_number(vm)?;
+    Ok(JsValue::new(y.atan2(x)))
+}
+
+/// Math.cbrt ( x )
+pub fn math_cbrt(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::cbrt)
+}
+
+/// Math.ceil ( x )
+pub fn math_ceil(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::ceil)
+}
+
+/// Math.clz32 ( x )
+pub fn math_clz32(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let n = args.at(0).to_uint32(vm)?;
+    Ok(JsValue::new(n.leading_zeros() as i32))
+}
+
+/// Math.cos ( x )
+pub fn math_cos(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::cos)
+}
+
+/// Math.cosh ( x )
+pub fn math_cosh(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::cosh)
+}
+
+/// Math.exp ( x )
+pub fn math_exp(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::exp)
+}
+
+/// Math.expm1 ( x )
+pub fn math_expm1(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::exp_m1)
+}
+
+/// Math.floor ( x )
+pub fn math_floor(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::floor)
+}
+
+/// Math.fround ( x )
+pub fn math_fround(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, |x| x as f32 as f64)
+}
+
+/// Math.hypot ( ...args )
+pub fn math_hypot(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    // every argument is coerced before looking at any of them.
+    let mut numbers = Vec::with_capacity(args.size());
+    for ix in 0..args.size() {
+        numbers.push(args.at(ix).to_number(vm)?);
+    }
+    if numbers.iter().any(|n| n.is_infinite()) {
+        return Ok(JsValue::new(f64::INFINITY));
+    }
+    if numbers.iter().any(|n| n.is_nan()) {
+        return Ok(JsValue::new(pure_nan()));
+    }
+    Ok(JsValue::new(
+        numbers.iter().fold(0.0f64, |acc, n| acc.hypot(*n)),
+    ))
+}
+
+/// Math.imul ( x, y )
+pub fn math_imul(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let a = args.at(0).to_uint32(vm)?;
+    let b = args.at(1).to_uint32(vm)?;
+    Ok(JsValue::new((a as i32).wrapping_mul(b as i32)))
+}
+
+/// Math.log ( x )
+pub fn math_log(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::ln)
+}
+
+/// Math.log1p ( x )
+pub fn math_log1p(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::ln_1p)
+}
+
+/// Math.log10 ( x )
+pub fn math_log10(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::log10)
+}
+
+/// Math.log2 ( x )
+pub fn math_log2(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::log2)
+}
+
+/// Math.max ( ...args )
+pub fn math_max(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let mut result = f64::NEG_INFINITY;
+    for ix in 0..args.size() {
+        let n = args.at(ix).to_number(vm)?;
+        if n.is_nan() || result.is_nan() {
+            result = pure_nan();
+        } else if n > result || (n == 0.0 && result == 0.0 && result.is_sign_negative()) {
+            result = n;
+        }
+    }
+    Ok(JsValue::new(result))
+}
+
+/// Math.min ( ...args )
+pub fn math_min(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let mut result = f64::INFINITY;
+    for ix in 0..args.size() {
+        let n = args.at(ix).to_number(vm)?;
+        if n.is_nan() || result.is_nan() {
+            result = pure_nan();
+        } else if n < result || (n == 0.0 && result == 0.0 && n.is_sign_negative()) {
+            result = n;
+        }
+    }
+    Ok(JsValue::new(result))
+}
+
+/// Math.pow ( base, exponent )
+pub fn math_pow(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let base = args.at(0).to_number(vm)?;
+    let exponent = args.at(1).to_number(vm)?;
+    // Number::exponentiate differs from IEEE pow where the base is 1 or -1.
+    if exponent.is_nan() || (base.abs() == 1.0 && exponent.is_infinite()) {
+        return Ok(JsValue::new(pure_nan()));
+    }
+    Ok(JsValue::new(base.powf(exponent)))
+}
+
+/// Math.random ( )
+pub fn math_random(vm: &mut VirtualMachine, _args: &Arguments) -> Result<JsValue, JsValue> {
+    Ok(JsValue::new(vm.random()))
+}
+
+/// Math.round ( x )
+pub fn math_round(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    // halfway cases round towards +Infinity, unlike `f64::round`.
+    math_unary(vm, args, |x| {
+        let floor = x.floor();
+        if (-0.5..0.0).contains(&x) {
+            -0.0
+        } else if x - floor >= 0.5 {
+            floor + 1.0
+        } else {
+            floor
+        }
+    })
+}
+
+/// Math.sign ( x )
+pub fn math_sign(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, |x| {
+        if x == 0.0 || x.is_nan() {
+            x
+        } else {
+            x.signum()
+        }
+    })
+}
+
+/// Math.sin ( x )
+pub fn math_sin(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::sin)
+}
+
+/// Math.sinh ( x )
+pub fn math_sinh(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::sinh)
+}
+
+/// Math.sqrt ( x )
+pub fn math_sqrt(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::sqrt)
+}
+
+/// Math.tan ( x )
+pub fn math_tan(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::tan)
+}
+
+/// Math.tanh ( x )
+pub fn math_tanh(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::tanh)
+}
+
+/// Math.trunc ( x )
+pub fn math_trunc(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    math_unary(vm, args, f64::trunc)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        testing::{eval_ok, eval_with},
+        vm::Options,
+    };
+
+    #[test]
+    fn test_rounding() {
+        let source = "result = [Math.abs(-2), Math.floor(1.7), Math.ceil(1.2), Math.round(2.5),
+                Math.round(-2.5), Math.round(-0.6), Math.trunc(-1.7), Math.sign(-3)].join();";
+        assert_eq!(eval_ok(source), "2,1,2,3,-2,-1,-1,-1");
+    }
+
+    #[test]
+    fn test_negative_zero_results() {
+        let source = "result = [1 / Math.round(-0.4), 1 / Math.round(-0.5), 1 / Math.min(0, -0),
+                1 / Math.max(-0, 0), 1 / Math.sign(-0), 1 / Math.trunc(-0.5), 1 / Math.ceil(-0.5),
+                1 / Math.abs(-0)].join();";
+        assert_eq!(
+            eval_ok(source),
+            "-Infinity,-Infinity,-Infinity,Infinity,-Infinity,-Infinity,-Infinity,Infinity"
+        );
+    }
+
+    #[test]
+    fn test_min_max() {
+        let source = "var o = { valueOf: function () { return 9; } };
+            result = [Math.max(1, 3, 2), Math.min(), Math.max(), Math.max(1, NaN), Math.max(o, 1),
+                Math.max.length].join();";
+        assert_eq!(eval_ok(source), "3,Infinity,-Infinity,NaN,9,2");
+    }
+
+    #[test]
+    fn test_exponential_and_trigonometric() {
+        let source =
+            "result = [Math.sqrt(16), Math.pow(2, 10), Math.pow(1, Infinity), Math.cbrt(27),
+                Math.hypot(3, 4), Math.exp(0), Math.log(1), Math.log2(8), Math.log10(1000),
+                Math.sin(0), Math.cos(0), Math.atan2(1, 1) * 4 === Math.PI, Math.sinh(0),
+                Math.asin(2)].join();";
+        assert_eq!(eval_ok(source), "4,1024,NaN,3,5,1,0,3,3,0,1,true,0,NaN");
+    }
+
+    #[test]
+    fn test_integer_helpers_and_constants() {
+        let source = "result = [Math.fround(5.5), Math.clz32(1), Math.imul(3, 4),
+                Math.imul(0xffffffff, 5), Math.PI.toFixed(5), Math.E > 2.7, String(Math)].join();";
+        assert_eq!(eval_ok(source), "5.5,31,12,-5,3.14159,true,[object Math]");
+    }
+
+    #[test]
+    fn test_random_is_seeded() {
+        let source = "var ok = true;
+            for (var i = 0; i < 100; i = i + 1) {
+                var r = Math.random();
+                if (!(r >= 0 && r < 1)) ok = false;
+            }
+            result = ok + ' ' + Math.random() + ' ' + Math.random();";
+        let seeded = || Options {
+            random_seed: Some(42),
+            ..Default::default()
+        };
+        let first = eval_with(seeded(), source);
+        assert!(first.as_ref().is_ok_and(|r| r.starts_with("true ")));
+        assert_eq!(first, eval_with(seeded(), source));
+    }
+}
@@ -0,0 +1,466 @@
+use super::string::range_error;
+use crate::{
+    runtime::{
+        arguments::Arguments,
+        number::{decimal_digits, format_number, format_number_radix, JsNumber},
+        object::ObjectTag,
+        promise::type_error,
+        string::{is_js_whitespace, JsString},
+        value::JsValue,
+    },
+    vm::VirtualMachine,
+};
+use wtf_rs::pure_nan::pure_nan;
+
+/// 2^53 - 1, the largest integer n such that n and n + 1 are both exactly representable.
+const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+fn new_string(vm: &mut VirtualMachine, s: String) -> JsValue {
+    JsValue::new(JsString::new(vm, s))
+}
+
+/// Exponential notation of `digits * 10^(exponent - digits.len() + 1)`, as used by
+/// toExponential and toPrecision.
+fn exponential(digits: &[u8], exponent: i32) -> String {
+    let mut s = String::from_utf8_lossy(&digits[..1]).into_owned();
+    if digits.len() > 1 {
+        s.push('.');
+        s.push_str(&String::from_utf8_lossy(&digits[1..]));
+    }
+    let sign = if exponent < 0 { '-' } else { '+' };
+    format!("{}e{}{}", s, sign, exponent.abs())
+}
+
+/// 21.1.1.1 Number ( value )
+pub fn number_constructor(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let n = if args.size() == 0 {
+        0.0
+    } else {
+        args.at(0).to_number(vm)?
+    };
+    if !args.ctor_call {
+        return Ok(JsValue::new(n));
+    }
+    Ok(JsValue::new(JsNumber::new(vm, n)))
+}
+
+/// thisNumberValue ( value )
+fn this_number_value(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    method: &str,
+) -> Result<f64, JsValue> {
+    let this = args.this;
+    if this.is_number() {
+        return Ok(this.number());
+    }
+    if this.is_object() && this.as_object().tag() == ObjectTag::Number {
+        return Ok(this.as_object().as_number().value());
+    }
+    Err(type_error(
+        vm,
+        &format!(
+            "Number.prototype.{} requires that 'this' be a Number",
+            method
+        ),
+    ))
+}
+
+/// Number.prototype.toExponential ( fractionDigits )
+pub fn number_to_exponential(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let x = this_number_value(vm, args, "toExponential")?;
+    let f = args.at(0).to_integer_or_infinity(vm)?;
+    if !x.is_finite() {
+        return Ok(new_string(vm, format_number(x)));
+    }
+    if !(0.0..=100.0).contains(&f) {
+        return Err(range_error(
+            vm,
+            "toExponential() argument must be between 0 and 100".to_owned(),
+        ));
+    }
+    let sign = if x < 0.0 { "-" } else { "" };
+    let x = x.abs();
+    let (digits, n) = if x == 0.0 {
+        (vec![b'0'; f as usize + 1], 1)
+    } else if args.at(0).is_undefined() {
+        decimal_digits(x, None)
+    } else {
+        decimal_digits(x, Some(f as usize + 1))
+    };
+    Ok(new_string(
+        vm,
+        format!("{}{}", sign, exponential(&digits, n - 1)),
+    ))
+}
+
+/// Number.prototype.toFixed ( fractionDigits )
+pub fn number_to_fixed(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let x = this_number_value(vm, args, "toFixed")?;
+    let f = args.at(0).to_integer_or_infinity(vm)?;
+    if !(0.0..=100.0).contains(&f) {
+        return Err(range_error(
+            vm,
+            "toFixed() digits argument must be between 0 and 100".to_owned(),
+        ));
+    }
+    if !x.is_finite() || x.abs() >= 1e21 {
+        return Ok(new_string(vm, format_number(x)));
+    }
+    let f = f as usize;
+    let sign = if x < 0.0 { "-" } else { "" };
+    // the exact decimal expansion, rounded half up by hand since `{:.N}` rounds half to even.
+    let exact = format!("{:.1100}", x.abs());
+    let point = exact.find('.').unwrap();
+    let mut digits = exact[..point]
+        .bytes()
+        .chain(exact[point + 1..].bytes().take(f))
+        .collect::<Vec<u8>>();
+    if exact.as_bytes()[point + 1 + f] >= b'5' {
+        let mut ix = digits.len();
+        loop {
+            if ix == 0 {
+                digits.insert(0, b'1');
+                break;
+            }
+            ix -= 1;
+            if digits[ix] == b'9' {
+                digits[ix] = b'0';
+            } else {
+                digits[ix] += 1;
+                break;
+            }
+        }
+    }
+    let digits = String::from_utf8(digits).unwrap();
+    let (integer, fraction) = digits.split_at(digits.len() - f);
+    let s = if f == 0 {
+        format!("{}{}", sign, integer)
+    } else {
+        format!("{}{}.{}", sign, integer, fraction)
+    };
+    Ok(new_string(vm, s))
+}
+
+/// Number.prototype.toPrecision ( precision )
+pub fn number_to_precision(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let x = this_number_value(vm, args, "toPrecision")?;
+    if args.at(0).is_undefined() {
+        return Ok(new_string(vm, format_number(x)));
+    }
+    let p = args.at(0).to_integer_or_infinity(vm)?;
+    if !x.is_finite() {
+        return Ok(new_string(vm, format_number(x)));
+    }
+    if !(1.0..=100.0).contains(&p) {
+        return Err(range_error(
+            vm,
+            "toPrecision() argument must be between 1 and 100".to_owned(),
+        ));
+    }
+    let p = p as i32;
+    let sign = if x < 0.0 { "-" } else { "" };
+    let x = x.abs();
+    let (digits, n) = if x == 0.0 {
+        (vec![b'0'; p as usize], 1)
+    } else {
+        decimal_digits(x, Some(p as usize))
+    };
+    let e = n - 1;
+    let s = if e < -6 || e >= p {
+        exponential(&digits, e)
+    } else {
+        let digits = String::from_utf8(digits).unwrap();
+        if e == p - 1 {
+            digits
+        } else if e >= 0 {
+            format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+        } else {
+            format!("0.{}{}", "0".repeat(-n as usize), digits)
+        }
+    };
+    Ok(new_string(vm, format!("{}{}", sign, s)))
+}
+
+/// Number.prototype.toString ( [ radix ] )
+pub fn number_to_string(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let x = this_number_value(vm, args, "toString")?;
+    let radix = if args.at(0).is_undefined() {
+        10.0
+    } else {
+        args.at(0).to_integer_or_infinity(vm)?
+    };
+    if !(2.0..=36.0).contains(&radix) {
+        return Err(range_error(
+            vm,
+            "toString() radix must be between 2 and 36".to_owned(),
+        ));
+    }
+    let s = if radix == 10.0 {
+        format_number(x)
+    } else {
+        format_number_radix(x, radix as u32)
+    };
+    Ok(new_string(vm, s))
+}
+
+/// Number.prototype.toLocaleString ( [ reserved1 [ , reserved2 ] ] )
+pub fn number_to_locale_string(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let x = this_number_value(vm, args, "toLocaleString")?;
+    Ok(new_string(vm, format_number(x)))
+}
+
+/// Number.prototype.valueOf ( )
+pub fn number_value_of(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let x = this_number_value(vm, args, "valueOf")?;
+    Ok(JsValue::new(x))
+}
+
+/// Number.isFinite ( number )
+pub fn number_is_finite(_vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let n = args.at(0);
+    Ok(JsValue::new(n.is_number() && n.number().is_finite()))
+}
+
+/// Number.isInteger ( number )
+pub fn number_is_integer(_vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let n = args.at(0);
+    Ok(JsValue::new(
+        n.is_number() && n.number().is_finite() && n.number().trunc() == n.number(),
+    ))
+}
+
+/// Number.isNaN ( number )
+pub fn number_is_nan(_vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let n = args.at(0);
+    Ok(JsValue::new(n.is_number() && n.number().is_nan()))
+}
+
+/// Number.isSafeInteger ( number )
+pub fn number_is_safe_integer(
+    _vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let n = args.at(0);
+    Ok(JsValue::new(
+        n.is_number()
+            && n.number().is_finite()
+            && n.number().trunc() == n.number()
+            && n.number().abs() <= MAX_SAFE_INTEGER,
+    ))
+}
+
+/// isFinite ( number )
+pub fn is_finite(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let n = args.at(0).to_number(vm)?;
+    Ok(JsValue::new(n.is_finite()))
+}
+
+/// isNaN ( number )
+pub fn is_nan(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let n = args.at(0).to_number(vm)?;
+    Ok(JsValue::new(n.is_nan()))
+}
+
+fn trim_start(units: &[u16]) -> &[u16] {
+    let start = units
+        .iter()
+        .position(|unit| !is_js_whitespace(*unit))
+        .unwrap_or(units.len());
+    &units[start..]
+}
+
+/// parseFloat ( string )
+pub fn parse_float(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let input = args.at(0).to_js_string(vm)?.to_utf16().into_owned();
+    let input = trim_start(&input);
+    // only ASCII can be part of a StrDecimalLiteral, anything else ends the literal anyway.
+    let s = input
+        .iter()
+        .take_while(|unit| **unit < 0x80)
+        .map(|unit| *unit as u8 as char)
+        .collect::<String>();
+    let bytes = s.as_bytes();
+    let is_digit = |ix: usize| ix < bytes.len() && bytes[ix].is_ascii_digit();
+
+    let mut ix = 0;
+    if ix < bytes.len() && (bytes[ix] == b'+' || bytes[ix] == b'-') {
+        ix += 1;
+    }
+    if s[ix..].starts_with("Infinity") {
+        let n = if bytes[0] == b'-' {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        };
+        return Ok(JsValue::new(n));
+    }
+    let mut has_digits = false;
+    while is_digit(ix) {
+        ix += 1;
+        has_digits = true;
+    }
+    if ix < bytes.len() && bytes[ix] == b'.' {
+        ix += 1;
+        while is_digit(ix) {
+            ix += 1;
+            has_digits = true;
+        }
+    }
+    if !has_digits {
+        return Ok(JsValue::new(pure_nan()));
+    }
+    let mut end = ix;
+    if ix < bytes.len() && (bytes[ix] == b'e' || bytes[ix] == b'E') {
+        ix += 1;
+        if ix < bytes.len() && (bytes[ix] == b'+' || bytes[ix] == b'-') {
+            ix += 1;
+        }
+        while is_digit(ix) {
+            ix += 1;
+            end = ix;
+        }
+    }
+    let n = s[..end].parse::<f64>().unwrap_or_else(|_| pure_nan());
+    Ok(JsValue::new(n))
+}
+
+/// parseInt ( string, radix )
+pub fn parse_int(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let input = args.at(0).to_js_string(vm)?.to_utf16().into_owned();
+    let mut radix = args.at(1).to_uint32(vm)? as i32;
+    let mut s = trim_start(&input);
+    let mut sign = 1.0;
+    if !s.is_empty() && (s[0] == b'-' as u16 || s[0] == b'+' as u16) {
+        if s[0] == b'-' as u16 {
+            sign = -1.0;
+        }
+        s = &s[1..];
+    }
+    let mut strip_prefix = true;
+    if radix != 0 {
+        if !(2..=36).contains(&radix) {
+            return Ok(JsValue::new(pure_nan()));
+        }
+        if radix != 16 {
+            strip_prefix = false;
+        }
+    } else {
+        radix = 10;
+    }
+    if strip_prefix
+        && s.len() >= 2
+        && s[0] == b'0' as u16
+        && (s[1] == b'x' as u16 || s[1] == b'X' as u16)
+    {
+        s = &s[2..];
+        radix = 16;
+    }
+    let digit = |unit: u16| std::char::from_u32(unit as u32).and_then(|c| c.to_digit(radix as u32));
+    let end = s
+        .iter()
+        .position(|unit| digit(*unit).is_none())
+        .unwrap_or(s.len());
+    if end == 0 {
+        return Ok(JsValue::new(pure_nan()));
+    }
+    let digits = &s[..end];
+    let n = if radix == 10 {
+        // correctly rounded, however many digits there are.
+        String::from_utf16_lossy(digits).parse::<f64>().unwrap()
+    } else {
+        digits.iter().fold(0.0, |acc, unit| {
+            acc * radix as f64 + digit(*unit).unwrap() as f64
+        })
+    };
+    Ok(JsValue::new(sign * n))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::eval_ok;
+
+    #[test]
+    fn test_number_to_string() {
+        let source = "result = [1e21, 1e-7, 0.000001, 123.456, -0, 0.1 + 0.2, 5e-324,
+                1.7976931348623157e308, Math.pow(2, 53), -1.5e-10, 1 / 3, -1e21, 123e-20].join('|');";
+        assert_eq!(
+            eval_ok(source),
+            "1e+21|1e-7|0.000001|123.456|0|0.30000000000000004|5e-324|1.7976931348623157e+308|\
+             9007199254740992|-1.5e-10|0.3333333333333333|-1e+21|1.23e-18"
+        );
+    }
+
+    #[test]
+    fn test_number_prototype_formatting() {
+        let source = "result = [(255).toString(16), (255).toString(2), (-7.5).toString(2),
+                (0.5).toString(3).slice(0, 6), (1.005).toFixed(2), (123.456).toFixed(1),
+                (0).toFixed(2), (1e21).toFixed(2), (123.456).toPrecision(4),
+                (0.00001234).toPrecision(2), (123456).toPrecision(2), (123.456).toExponential(2),
+                (0).toExponential(), (1.5).toExponential()].join('|');";
+        assert_eq!(
+            eval_ok(source),
+            "ff|11111111|-111.1|0.1111|1.00|123.5|0.00|1e+21|123.5|0.000012|1.2e+5|1.23e+2|0e+0|\
+             1.5e+0"
+        );
+    }
+
+    #[test]
+    fn test_string_to_number() {
+        let source = "result = [Number('  12  '), Number(''), Number(' \\n '), Number('0x10'),
+                Number('0b101'), Number('0o17'), Number('-0x10'), Number('1e3'), Number('abc'),
+                Number('inf'), Number('-Infinity'), Number('+.5'), Number('5.'), Number('1e'),
+                Number('.'), Number('12px'), +'  7 ', '3' * '4', 1 / Number('-0')].join('|');";
+        assert_eq!(
+            eval_ok(source),
+            "12|0|0|16|5|15|NaN|1000|NaN|NaN|-Infinity|0.5|5|NaN|NaN|NaN|7|12|-Infinity"
+        );
+    }
+
+    #[test]
+    fn test_number_constructor() {
+        let source = "result = [Number(null), Number(undefined), Number(true), Number([5]),
+                new Number(3) + 1, typeof new Number(3), Number.MAX_SAFE_INTEGER,
+                Number.EPSILON > 0, Number.MIN_VALUE].join('|');";
+        assert_eq!(
+            eval_ok(source),
+            "0|NaN|1|5|4|object|9007199254740991|true|5e-324"
+        );
+    }
+
+    #[test]
+    fn test_number_statics_and_globals() {
+        let source = "result = [Number.isInteger(5), Number.isInteger(5.5),
+                Number.isSafeInteger(Math.pow(2, 53)), Number.isSafeInteger(Math.pow(2, 53) - 1),
+                Number.parseFloat('3.14abc'), Number.parseInt('0x1f'), parseInt('  42px'),
+                parseInt('z', 36), parseInt('08'), parseInt(''), parseInt('-0x10'),
+                parseInt('11', 2), parseInt('1', 37), parseFloat('.5e1'), parseFloat('-Infinityx'),
+                isNaN('abc'), isFinite(' 12 '), Number.isNaN('abc'), Number.isFinite('12')].join('|');";
+        assert_eq!(
+            eval_ok(source),
+            "true|false|false|true|3.14|31|42|35|8|NaN|-16|3|NaN|5|-Infinity|true|true|false|false"
+        );
+    }
+
+    #[test]
+    fn test_number_errors() {
+        let source = "result = '';
+            var t = Number.prototype.toString;
+            var o = { t: t };
+            var cases = [
+                function () { (1).toString(1); },
+                function () { (1).toFixed(101); },
+                function () { o.t(); }
+            ];
+            for (var f of cases) {
+                try { f(); } catch (e) { result += e.name + ' '; }
+            }";
+        assert_eq!(eval_ok(source), "RangeError RangeError TypeError ");
+    }
+}
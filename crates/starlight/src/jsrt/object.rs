@@ -1,11 +1,18 @@
 use crate::{
-    gc::handle::Handle,
+    gc::{cell::Gc, handle::Handle},
     runtime::{
         arguments::Arguments,
+        array::JsArray,
+        attributes::*,
         error::JsTypeError,
-        object::{JsObject, ObjectTag},
+        iterator::{get_iterator, iterator_close, iterator_step},
+        object::{EnumerationMode, IntegrityLevel, JsObject, ObjectTag},
+        promise::{invoke, type_error},
+        property_descriptor::{DataDescriptor, PropertyDescriptor},
+        slot::Slot,
         string::JsString,
         structure::Structure,
+        symbol::{JsSymbol, Symbol},
         value::JsValue,
     },
     vm::VirtualMachine,
@@ -17,11 +24,15 @@ pub fn object_to_string(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsV
     if this_binding.is_undefined() {
         return Ok(JsValue::new(JsString::new(vm, "[object Undefined]")));
     } else if this_binding.is_null() {
-        return Ok(JsValue::new(JsString::new(vm, "[object Undefined]")));
+        return Ok(JsValue::new(JsString::new(vm, "[object Null]")));
     }
     let obj = this_binding.to_object(vm)?.root(vm.space());
-
-    let s = format!("[object {}]", obj.class().name);
+    let tag = obj.get(vm, Symbol::toStringTag())?;
+    let s = if tag.is_string() {
+        format!("[object {}]", tag.as_string().to_string_lossy())
+    } else {
+        format!("[object {}]", obj.class().name)
+    };
     Ok(JsValue::new(JsString::new(vm, s)))
 }
 
@@ -39,7 +50,7 @@ pub fn object_create(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValu
             let res = JsObject::new(vm, *structure, JsObject::get_class(), ObjectTag::Ordinary)
                 .root(vm.space());
             if !args.at(1).is_undefined() {
-                todo!("define properties");
+                define_properties(vm, *res, args.at(1))?;
             }
 
             return Ok(JsValue::new(*res));
@@ -54,7 +65,7 @@ pub fn object_constructor(vm: &mut VirtualMachine, args: &Arguments) -> Result<J
     if args.ctor_call {
         let val = Handle::new(vm.space(), args.at(0));
         if val.is_string() || val.is_number() || val.is_boolean() {
-            return val.to_object(vm).map(|x| JsValue::new(x));
+            return val.to_object(vm).map(JsValue::new);
         }
         return Ok(JsValue::new(JsObject::new_empty(vm)));
     } else {
@@ -62,9 +73,805 @@ pub fn object_constructor(vm: &mut VirtualMachine, args: &Arguments) -> Result<J
         if val.is_undefined_or_null() {
             return Ok(JsValue::new(JsObject::new_empty(vm)));
         } else {
-            return Handle::new(vm.space(), val)
-                .to_object(vm)
-                .map(|x| JsValue::new(x));
+            return Handle::new(vm.space(), val).to_object(vm).map(JsValue::new);
+        }
+    }
+}
+
+/// Property key `key` as a JS string value.
+pub(crate) fn key_to_value(vm: &mut VirtualMachine, key: Symbol) -> JsValue {
+    if key.is_unique() {
+        return JsValue::new(JsSymbol::new(vm, key));
+    }
+    JsValue::new(vm.key_string(key))
+}
+
+/// 6.2.5.5 ToPropertyDescriptor ( Obj )
+fn to_property_descriptor(
+    vm: &mut VirtualMachine,
+    value: JsValue,
+) -> Result<PropertyDescriptor, JsValue> {
+    if !value.is_object() {
+        return Err(type_error(vm, "Property description must be an object"));
+    }
+    let obj = value.as_object();
+    let field = |vm: &mut VirtualMachine, name: Symbol| -> Result<Option<JsValue>, JsValue> {
+        if !obj.has_property(vm, name) {
+            return Ok(None);
+        }
+        obj.get(vm, name).map(Some)
+    };
+
+    let mut attrs = UNDEF_ENUMERABLE | UNDEF_CONFIGURABLE;
+    if let Some(enumerable) = field(vm, Symbol::enumerable())? {
+        attrs &= !UNDEF_ENUMERABLE;
+        if enumerable.to_boolean() {
+            attrs |= E;
+        }
+    }
+    if let Some(configurable) = field(vm, Symbol::configurable())? {
+        attrs &= !UNDEF_CONFIGURABLE;
+        if configurable.to_boolean() {
+            attrs |= C;
+        }
+    }
+    let value = field(vm, Symbol::value())?;
+    let writable = field(vm, Symbol::writable())?;
+    let getter = field(vm, Symbol::get())?;
+    let setter = field(vm, Symbol::set())?;
+    for accessor in [getter, setter].iter().flatten() {
+        if !accessor.is_undefined() && !accessor.is_callable() {
+            return Err(type_error(vm, "Getter and setter must be functions"));
+        }
+    }
+
+    if getter.is_some() || setter.is_some() {
+        if value.is_some() || writable.is_some() {
+            return Err(type_error(
+                vm,
+                "Invalid property descriptor. Cannot both specify accessors and a value or writable attribute",
+            ));
+        }
+        if getter.is_none() {
+            attrs |= UNDEF_GETTER;
+        }
+        if setter.is_none() {
+            attrs |= UNDEF_SETTER;
+        }
+        return Ok(PropertyDescriptor::accessor_descriptor(
+            getter.unwrap_or_else(JsValue::undefined),
+            setter.unwrap_or_else(JsValue::undefined),
+            attrs,
+        ));
+    }
+    if value.is_none() && writable.is_none() {
+        return Ok(PropertyDescriptor::generic(attrs));
+    }
+    match writable {
+        Some(writable) if writable.to_boolean() => attrs |= W,
+        Some(_) => (),
+        None => attrs |= UNDEF_WRITABLE,
+    }
+    if value.is_none() {
+        attrs |= UNDEF_VALUE;
+    }
+    Ok(PropertyDescriptor::data_descriptor(
+        value.unwrap_or_else(JsValue::undefined),
+        attrs,
+    ))
+}
+
+/// 6.2.5.4 FromPropertyDescriptor ( Desc ) of the own property `key` of `obj`, undefined when
+/// there is no such property.
+fn own_property_descriptor(
+    vm: &mut VirtualMachine,
+    obj: Gc<JsObject>,
+    key: Symbol,
+) -> Result<JsValue, JsValue> {
+    let mut slot = Slot::new();
+    if !obj.get_own_property_slot(vm, key, &mut slot) {
+        return Ok(JsValue::undefined());
+    }
+    let desc = slot.to_descriptor();
+    let mut result = JsObject::new_empty(vm).root(vm.space());
+    if desc.is_data() {
+        result.put(vm, Symbol::value(), desc.value(), true)?;
+        result.put(
+            vm,
+            Symbol::writable(),
+            JsValue::new(desc.is_writable()),
+            true,
+        )?;
+    } else {
+        result.put(vm, Symbol::get(), desc.getter(), true)?;
+        result.put(vm, Symbol::set(), desc.setter(), true)?;
+    }
+    result.put(
+        vm,
+        Symbol::enumerable(),
+        JsValue::new(desc.is_enumerable()),
+        true,
+    )?;
+    result.put(
+        vm,
+        Symbol::configurable(),
+        JsValue::new(desc.is_configurable()),
+        true,
+    )?;
+    Ok(JsValue::new(*result))
+}
+
+/// Own enumerable property of `obj` named `key`, it may have been removed or made
+/// non-enumerable by a getter run since the keys were collected.
+fn is_own_enumerable(vm: &mut VirtualMachine, obj: Gc<JsObject>, key: Symbol) -> bool {
+    let mut slot = Slot::new();
+    obj.get_own_property_slot(vm, key, &mut slot) && slot.attributes().is_enumerable()
+}
+
+/// 20.1.2.3.1 ObjectDefineProperties ( O, Properties )
+fn define_properties(
+    vm: &mut VirtualMachine,
+    mut obj: Gc<JsObject>,
+    properties: JsValue,
+) -> Result<(), JsValue> {
+    let props = properties.to_object(vm)?.root(vm.space());
+    // every descriptor is read before any of them is applied, the values they hold are kept
+    // in `roots` meanwhile.
+    let mut descriptors = vec![];
+    let mut roots = Handle::new(vm.space(), Vec::<JsValue>::new());
+    for key in props.get_own_property_keys(vm, EnumerationMode::IncludeNotEnumerable) {
+        if !is_own_enumerable(vm, *props, key) {
+            continue;
+        }
+        let desc = props.get(vm, key)?;
+        let desc = to_property_descriptor(vm, desc)?;
+        if desc.is_data() {
+            roots.push(desc.value());
+        } else if desc.is_accessor() {
+            roots.push(desc.getter());
+            roots.push(desc.setter());
         }
+        descriptors.push((key, desc));
+    }
+    for (key, desc) in descriptors.iter() {
+        obj.define_own_property(vm, *key, desc, true)?;
+    }
+    Ok(())
+}
+
+fn object_argument(
+    vm: &mut VirtualMachine,
+    value: JsValue,
+    method: &str,
+) -> Result<Gc<JsObject>, JsValue> {
+    if !value.is_object() {
+        return Err(type_error(
+            vm,
+            &format!("Object.{} called on non-object", method),
+        ));
+    }
+    Ok(value.as_object())
+}
+
+/// Object.defineProperty ( O, P, Attributes )
+pub fn object_define_property(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let mut obj = object_argument(vm, args.at(0), "defineProperty")?;
+    let key = args.at(1).to_symbol(vm)?;
+    let desc = to_property_descriptor(vm, args.at(2))?;
+    obj.define_own_property(vm, key, &desc, true)?;
+    Ok(JsValue::new(obj))
+}
+
+/// Object.defineProperties ( O, Properties )
+pub fn object_define_properties(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let obj = object_argument(vm, args.at(0), "defineProperties")?;
+    define_properties(vm, obj, args.at(1))?;
+    Ok(JsValue::new(obj))
+}
+
+/// Object.getOwnPropertyDescriptor ( O, P )
+pub fn object_get_own_property_descriptor(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let obj = args.at(0).to_object(vm)?;
+    let key = args.at(1).to_symbol(vm)?;
+    own_property_descriptor(vm, obj, key)
+}
+
+/// Object.getOwnPropertyDescriptors ( O )
+pub fn object_get_own_property_descriptors(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let obj = args.at(0).to_object(vm)?;
+    let mut result = JsObject::new_empty(vm).root(vm.space());
+    for key in obj.get_own_property_keys(vm, EnumerationMode::IncludeNotEnumerable) {
+        let desc = own_property_descriptor(vm, obj, key)?;
+        if !desc.is_undefined() {
+            result.define_own_property(vm, key, &*DataDescriptor::new(desc, W | C | E), true)?;
+        }
+    }
+    Ok(JsValue::new(*result))
+}
+
+/// 20.1.2.11.1 GetOwnPropertyKeys ( O, type )
+fn get_own_property_keys(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    symbols: bool,
+) -> Result<JsValue, JsValue> {
+    let obj = args.at(0).to_object(vm)?;
+    let mut names = Handle::new(vm.space(), Vec::<JsValue>::new());
+    for key in obj.get_own_property_keys(vm, EnumerationMode::IncludeNotEnumerable) {
+        if key.is_unique() != symbols {
+            continue;
+        }
+        let name = key_to_value(vm, key);
+        names.push(name);
+    }
+    Ok(JsValue::new(JsArray::from_slice(vm, &names)))
+}
+
+/// Object.getOwnPropertyNames ( O )
+pub fn object_get_own_property_names(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    get_own_property_keys(vm, args, false)
+}
+
+/// Object.getOwnPropertySymbols ( O )
+pub fn object_get_own_property_symbols(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    get_own_property_keys(vm, args, true)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum PropertyKind {
+    Key,
+    Value,
+    KeyValue,
+}
+
+/// 7.3.23 EnumerableOwnPropertyNames ( O, kind )
+fn enumerable_own_properties(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    kind: PropertyKind,
+) -> Result<JsValue, JsValue> {
+    let obj = args.at(0).to_object(vm)?;
+    let mut properties = Handle::new(vm.space(), Vec::<JsValue>::new());
+    for key in obj.get_own_property_keys(vm, EnumerationMode::Default) {
+        if key.is_unique() || !is_own_enumerable(vm, obj, key) {
+            continue;
+        }
+        let name = key_to_value(vm, key);
+        let property = match kind {
+            PropertyKind::Key => name,
+            PropertyKind::Value => obj.get(vm, key)?,
+            PropertyKind::KeyValue => {
+                let value = obj.get(vm, key)?;
+                JsValue::new(JsArray::from_slice(vm, &[name, value]))
+            }
+        };
+        properties.push(property);
+    }
+    Ok(JsValue::new(JsArray::from_slice(vm, &properties)))
+}
+
+/// Object.keys ( O )
+pub fn object_keys(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    enumerable_own_properties(vm, args, PropertyKind::Key)
+}
+
+/// Object.values ( O )
+pub fn object_values(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    enumerable_own_properties(vm, args, PropertyKind::Value)
+}
+
+/// Object.entries ( O )
+pub fn object_entries(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    enumerable_own_properties(vm, args, PropertyKind::KeyValue)
+}
+
+/// Object.fromEntries ( iterable )
+pub fn object_from_entries(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let iterable = args.at(0);
+    if iterable.is_undefined_or_null() {
+        return Err(type_error(
+            vm,
+            "Object.fromEntries requires an iterable argument",
+        ));
+    }
+    let mut result = JsObject::new_empty(vm).root(vm.space());
+    let (iterator, next) = get_iterator(vm, iterable)?;
+    while let Some(entry) = iterator_step(vm, iterator, next)? {
+        let added = add_entry(vm, *result, entry);
+        if let Err(error) = added {
+            let _ = iterator_close(vm, iterator);
+            return Err(error);
+        }
+    }
+    Ok(JsValue::new(*result))
+}
+
+fn add_entry(
+    vm: &mut VirtualMachine,
+    mut target: Gc<JsObject>,
+    entry: JsValue,
+) -> Result<(), JsValue> {
+    if !entry.is_object() {
+        return Err(type_error(vm, "Iterator value is not an entry object"));
+    }
+    let entry = entry.as_object();
+    let key = entry.get(vm, Symbol::Indexed(0))?;
+    let value = entry.get(vm, Symbol::Indexed(1))?;
+    let key = key.to_symbol(vm)?;
+    target.define_own_property(vm, key, &*DataDescriptor::new(value, W | C | E), true)?;
+    Ok(())
+}
+
+/// Object.assign ( target, ...sources )
+pub fn object_assign(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let mut target = args.at(0).to_object(vm)?;
+    for ix in 1..args.size() {
+        let source = args.at(ix);
+        if source.is_undefined_or_null() {
+            continue;
+        }
+        let source = source.to_object(vm)?;
+        for key in source.get_own_property_keys(vm, EnumerationMode::IncludeNotEnumerable) {
+            if !is_own_enumerable(vm, source, key) {
+                continue;
+            }
+            let value = source.get(vm, key)?;
+            target.put(vm, key, value, true)?;
+        }
+    }
+    Ok(JsValue::new(target))
+}
+
+/// Object.getPrototypeOf ( O )
+pub fn object_get_prototype_of(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let obj = args.at(0).to_object(vm)?;
+    Ok(match obj.prototype() {
+        Some(proto) => JsValue::new(proto),
+        None => JsValue::null(),
+    })
+}
+
+/// Object.setPrototypeOf ( O, proto )
+pub fn object_set_prototype_of(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let value = args.at(0);
+    if value.is_undefined_or_null() {
+        return Err(type_error(
+            vm,
+            "Object.setPrototypeOf called on null or undefined",
+        ));
+    }
+    let proto = args.at(1);
+    let proto = if proto.is_object() {
+        Some(proto.as_object())
+    } else if proto.is_null() {
+        None
+    } else {
+        return Err(type_error(
+            vm,
+            "Object prototype may only be an Object or null",
+        ));
+    };
+    if !value.is_object() {
+        return Ok(value);
+    }
+    let mut obj = value.as_object();
+    if !obj.set_prototype(vm, proto) {
+        return Err(type_error(vm, "Object.setPrototypeOf failed"));
+    }
+    Ok(value)
+}
+
+/// Object.freeze ( O )
+pub fn object_freeze(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let value = args.at(0);
+    if value.is_object() {
+        value
+            .as_object()
+            .set_integrity_level(vm, IntegrityLevel::Frozen)?;
+    }
+    Ok(value)
+}
+
+/// Object.isFrozen ( O )
+pub fn object_is_frozen(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let value = args.at(0);
+    Ok(JsValue::new(
+        !value.is_object()
+            || value
+                .as_object()
+                .test_integrity_level(vm, IntegrityLevel::Frozen),
+    ))
+}
+
+/// Object.seal ( O )
+pub fn object_seal(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let value = args.at(0);
+    if value.is_object() {
+        value
+            .as_object()
+            .set_integrity_level(vm, IntegrityLevel::Sealed)?;
+    }
+    Ok(value)
+}
+
+/// Object.isSealed ( O )
+pub fn object_is_sealed(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let value = args.at(0);
+    Ok(JsValue::new(
+        !value.is_object()
+            || value
+                .as_object()
+                .test_integrity_level(vm, IntegrityLevel::Sealed),
+    ))
+}
+
+/// Object.preventExtensions ( O )
+pub fn object_prevent_extensions(
+    _vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let value = args.at(0);
+    if value.is_object() {
+        value.as_object().prevent_extensions();
+    }
+    Ok(value)
+}
+
+/// Object.isExtensible ( O )
+pub fn object_is_extensible(
+    _vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let value = args.at(0);
+    Ok(JsValue::new(
+        value.is_object() && value.as_object().is_extensible(),
+    ))
+}
+
+/// Object.is ( value1, value2 )
+pub fn object_is(_vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    Ok(JsValue::new(JsValue::same_value(args.at(0), args.at(1))))
+}
+
+/// Object.prototype.hasOwnProperty ( V )
+pub fn object_has_own_property(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let key = args.at(0).to_symbol(vm)?;
+    let obj = args.this.to_object(vm)?;
+    Ok(JsValue::new(obj.has_own_property(vm, key)))
+}
+
+/// Object.prototype.isPrototypeOf ( V )
+pub fn object_is_prototype_of(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let value = args.at(0);
+    if !value.is_object() {
+        return Ok(JsValue::new(false));
+    }
+    let obj = args.this.to_object(vm)?;
+    let mut current = value.as_object().prototype();
+    while let Some(proto) = current {
+        if Gc::ptr_eq(proto, obj) {
+            return Ok(JsValue::new(true));
+        }
+        current = proto.prototype();
+    }
+    Ok(JsValue::new(false))
+}
+
+/// Object.prototype.propertyIsEnumerable ( V )
+pub fn object_property_is_enumerable(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let key = args.at(0).to_symbol(vm)?;
+    let obj = args.this.to_object(vm)?;
+    Ok(JsValue::new(is_own_enumerable(vm, obj, key)))
+}
+
+/// Object.prototype.toLocaleString ( [ reserved1 [ , reserved2 ] ] )
+pub fn object_to_locale_string(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    invoke(vm, args.this, Symbol::toString(), &[])
+}
+
+/// Object.prototype.valueOf ( )
+pub fn object_value_of(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    Ok(JsValue::new(args.this.to_object(vm)?))
+}
+
+/// get Object.prototype.__proto__
+pub fn object_proto_getter(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    Ok(match obj.prototype() {
+        Some(proto) => JsValue::new(proto),
+        None => JsValue::null(),
+    })
+}
+
+/// set Object.prototype.__proto__
+pub fn object_proto_setter(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    if args.this.is_undefined_or_null() {
+        return Err(type_error(
+            vm,
+            "Object.prototype.__proto__ called on null or undefined",
+        ));
+    }
+    let proto = args.at(0);
+    if !args.this.is_object() || !(proto.is_object() || proto.is_null()) {
+        return Ok(JsValue::undefined());
+    }
+    let proto = if proto.is_object() {
+        Some(proto.as_object())
+    } else {
+        None
+    };
+    if !args.this.as_object().set_prototype(vm, proto) {
+        return Err(type_error(vm, "Object.prototype.__proto__ setter failed"));
+    }
+    Ok(JsValue::undefined())
+}
+
+fn define_accessor(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    method: &str,
+    getter: bool,
+) -> Result<JsValue, JsValue> {
+    let mut obj = args.this.to_object(vm)?;
+    let f = args.at(1);
+    if !f.is_callable() {
+        return Err(type_error(
+            vm,
+            &format!("Object.prototype.{}: Expecting function", method),
+        ));
+    }
+    let desc = if getter {
+        PropertyDescriptor::accessor_getter(f, E | C)
+    } else {
+        PropertyDescriptor::accessor_setter(f, E | C)
+    };
+    let key = args.at(0).to_symbol(vm)?;
+    obj.define_own_property(vm, key, &desc, true)?;
+    Ok(JsValue::undefined())
+}
+
+fn lookup_accessor(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    getter: bool,
+) -> Result<JsValue, JsValue> {
+    let obj = args.this.to_object(vm)?;
+    let key = args.at(0).to_symbol(vm)?;
+    let mut current = Some(obj);
+    while let Some(obj) = current {
+        let mut slot = Slot::new();
+        if obj.get_own_property_slot(vm, key, &mut slot) {
+            if !slot.attributes().is_accessor() {
+                return Ok(JsValue::undefined());
+            }
+            let accessor = slot.accessor();
+            return Ok(if getter {
+                accessor.getter()
+            } else {
+                accessor.setter()
+            });
+        }
+        current = obj.prototype();
+    }
+    Ok(JsValue::undefined())
+}
+
+/// Object.prototype.__defineGetter__ ( P, getter )
+pub fn object_define_getter(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    define_accessor(vm, args, "__defineGetter__", true)
+}
+
+/// Object.prototype.__defineSetter__ ( P, setter )
+pub fn object_define_setter(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    define_accessor(vm, args, "__defineSetter__", false)
+}
+
+/// Object.prototype.__lookupGetter__ ( P )
+pub fn object_lookup_getter(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    lookup_accessor(vm, args, true)
+}
+
+/// Object.prototype.__lookupSetter__ ( P )
+pub fn object_lookup_setter(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    lookup_accessor(vm, args, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::eval_ok;
+
+    #[test]
+    fn test_define_property_and_descriptors() {
+        let source = "var o = {};
+            Object.defineProperty(o, 'x', { value: 1 });
+            var d = Object.getOwnPropertyDescriptor(o, 'x');
+            o.x = 2;
+            Object.defineProperty(o, 'y', { get: function () { return 7; }, enumerable: true });
+            var g = Object.getOwnPropertyDescriptor(o, 'y');
+            result = [o.x, d.writable, d.enumerable, d.configurable, Object.keys(o).join(),
+                o.y, typeof g.get, g.set, 'value' in g].join('|');";
+        assert_eq!(eval_ok(source), "1|false|false|false|y|7|function||false");
+    }
+
+    #[test]
+    fn test_define_properties() {
+        let source = "var o = Object.defineProperties({}, { a: { value: 1, enumerable: true }, b: { value: 2 } });
+            var ds = Object.getOwnPropertyDescriptors(o);
+            result = [Object.keys(o).join(), ds.a.value, ds.b.value, ds.b.enumerable].join('|');";
+        assert_eq!(eval_ok(source), "a|1|2|false");
+    }
+
+    #[test]
+    fn test_keys_values_entries() {
+        let source = "var o = { a: 1, b: 2 };
+            result = [Object.keys(o).join(), Object.values(o).join(), Object.entries(o).join(';'),
+                Object.fromEntries([['x', 1], ['y', 2]]).y, Object.keys('ab').join()].join('|');";
+        assert_eq!(eval_ok(source), "a,b|1,2|a,1;b,2|2|0,1");
+    }
+
+    #[test]
+    fn test_assign() {
+        let source = "var t = { a: 1 };
+            var r = Object.assign(t, { b: 2 }, null, { a: 3 });
+            result = [r === t, t.a, t.b].join('|');";
+        assert_eq!(eval_ok(source), "true|3|2");
+    }
+
+    #[test]
+    fn test_create_and_prototypes() {
+        let source = "var p = { greet: function () { return 'hi'; } };
+            var o = Object.create(p, { x: { value: 1, enumerable: true } });
+            var n = Object.create(null);
+            result = [o.greet(), o.x, Object.getPrototypeOf(o) === p, Object.getPrototypeOf(n) === null,
+                typeof n.toString].join('|');";
+        assert_eq!(eval_ok(source), "hi|1|true|true|undefined");
+    }
+
+    #[test]
+    fn test_set_prototype_of() {
+        let source = "var a = {};
+            var b = Object.create(a);
+            result = '';
+            try { Object.setPrototypeOf(a, b); } catch (e) { result += e.name; }
+            try { Object.setPrototypeOf(Object.preventExtensions({}), {}); } catch (e) { result += '|' + e.name; }
+            var c = {};
+            result += '|' + (Object.setPrototypeOf(c, a) === c) + '|' + (Object.getPrototypeOf(c) === a);";
+        assert_eq!(eval_ok(source), "TypeError|TypeError|true|true");
+    }
+
+    #[test]
+    fn test_freeze_seal_prevent_extensions() {
+        let source = "var f = Object.freeze({ x: 1 });
+            var s = Object.seal({ y: 1 });
+            var p = Object.preventExtensions({ z: 1 });
+            f.x = 2; s.y = 2; s.w = 1; p.q = 1;
+            result = [f.x, s.y, s.w, p.q, Object.isFrozen(f), Object.isSealed(s), Object.isFrozen(s),
+                Object.isExtensible(p), Object.isExtensible({}), Object.isFrozen(1), Object.freeze(1)].join('|');";
+        assert_eq!(eval_ok(source), "1|2|||true|true|false|false|true|true|1");
+    }
+
+    #[test]
+    fn test_frozen_writes_throw_in_strict_code() {
+        let source = "function write(o) {
+                'use strict';
+                try { o.x = 2; } catch (e) { return e.name; }
+                return 'none';
+            }
+            var a = Object.freeze([1, 2]);
+            result = write(Object.freeze({ x: 1 })) + '|' + write(Object.preventExtensions({}));
+            try { a.push(3); } catch (e) { result += '|' + e.name + a.length; }";
+        assert_eq!(eval_ok(source), "TypeError|TypeError|TypeError2");
+    }
+
+    #[test]
+    fn test_is_and_primitives() {
+        let source = "result = [Object.is(NaN, NaN), Object.is(0, -0), Object.is('a', 'a'), Object.is({}, {})].join();
+            try { Object.defineProperty(1, 'x', { value: 1 }); } catch (e) { result += '|' + e.name; }";
+        assert_eq!(eval_ok(source), "true,false,true,false|TypeError");
+    }
+
+    #[test]
+    fn test_has_own_property_on_exotic_receivers() {
+        let source = "var a = [1, 2];
+            var s = new String('ab');
+            var args = (function () { return arguments; })(7, 8);
+            globalProp = 1;
+            var g = (function () { return this; })();
+            result = [a.hasOwnProperty(0), a.hasOwnProperty(2), a.hasOwnProperty('length'),
+                s.hasOwnProperty(1), s.hasOwnProperty('length'), s.hasOwnProperty(2),
+                args.hasOwnProperty(1), args.hasOwnProperty('length'), args.hasOwnProperty(2),
+                g.hasOwnProperty('globalProp'), g.hasOwnProperty('Object'), ({}).hasOwnProperty('toString')].join();";
+        assert_eq!(
+            eval_ok(source),
+            "true,false,true,true,true,false,true,true,false,true,true,false"
+        );
+    }
+
+    #[test]
+    fn test_is_prototype_of_and_property_is_enumerable() {
+        let source = "var p = {};
+            var o = Object.create(p);
+            var a = [1];
+            var s = new String('ab');
+            result = [p.isPrototypeOf(o), o.isPrototypeOf(p), Object.prototype.isPrototypeOf([]),
+                p.isPrototypeOf(1), a.propertyIsEnumerable(0), a.propertyIsEnumerable('length'),
+                s.propertyIsEnumerable(0), s.propertyIsEnumerable('length'),
+                ({ x: 1 }).propertyIsEnumerable('x'), ({}).propertyIsEnumerable('toString')].join();";
+        assert_eq!(
+            eval_ok(source),
+            "true,false,true,false,true,false,true,false,true,false"
+        );
+    }
+
+    #[test]
+    fn test_value_of_and_to_locale_string() {
+        let source = "var o = {};
+            result = [o.valueOf() === o, typeof new Number(3).valueOf(),
+                ({ toString: function () { return 'T'; } }).toLocaleString(), [1, 2].toLocaleString()].join('|');";
+        assert_eq!(eval_ok(source), "true|number|T|1,2");
+    }
+
+    #[test]
+    fn test_proto_accessor() {
+        let source = "var p = { x: 1 };
+            var o = {};
+            o.__proto__ = p;
+            var q = {};
+            q.__proto__ = 5;
+            result = [o.x, o.__proto__ === p, [].__proto__ === Array.prototype, Object.create(null).__proto__,
+                Object.getPrototypeOf(q) === Object.prototype].join('|');
+            try { var a = {}; a.__proto__ = Object.create(a); } catch (e) { result += '|' + e.name; }";
+        assert_eq!(eval_ok(source), "1|true|true||true|TypeError");
+    }
+
+    #[test]
+    fn test_define_and_lookup_accessors() {
+        let source = "var o = {};
+            o.__defineGetter__('x', function () { return 4; });
+            o.__defineSetter__('x', function (v) { this.y = v; });
+            o.x = 9;
+            var c = Object.create(o);
+            var a = [1, 2];
+            a.__defineGetter__(5, function () { return 'g'; });
+            result = [o.x, o.y, typeof c.__lookupGetter__('x'), typeof o.__lookupSetter__('x'),
+                o.__lookupGetter__('y'), Object.keys(o).join(), a[5], a.length].join('|');
+            try { o.__defineGetter__('z', 1); } catch (e) { result += '|' + e.name; }";
+        assert_eq!(eval_ok(source), "4|9|function|function||x,y|g|6|TypeError");
     }
 }
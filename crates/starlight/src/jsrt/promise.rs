@@ -0,0 +1,541 @@
+use crate::{
+    gc::{cell::Gc, handle::Handle},
+    runtime::{
+        arguments::Arguments,
+        array::JsArray,
+        error::JsAggregateError,
+        function::{JsClosureAPI, JsClosureFunction},
+        gc_array::GcArray,
+        iterator::{get_iterator, iterator_close, iterator_step},
+        object::{JsObject, ObjectTag},
+        promise::*,
+        slot::Slot,
+        string::JsString,
+        symbol::Symbol,
+        value::JsValue,
+    },
+    vm::VirtualMachine,
+};
+
+/// Promise ( executor )
+pub fn promise_constructor(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    if !args.ctor_call {
+        return Err(type_error(
+            vm,
+            "Promise constructor cannot be invoked without 'new'",
+        ));
+    }
+    let executor = args.at(0);
+    if !executor.is_callable() {
+        return Err(type_error(vm, "Promise resolver is not a function"));
+    }
+    let promise = JsPromise::new(vm, args.ctor_structure()).root(vm.space());
+    let (resolve, reject) = create_resolving_functions(vm, *promise);
+    let reject = JsValue::new(reject);
+    if let Err(error) = call(
+        vm,
+        executor,
+        JsValue::undefined(),
+        &[JsValue::new(resolve), reject],
+    ) {
+        call(vm, reject, JsValue::undefined(), &[error])?;
+    }
+    Ok(JsValue::new(*promise))
+}
+
+fn this_promise(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    method: &str,
+) -> Result<Gc<JsObject>, JsValue> {
+    let this = args.this;
+    if !this.is_object() || this.as_object().tag() != ObjectTag::Promise {
+        let msg = format!(
+            "Promise.prototype.{} called on incompatible receiver",
+            method
+        );
+        return Err(type_error(vm, &msg));
+    }
+    Ok(this.as_object())
+}
+
+/// 7.3.22 SpeciesConstructor
+///
+/// `@@species` is not supported, constructor of `obj` is used as if it had the default
+/// `@@species` getter.
+fn species_constructor(vm: &mut VirtualMachine, obj: Gc<JsObject>) -> Result<JsValue, JsValue> {
+    let ctor = obj.get(vm, Symbol::constructor())?;
+    if ctor.is_undefined() {
+        return Ok(JsValue::new(vm.global_data().promise_constructor.unwrap()));
+    }
+    if !ctor.is_object() {
+        return Err(type_error(vm, "object.constructor is not an object"));
+    }
+    Ok(ctor)
+}
+
+/// Promise.prototype.then ( onFulfilled, onRejected )
+pub fn promise_then(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let promise = this_promise(vm, args, "then")?.root(vm.space());
+    let ctor = species_constructor(vm, *promise)?;
+    let capability = new_promise_capability(vm, ctor)?;
+    Ok(perform_promise_then(
+        vm,
+        *promise,
+        args.at(0),
+        args.at(1),
+        Some(capability),
+    ))
+}
+
+/// Promise.prototype.catch ( onRejected )
+pub fn promise_catch(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let then = vm.intern("then");
+    invoke(vm, args.this, then, &[JsValue::undefined(), args.at(0)])
+}
+
+/// Promise.prototype.finally ( onFinally )
+pub fn promise_finally(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let this = args.this;
+    if !this.is_object() {
+        return Err(type_error(
+            vm,
+            "Promise.prototype.finally called on non-object",
+        ));
+    }
+    let ctor = species_constructor(vm, this.as_object())?;
+    let on_finally = args.at(0);
+    let (then_finally, catch_finally) = if on_finally.is_callable() {
+        let then_finally = closure(vm, then_finally_function, &[on_finally, ctor], 1);
+        let then_finally = then_finally.root(vm.space());
+        let catch_finally = closure(vm, catch_finally_function, &[on_finally, ctor], 1);
+        (JsValue::new(*then_finally), JsValue::new(catch_finally))
+    } else {
+        (on_finally, on_finally)
+    };
+    let then = vm.intern("then");
+    invoke(vm, this, then, &[then_finally, catch_finally])
+}
+
+/// Then Finally Functions, captures `[onFinally, C]`.
+fn then_finally_function(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    data: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue> {
+    let result = call(vm, data[0], JsValue::undefined(), &[])?;
+    let promise = promise_resolve(vm, data[1], result)?;
+    let value_thunk = closure(vm, return_captured, &[args.at(0)], 0);
+    let then = vm.intern("then");
+    invoke(vm, promise, then, &[JsValue::new(value_thunk)])
+}
+
+/// Catch Finally Functions, captures `[onFinally, C]`.
+fn catch_finally_function(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    data: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue> {
+    let result = call(vm, data[0], JsValue::undefined(), &[])?;
+    let promise = promise_resolve(vm, data[1], result)?;
+    let thrower = closure(vm, throw_captured, &[args.at(0)], 0);
+    let then = vm.intern("then");
+    invoke(vm, promise, then, &[JsValue::new(thrower)])
+}
+
+fn return_captured(
+    _vm: &mut VirtualMachine,
+    _args: &Arguments,
+    data: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue> {
+    Ok(data[0])
+}
+
+fn throw_captured(
+    _vm: &mut VirtualMachine,
+    _args: &Arguments,
+    data: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue> {
+    Err(data[0])
+}
+
+/// Promise.resolve ( x )
+pub fn promise_resolve_static(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    if !args.this.is_object() {
+        return Err(type_error(vm, "PromiseResolve called on non-object"));
+    }
+    promise_resolve(vm, args.this, args.at(0))
+}
+
+/// Promise.reject ( r )
+pub fn promise_reject_static(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let capability = new_promise_capability(vm, args.this)?;
+    call(vm, capability.reject, JsValue::undefined(), &[args.at(0)])?;
+    Ok(capability.promise)
+}
+
+/// Promise.all ( iterable )
+pub fn promise_all(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    promise_combinator(vm, args, Combinator::All)
+}
+
+/// Promise.allSettled ( iterable )
+pub fn promise_all_settled(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    promise_combinator(vm, args, Combinator::AllSettled)
+}
+
+/// Promise.any ( iterable )
+pub fn promise_any(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    promise_combinator(vm, args, Combinator::Any)
+}
+
+/// Promise.race ( iterable )
+pub fn promise_race(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    promise_combinator(vm, args, Combinator::Race)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    All,
+    AllSettled,
+    Any,
+    Race,
+}
+
+fn promise_combinator(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    kind: Combinator,
+) -> Result<JsValue, JsValue> {
+    let ctor = Handle::new(vm.space(), args.this);
+    let capability = new_promise_capability(vm, *ctor)?;
+    let capability = Handle::new(vm.space(), capability);
+    match perform_combinator(vm, *ctor, *capability, args.at(0), kind) {
+        Ok(()) => Ok(capability.promise),
+        // IfAbruptRejectPromise
+        Err(error) => {
+            call(vm, capability.reject, JsValue::undefined(), &[error])?;
+            Ok(capability.promise)
+        }
+    }
+}
+
+/// PerformPromiseAll, PerformPromiseAllSettled, PerformPromiseAny and PerformPromiseRace.
+///
+/// Element functions share `[values, remainingElementsCount, resolve]` record, `resolve` is
+/// the reject function of the capability for `Promise.any`.
+fn perform_combinator(
+    vm: &mut VirtualMachine,
+    ctor: JsValue,
+    capability: PromiseCapability,
+    iterable: JsValue,
+    kind: Combinator,
+) -> Result<(), JsValue> {
+    let name = vm.intern("resolve");
+    let promise_resolve = ctor.get_slot(vm, name, &mut Slot::new())?;
+    let promise_resolve = Handle::new(vm.space(), promise_resolve);
+    if !promise_resolve.is_callable() {
+        return Err(type_error(vm, "Promise resolve is not a function"));
+    }
+    let (iterator, next) = get_iterator(vm, iterable)?;
+    let iterator = Handle::new(vm.space(), iterator);
+    let next = Handle::new(vm.space(), next);
+    let values = JsArray::new(vm, 0).root(vm.space());
+    let mut record = GcArray::new(vm, 3, JsValue::undefined()).root(vm.space());
+    record[0] = JsValue::new(*values);
+    record[1] = JsValue::new(1);
+    record[2] = if kind == Combinator::Any {
+        capability.reject
+    } else {
+        capability.resolve
+    };
+    let mut index = 0;
+    while let Some(value) = iterator_step(vm, *iterator, *next)? {
+        let value = Handle::new(vm.space(), value);
+        let result = combinator_step(
+            vm,
+            kind,
+            ctor,
+            *promise_resolve,
+            capability,
+            *record,
+            index,
+            *value,
+        );
+        if let Err(error) = result {
+            let error = Handle::new(vm.space(), error);
+            let _ = iterator_close(vm, *iterator);
+            return Err(*error);
+        }
+        index += 1;
+    }
+    if kind != Combinator::Race {
+        record[1] = JsValue::new(record[1].number() - 1.0);
+        if record[1].number() == 0.0 {
+            settle_combinator(vm, kind, *record)?;
+        }
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn combinator_step(
+    vm: &mut VirtualMachine,
+    kind: Combinator,
+    ctor: JsValue,
+    promise_resolve: JsValue,
+    capability: PromiseCapability,
+    mut record: Gc<GcArray<JsValue>>,
+    index: u32,
+    value: JsValue,
+) -> Result<JsValue, JsValue> {
+    let next_promise = call(vm, promise_resolve, ctor, &[value])?;
+    let next_promise = Handle::new(vm.space(), next_promise);
+    let then = vm.intern("then");
+    if kind == Combinator::Race {
+        return invoke(
+            vm,
+            *next_promise,
+            then,
+            &[capability.resolve, capability.reject],
+        );
+    }
+    record[0]
+        .as_object()
+        .put(vm, Symbol::Indexed(index), JsValue::undefined(), false)?;
+    record[1] = JsValue::new(record[1].number() + 1.0);
+    // element functions of one element share `[index, alreadyCalled, record]`.
+    let mut element = GcArray::new(vm, 3, JsValue::undefined()).root(vm.space());
+    element[0] = JsValue::new(index);
+    element[1] = JsValue::new(false);
+    element[2] = JsValue::new(record);
+    let element_function = |vm: &mut VirtualMachine, f: JsClosureAPI| {
+        let name = vm.intern("");
+        JsValue::new(JsClosureFunction::new(vm, name, f, *element, 1))
+    };
+    let on_fulfilled = match kind {
+        Combinator::All => element_function(vm, all_resolve_element),
+        Combinator::AllSettled => element_function(vm, all_settled_resolve_element),
+        Combinator::Any => capability.resolve,
+        Combinator::Race => unreachable!(),
+    };
+    let on_fulfilled = Handle::new(vm.space(), on_fulfilled);
+    let on_rejected = match kind {
+        Combinator::All => capability.reject,
+        Combinator::AllSettled => element_function(vm, all_settled_reject_element),
+        Combinator::Any => element_function(vm, any_reject_element),
+        Combinator::Race => unreachable!(),
+    };
+    let on_rejected = Handle::new(vm.space(), on_rejected);
+    invoke(vm, *next_promise, then, &[*on_fulfilled, *on_rejected])
+}
+
+/// Store `value` of the element described by `element` and settle the combined promise once
+/// all elements are done. Does nothing if an element function of the element was already
+/// called.
+fn store_element(
+    vm: &mut VirtualMachine,
+    mut element: Gc<GcArray<JsValue>>,
+    value: JsValue,
+    kind: Combinator,
+) -> Result<JsValue, JsValue> {
+    if element[1].to_boolean() {
+        return Ok(JsValue::undefined());
+    }
+    element[1] = JsValue::new(true);
+    let mut record = element[2].as_cell().downcast::<GcArray<JsValue>>().unwrap();
+    let index = element[0].number() as u32;
+    record[0]
+        .as_object()
+        .put(vm, Symbol::Indexed(index), value, false)?;
+    record[1] = JsValue::new(record[1].number() - 1.0);
+    if record[1].number() == 0.0 {
+        settle_combinator(vm, kind, record)?;
+    }
+    Ok(JsValue::undefined())
+}
+
+fn settle_combinator(
+    vm: &mut VirtualMachine,
+    kind: Combinator,
+    record: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue> {
+    let values = record[0];
+    let result = if kind == Combinator::Any {
+        let msg = JsString::new(vm, "All promises were rejected").root(vm.space());
+        JsValue::new(JsAggregateError::new(vm, *msg, values, None))
+    } else {
+        values
+    };
+    call(vm, record[2], JsValue::undefined(), &[result])
+}
+
+/// Promise.all Resolve Element Functions
+fn all_resolve_element(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    element: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue> {
+    store_element(vm, element, args.at(0), Combinator::All)
+}
+
+/// Promise.allSettled Resolve Element Functions
+fn all_settled_resolve_element(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    element: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue> {
+    let entry = settled_entry(vm, "fulfilled", Symbol::value(), args.at(0))?;
+    store_element(vm, element, entry, Combinator::AllSettled)
+}
+
+/// Promise.allSettled Reject Element Functions
+fn all_settled_reject_element(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    element: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue> {
+    let reason = vm.intern("reason");
+    let entry = settled_entry(vm, "rejected", reason, args.at(0))?;
+    store_element(vm, element, entry, Combinator::AllSettled)
+}
+
+/// Promise.any Reject Element Functions
+fn any_reject_element(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    element: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue> {
+    store_element(vm, element, args.at(0), Combinator::Any)
+}
+
+/// `{ status, value }` or `{ status, reason }` object of `Promise.allSettled` result.
+fn settled_entry(
+    vm: &mut VirtualMachine,
+    status: &str,
+    key: Symbol,
+    value: JsValue,
+) -> Result<JsValue, JsValue> {
+    let mut obj = JsObject::new_empty(vm).root(vm.space());
+    let status = JsValue::new(JsString::new(vm, status));
+    let name = vm.intern("status");
+    obj.put(vm, name, status, false)?;
+    obj.put(vm, key, value, false)?;
+    Ok(JsValue::new(*obj))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::eval_ok;
+
+    #[test]
+    fn test_then_callbacks_run_as_jobs() {
+        let source = "var log = '';
+            Promise.resolve(1)
+                .then(function (v) { log += 'a' + v; return v + 1; })
+                .then(function (v) { log += 'b' + v; result = log; });
+            log += 's';";
+        assert_eq!(eval_ok(source), "sa1b2");
+    }
+
+    #[test]
+    fn test_rejection_handlers() {
+        let source = "var log = '';
+            new Promise(function (res, rej) { rej('e'); })
+                .then(function () { log += 'no'; }, function (e) { log += 'r' + e; });
+            new Promise(function () { throw 't'; }).catch(function (e) { log += e; });
+            Promise.reject('x')
+                .catch(function (e) { return e + 'c'; })
+                .finally(function () { log += 'f'; })
+                .then(function (v) { result = log + v; });";
+        assert_eq!(eval_ok(source), "retfxc");
+    }
+
+    #[test]
+    fn test_resolve_with_promise() {
+        let source = "var p = Promise.resolve(1);
+            var r;
+            var q = new Promise(function (res) { r = res; });
+            r(q);
+            q.catch(function (e) { result += e.name; });
+            result = (Promise.resolve(p) === p) + ' ';";
+        assert_eq!(eval_ok(source), "true TypeError");
+    }
+
+    #[test]
+    fn test_promise_combinators() {
+        let source = "var log = '';
+            Promise.all([1, Promise.resolve(2), 3]).then(function (v) { log += v.join(','); });
+            Promise.all([1, Promise.reject('no'), 3]).catch(function (e) { log += ' ' + e; });
+            Promise.race([new Promise(function () {}), Promise.resolve('fast')])
+                .then(function (v) { result = log + ' ' + v; });";
+        assert_eq!(eval_ok(source), "1,2,3 no fast");
+    }
+
+    #[test]
+    fn test_async_functions() {
+        let source = "async function add(x) { var a = await x; var b = await Promise.resolve(2); return a + b; }
+            async function fail() { throw 'bad'; }
+            async function handle() {
+                try { await Promise.reject('r'); } catch (e) { return 'handled ' + e; }
+            }
+            var twice = async (x) => x * 2;
+            var failed = fail().catch(function (e) { return e; });
+            Promise.all([add(1), failed, handle(), twice(4)])
+                .then(function (v) { result = v.join(); });";
+        assert_eq!(eval_ok(source), "3,bad,handled r,8");
+    }
+
+    #[test]
+    fn test_await_suspends_until_job() {
+        let source = "var log = '';
+            async function f() { log += '1'; await null; log += '3'; }
+            f();
+            log += '2';
+            Promise.resolve().then(function () { result = log; });";
+        assert_eq!(eval_ok(source), "123");
+    }
+
+    #[test]
+    fn test_many_awaits_survive_collections() {
+        let source = "async function sum(n) {
+                var acc = 0;
+                for (var i = 0; i < n; i = i + 1) { acc = acc + await i; }
+                return acc;
+            }
+            sum(3000).then(function (v) { result = v; });";
+        assert_eq!(eval_ok(source), "4498500");
+    }
+
+    #[test]
+    fn test_combinators_survive_collections() {
+        let source = "var ps = [];
+            for (var i = 0; i < 2000; i = i + 1) { ps.push(Promise.resolve(i)); }
+            var log = [];
+            Promise.all(ps).then(function (v) { log.push(v.length + ':' + v[1999]); });
+            Promise.allSettled(ps).then(function (v) { log.push(v[1999].status + v[1999].value); });
+            Promise.any(ps).then(function (v) { log.push('any' + v); });
+            Promise.race(ps).then(function (v) { log.push('race' + v); });
+            Promise.all([Promise.all(ps), Promise.allSettled(ps)])
+                .then(function () { result = log.join(); });";
+        assert_eq!(eval_ok(source), "2000:1999,fulfilled1999,any0,race0");
+    }
+
+    #[test]
+    fn test_any_rejects_with_aggregate_error() {
+        let source = "Promise.any([Promise.reject(1), Promise.reject(2)]).catch(function (e) {
+                result = [e instanceof AggregateError, e instanceof Error, e.name,
+                    e.message, e.errors.join(',')].join('|');
+            });";
+        assert_eq!(
+            eval_ok(source),
+            "true|true|AggregateError|All promises were rejected|1,2"
+        );
+    }
+}
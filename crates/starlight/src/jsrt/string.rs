@@ -0,0 +1,616 @@
+use crate::{
+    runtime::{
+        arguments::Arguments,
+        array::JsArray,
+        error::{JsRangeError, JsTypeError},
+        iterator::JsStringIterator,
+        object::ObjectTag,
+        promise::type_error,
+        string::{is_js_whitespace, JsString, JsStringObject},
+        symbol::Symbol,
+        value::JsValue,
+    },
+    vm::VirtualMachine,
+};
+use wtf_rs::pure_nan::pure_nan;
+
+/// String.prototype [ @@iterator ] ( )
+pub fn string_iterator(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let this = args.this;
+    if this.is_undefined_or_null() {
+        let msg = JsString::new(
+            vm,
+            "String.prototype[Symbol.iterator] called on null or undefined",
+        );
+        return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
+    }
+    let string = this.to_js_string(vm)?;
+    Ok(JsValue::new(JsStringIterator::new(vm, string)))
+}
+
+pub(crate) fn range_error(vm: &mut VirtualMachine, msg: String) -> JsValue {
+    let msg = JsString::new(vm, msg).root(vm.space());
+    JsValue::new(JsRangeError::new(vm, *msg, None))
+}
+
+fn new_string(vm: &mut VirtualMachine, units: &[u16]) -> JsValue {
+    JsValue::new(JsString::from_utf16(vm, units))
+}
+
+fn to_units(vm: &mut VirtualMachine, value: JsValue) -> Result<Vec<u16>, JsValue> {
+    Ok(value.to_js_string(vm)?.to_utf16().into_owned())
+}
+
+/// RequireObjectCoercible ( this ) followed by ToString, as UTF-16 code units.
+fn this_string(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    method: &str,
+) -> Result<Vec<u16>, JsValue> {
+    if args.this.is_undefined_or_null() {
+        return Err(type_error(
+            vm,
+            &format!("String.prototype.{} called on null or undefined", method),
+        ));
+    }
+    to_units(vm, args.this)
+}
+
+/// Clamp a relative index as taken by `slice` and `at`; negative values count from `len`.
+fn relative_index(n: f64, len: usize) -> usize {
+    if n < 0.0 {
+        (len as f64 + n).max(0.0) as usize
+    } else {
+        n.min(len as f64) as usize
+    }
+}
+
+fn find_units(units: &[u16], search: &[u16], from: usize) -> Option<usize> {
+    if search.len() > units.len() {
+        return None;
+    }
+    (from..=units.len() - search.len()).find(|&ix| units[ix..].starts_with(search))
+}
+
+/// 22.1.1.1 String ( value )
+pub fn string_constructor(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let value = if args.size() == 0 {
+        JsString::new(vm, "")
+    } else {
+        let value = args.at(0);
+        if value.is_symbol() && !args.ctor_call {
+            let desc = vm.description(value.as_symbol().sym());
+            JsString::new(vm, format!("Symbol({})", desc))
+        } else {
+            value.to_js_string(vm)?
+        }
+    };
+    if !args.ctor_call {
+        return Ok(JsValue::new(value));
+    }
+    Ok(JsValue::new(JsStringObject::new(vm, value)))
+}
+
+/// thisStringValue ( value )
+fn this_string_value(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    method: &str,
+) -> Result<JsValue, JsValue> {
+    let this = args.this;
+    if this.is_string() {
+        return Ok(this);
+    }
+    if this.is_object() && this.as_object().tag() == ObjectTag::String {
+        return Ok(JsValue::new(this.as_object().as_string().value()));
+    }
+    Err(type_error(
+        vm,
+        &format!(
+            "String.prototype.{} requires that 'this' be a String",
+            method
+        ),
+    ))
+}
+
+/// String.prototype.toString ( )
+pub fn string_to_string(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    this_string_value(vm, args, "toString")
+}
+
+/// String.prototype.valueOf ( )
+pub fn string_value_of(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    this_string_value(vm, args, "valueOf")
+}
+
+/// String.prototype.at ( index )
+pub fn string_at(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "at")?;
+    let n = args.at(0).to_integer_or_infinity(vm)?;
+    let k = if n < 0.0 { s.len() as f64 + n } else { n };
+    if k < 0.0 || k >= s.len() as f64 {
+        return Ok(JsValue::undefined());
+    }
+    Ok(new_string(vm, &s[k as usize..k as usize + 1]))
+}
+
+/// String.prototype.charAt ( pos )
+pub fn string_char_at(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "charAt")?;
+    let pos = args.at(0).to_integer_or_infinity(vm)?;
+    if pos < 0.0 || pos >= s.len() as f64 {
+        return Ok(JsValue::new(JsString::new(vm, "")));
+    }
+    Ok(new_string(vm, &s[pos as usize..pos as usize + 1]))
+}
+
+/// String.prototype.charCodeAt ( pos )
+pub fn string_char_code_at(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "charCodeAt")?;
+    let pos = args.at(0).to_integer_or_infinity(vm)?;
+    if pos < 0.0 || pos >= s.len() as f64 {
+        return Ok(JsValue::new(pure_nan()));
+    }
+    Ok(JsValue::new(s[pos as usize] as i32))
+}
+
+/// String.prototype.codePointAt ( pos )
+pub fn string_code_point_at(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "codePointAt")?;
+    let pos = args.at(0).to_integer_or_infinity(vm)?;
+    if pos < 0.0 || pos >= s.len() as f64 {
+        return Ok(JsValue::undefined());
+    }
+    let pos = pos as usize;
+    let first = s[pos];
+    if (0xd800..=0xdbff).contains(&first) && pos + 1 < s.len() {
+        let second = s[pos + 1];
+        if (0xdc00..=0xdfff).contains(&second) {
+            let cp = 0x10000 + ((first as i32 - 0xd800) << 10) + (second as i32 - 0xdc00);
+            return Ok(JsValue::new(cp));
+        }
+    }
+    Ok(JsValue::new(first as i32))
+}
+
+/// String.prototype.concat ( ...args )
+pub fn string_concat(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let mut s = this_string(vm, args, "concat")?;
+    for ix in 0..args.size() {
+        s.extend(to_units(vm, args.at(ix))?);
+    }
+    Ok(new_string(vm, &s))
+}
+
+/// String.prototype.endsWith ( searchString [ , endPosition ] )
+pub fn string_ends_with(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "endsWith")?;
+    let search = to_units(vm, args.at(0))?;
+    let end = if args.at(1).is_undefined() {
+        s.len()
+    } else {
+        let pos = args.at(1).to_integer_or_infinity(vm)?;
+        pos.max(0.0).min(s.len() as f64) as usize
+    };
+    Ok(JsValue::new(s[..end].ends_with(&search)))
+}
+
+/// String.prototype.includes ( searchString [ , position ] )
+pub fn string_includes(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "includes")?;
+    let search = to_units(vm, args.at(0))?;
+    let pos = args.at(1).to_integer_or_infinity(vm)?;
+    let start = pos.max(0.0).min(s.len() as f64) as usize;
+    Ok(JsValue::new(find_units(&s, &search, start).is_some()))
+}
+
+/// String.prototype.indexOf ( searchString [ , position ] )
+pub fn string_index_of(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "indexOf")?;
+    let search = to_units(vm, args.at(0))?;
+    let pos = args.at(1).to_integer_or_infinity(vm)?;
+    let start = pos.max(0.0).min(s.len() as f64) as usize;
+    match find_units(&s, &search, start) {
+        Some(ix) => Ok(JsValue::new(ix as i32)),
+        None => Ok(JsValue::new(-1)),
+    }
+}
+
+/// String.prototype.lastIndexOf ( searchString [ , position ] )
+pub fn string_last_index_of(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "lastIndexOf")?;
+    let search = to_units(vm, args.at(0))?;
+    let num_pos = args.at(1).to_number(vm)?;
+    let pos = if num_pos.is_nan() {
+        f64::INFINITY
+    } else {
+        num_pos.trunc()
+    };
+    if search.len() > s.len() {
+        return Ok(JsValue::new(-1));
+    }
+    let start = pos.max(0.0).min((s.len() - search.len()) as f64) as usize;
+    match (0..=start).rev().find(|&ix| s[ix..].starts_with(&search)) {
+        Some(ix) => Ok(JsValue::new(ix as i32)),
+        None => Ok(JsValue::new(-1)),
+    }
+}
+
+fn string_pad(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    at_start: bool,
+) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, if at_start { "padStart" } else { "padEnd" })?;
+    let max_length = args.at(0).to_integer_or_infinity(vm)?;
+    if max_length <= s.len() as f64 {
+        return Ok(new_string(vm, &s));
+    }
+    let filler = if args.at(1).is_undefined() {
+        vec![0x20]
+    } else {
+        to_units(vm, args.at(1))?
+    };
+    if filler.is_empty() {
+        return Ok(new_string(vm, &s));
+    }
+    if max_length > MAX_STRING_LENGTH as f64 {
+        return Err(range_error(vm, "Invalid string length".to_owned()));
+    }
+    let fill_len = max_length as usize - s.len();
+    let padding = filler.iter().copied().cycle().take(fill_len);
+    let result: Vec<u16> = if at_start {
+        padding.chain(s.iter().copied()).collect()
+    } else {
+        s.iter().copied().chain(padding).collect()
+    };
+    Ok(new_string(vm, &result))
+}
+
+/// String.prototype.padEnd ( maxLength [ , fillString ] )
+pub fn string_pad_end(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    string_pad(vm, args, false)
+}
+
+/// String.prototype.padStart ( maxLength [ , fillString ] )
+pub fn string_pad_start(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    string_pad(vm, args, true)
+}
+
+/// Longest string `repeat` and `pad*` will build.
+const MAX_STRING_LENGTH: usize = (1 << 30) - 25;
+
+/// String.prototype.repeat ( count )
+pub fn string_repeat(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "repeat")?;
+    let n = args.at(0).to_integer_or_infinity(vm)?;
+    if n < 0.0 || n.is_infinite() {
+        return Err(range_error(vm, format!("Invalid count value: {}", n)));
+    }
+    if s.is_empty() || n == 0.0 {
+        return Ok(JsValue::new(JsString::new(vm, "")));
+    }
+    if n * s.len() as f64 > MAX_STRING_LENGTH as f64 {
+        return Err(range_error(vm, "Invalid string length".to_owned()));
+    }
+    Ok(new_string(vm, &s.repeat(n as usize)))
+}
+
+/// String.prototype.slice ( start, end )
+pub fn string_slice(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "slice")?;
+    let start = args.at(0).to_integer_or_infinity(vm)?;
+    let from = relative_index(start, s.len());
+    let to = if args.at(1).is_undefined() {
+        s.len()
+    } else {
+        let end = args.at(1).to_integer_or_infinity(vm)?;
+        relative_index(end, s.len())
+    };
+    if from >= to {
+        return Ok(JsValue::new(JsString::new(vm, "")));
+    }
+    Ok(new_string(vm, &s[from..to]))
+}
+
+/// String.prototype.split ( separator, limit )
+pub fn string_split(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "split")?;
+    let limit = if args.at(1).is_undefined() {
+        u32::MAX
+    } else {
+        args.at(1).to_uint32(vm)?
+    };
+    let separator = args.at(0);
+    let separator = if separator.is_undefined() {
+        None
+    } else {
+        Some(to_units(vm, separator)?)
+    };
+
+    let mut parts: Vec<&[u16]> = vec![];
+    match separator {
+        _ if limit == 0 => (),
+        None => parts.push(&s),
+        Some(ref sep) if sep.is_empty() => {
+            parts.extend(s.chunks(1).take(limit as usize));
+        }
+        Some(ref sep) => {
+            let mut start = 0;
+            while let Some(ix) = find_units(&s, sep, start) {
+                parts.push(&s[start..ix]);
+                if parts.len() == limit as usize {
+                    break;
+                }
+                start = ix + sep.len();
+            }
+            if parts.len() < limit as usize {
+                parts.push(&s[start..]);
+            }
+        }
+    }
+
+    let mut ary = JsArray::new(vm, parts.len() as _).root(vm.space());
+    for (ix, part) in parts.iter().enumerate() {
+        let part = new_string(vm, part);
+        ary.put(vm, Symbol::Indexed(ix as _), part, false)?;
+    }
+    Ok(JsValue::new(*ary))
+}
+
+/// String.prototype.startsWith ( searchString [ , position ] )
+pub fn string_starts_with(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "startsWith")?;
+    let search = to_units(vm, args.at(0))?;
+    let pos = args.at(1).to_integer_or_infinity(vm)?;
+    let start = pos.max(0.0).min(s.len() as f64) as usize;
+    Ok(JsValue::new(s[start..].starts_with(&search)))
+}
+
+/// String.prototype.substr ( start, length )
+pub fn string_substr(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "substr")?;
+    let start = args.at(0).to_integer_or_infinity(vm)?;
+    let from = relative_index(start, s.len());
+    let length = if args.at(1).is_undefined() {
+        s.len() as f64
+    } else {
+        args.at(1).to_integer_or_infinity(vm)?
+    };
+    let to = (from as f64 + length).min(s.len() as f64);
+    if to <= from as f64 {
+        return Ok(JsValue::new(JsString::new(vm, "")));
+    }
+    Ok(new_string(vm, &s[from..to as usize]))
+}
+
+/// String.prototype.substring ( start, end )
+pub fn string_substring(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "substring")?;
+    let len = s.len() as f64;
+    let start = args.at(0).to_integer_or_infinity(vm)?.max(0.0).min(len) as usize;
+    let end = if args.at(1).is_undefined() {
+        s.len()
+    } else {
+        args.at(1).to_integer_or_infinity(vm)?.max(0.0).min(len) as usize
+    };
+    let (from, to) = if start < end {
+        (start, end)
+    } else {
+        (end, start)
+    };
+    Ok(new_string(vm, &s[from..to]))
+}
+
+/// Map every code point through `convert`, keeping lone surrogates as they are.
+fn convert_case(units: &[u16], convert: fn(char, &mut Vec<u16>)) -> Vec<u16> {
+    let mut result = Vec::with_capacity(units.len());
+    for c in std::char::decode_utf16(units.iter().copied()) {
+        match c {
+            Ok(c) => convert(c, &mut result),
+            Err(e) => result.push(e.unpaired_surrogate()),
+        }
+    }
+    result
+}
+
+/// String.prototype.toLowerCase ( )
+pub fn string_to_lower_case(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "toLowerCase")?;
+    let s = convert_case(&s, |c, out| {
+        for c in c.to_lowercase() {
+            out.extend(c.encode_utf16(&mut [0; 2]).iter());
+        }
+    });
+    Ok(new_string(vm, &s))
+}
+
+/// String.prototype.toUpperCase ( )
+pub fn string_to_upper_case(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, "toUpperCase")?;
+    let s = convert_case(&s, |c, out| {
+        for c in c.to_uppercase() {
+            out.extend(c.encode_utf16(&mut [0; 2]).iter());
+        }
+    });
+    Ok(new_string(vm, &s))
+}
+
+fn string_trim_impl(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    method: &str,
+    start: bool,
+    end: bool,
+) -> Result<JsValue, JsValue> {
+    let s = this_string(vm, args, method)?;
+    let mut from = 0;
+    let mut to = s.len();
+    if start {
+        while from < to && is_js_whitespace(s[from]) {
+            from += 1;
+        }
+    }
+    if end {
+        while to > from && is_js_whitespace(s[to - 1]) {
+            to -= 1;
+        }
+    }
+    Ok(new_string(vm, &s[from..to]))
+}
+
+/// String.prototype.trim ( )
+pub fn string_trim(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    string_trim_impl(vm, args, "trim", true, true)
+}
+
+/// String.prototype.trimEnd ( )
+pub fn string_trim_end(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    string_trim_impl(vm, args, "trimEnd", false, true)
+}
+
+/// String.prototype.trimStart ( )
+pub fn string_trim_start(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    string_trim_impl(vm, args, "trimStart", true, false)
+}
+
+/// String.fromCharCode ( ...codeUnits )
+pub fn string_from_char_code(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let mut units = Vec::with_capacity(args.size());
+    for ix in 0..args.size() {
+        units.push(args.at(ix).to_uint32(vm)? as u16);
+    }
+    Ok(new_string(vm, &units))
+}
+
+/// String.fromCodePoint ( ...codePoints )
+pub fn string_from_code_point(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+) -> Result<JsValue, JsValue> {
+    let mut units = Vec::with_capacity(args.size());
+    for ix in 0..args.size() {
+        let next = args.at(ix).to_number(vm)?;
+        if next.trunc() != next || !(0.0..=1114111.0).contains(&next) {
+            return Err(range_error(vm, format!("Invalid code point {}", next)));
+        }
+        let cp = next as u32;
+        if cp < 0x10000 {
+            units.push(cp as u16);
+        } else {
+            let cp = cp - 0x10000;
+            units.push(0xd800 + (cp >> 10) as u16);
+            units.push(0xdc00 + (cp & 0x3ff) as u16);
+        }
+    }
+    Ok(new_string(vm, &units))
+}
+
+/// String.raw ( template, ...substitutions )
+pub fn string_raw(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let cooked = args.at(0).to_object(vm)?.root(vm.space());
+    let raw = vm.intern("raw");
+    let literals = cooked.get(vm, raw)?.to_object(vm)?.root(vm.space());
+    let count = literals
+        .get(vm, Symbol::length())?
+        .to_integer_or_infinity(vm)?;
+    let mut result = vec![];
+    let mut ix = 0;
+    while (ix as f64) < count {
+        let segment = literals.get(vm, Symbol::Indexed(ix))?;
+        result.extend(to_units(vm, segment)?);
+        if ix as f64 + 1.0 == count {
+            break;
+        }
+        if ((ix + 1) as usize) < args.size() {
+            result.extend(to_units(vm, args.at(ix as usize + 1))?);
+        }
+        ix += 1;
+    }
+    Ok(new_string(vm, &result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::eval_ok;
+
+    #[test]
+    fn test_search_and_extract() {
+        let source = "var s = 'Hello, World';
+            result = [s.charAt(1), s.charCodeAt(0), s.indexOf('o'), s.lastIndexOf('o'),
+                s.indexOf('o', 5), s.includes('World'), s.startsWith('Hell'), s.endsWith('ld'),
+                s.slice(-5), s.slice(2, -3), s.substring(5, 2), s.substr(-5, 2), s.at(-1), s.at(20),
+                s.indexOf(''), s.lastIndexOf('')].join('|');";
+        assert_eq!(
+            eval_ok(source),
+            "e|72|4|8|8|true|true|true|World|llo, Wo|llo|Wo|d||0|12"
+        );
+    }
+
+    #[test]
+    fn test_transform() {
+        let source =
+            "result = ['abc'.toUpperCase(), '\u{c0}BC'.toLowerCase(), '  x  '.trim() + '.',
+                '  x'.trimStart(), 'x  '.trimEnd() + '.', 'ab'.repeat(3), '5'.padStart(3, '0'),
+                '5'.padEnd(4, 'ab'), 'x'.concat(1, 2)].join('|');";
+        assert_eq!(eval_ok(source), "ABC|\u{e0}bc|x.|x|x.|ababab|005|5aba|x12");
+    }
+
+    #[test]
+    fn test_split() {
+        let source = "result = ['a,b,,c'.split(',').length, 'abc'.split('').join('-'),
+                'a-b-c'.split('-', 2).join(), 'abc'.split(undefined).length, ''.split('').length,
+                ''.split(',').length].join('|');";
+        assert_eq!(eval_ok(source), "4|a-b-c|a,b|1|0|1");
+    }
+
+    #[test]
+    fn test_code_points() {
+        let source = "var out = [];
+            for (var c of '\u{1F600}a') out.push(c.length);
+            result = ['abc'.codePointAt(1), '\u{1F600}'.codePointAt(0), '\u{1F600}'.codePointAt(1),
+                String.fromCharCode(72, 105, 65601), String.fromCodePoint(0x1F600).length,
+                out.join()].join('|');";
+        assert_eq!(eval_ok(source), "98|128512|56832|HiA|2|2,1");
+    }
+
+    #[test]
+    fn test_string_raw() {
+        let source =
+            "result = String.raw`a\\n${1}b` + String.raw({ raw: ['x', 'y', 'z'] }, 1, 2, 3) +
+                String.raw({ raw: 'abc' }, '-', '+');";
+        assert_eq!(eval_ok(source), "a\\n1bx1y2za-b+c");
+    }
+
+    #[test]
+    fn test_string_constructor() {
+        let source = "var s = new String('ab');
+            result = [String(123), String(null), String(Symbol('d')), typeof s, s.length, s[1],
+                Object.keys(s).join(), s == 'ab', typeof s.valueOf()].join('|');";
+        assert_eq!(
+            eval_ok(source),
+            "123|null|Symbol(d)|object|2|b|0,1|true|string"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let source = "result = '';
+            var cases = [
+                function () { var trim = String.prototype.trim; trim(); },
+                function () { 'a'.repeat(-1); },
+                function () { String.fromCodePoint(-1); },
+                function () { new String(Symbol()); }
+            ];
+            for (var f of cases) {
+                try { f(); } catch (e) { result += e.name + ' '; }
+            }";
+        assert_eq!(
+            eval_ok(source),
+            "TypeError RangeError RangeError TypeError "
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    runtime::{
+        arguments::Arguments,
+        object::ObjectTag,
+        promise::type_error,
+        string::JsString,
+        symbol::{JsSymbol, Symbol},
+        value::JsValue,
+    },
+    vm::VirtualMachine,
+};
+
+/// 20.4.1.1 Symbol ( [ description ] )
+pub fn symbol_constructor(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    if args.ctor_call {
+        return Err(type_error(vm, "Symbol is not a constructor"));
+    }
+    let description = args.at(0);
+    let sym = if description.is_undefined() {
+        Symbol::new_unique(vm, None)
+    } else {
+        let description = description.to_string(vm)?;
+        Symbol::new_unique(vm, Some(&description))
+    };
+    Ok(JsValue::new(JsSymbol::new(vm, sym)))
+}
+
+/// 20.4.2.2 Symbol.for ( key )
+pub fn symbol_for(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let key = args.at(0).to_string(vm)?;
+    let sym = vm.symbol_for(key);
+    Ok(JsValue::new(JsSymbol::new(vm, sym)))
+}
+
+/// 20.4.2.6 Symbol.keyFor ( sym )
+pub fn symbol_key_for(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let sym = args.at(0);
+    if !sym.is_symbol() {
+        let msg = format!("{} is not a symbol", sym.to_string(vm)?);
+        return Err(type_error(vm, &msg));
+    }
+    match vm.symbol_key_for(sym.as_symbol().sym()) {
+        Some(key) => Ok(JsValue::new(JsString::new(vm, key))),
+        None => Ok(JsValue::undefined()),
+    }
+}
+
+/// thisSymbolValue ( value )
+fn this_symbol_value(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    method: &str,
+) -> Result<Symbol, JsValue> {
+    let this = args.this;
+    if this.is_symbol() {
+        return Ok(this.as_symbol().sym());
+    }
+    if this.is_object() && this.as_object().tag() == ObjectTag::Symbol {
+        return Ok(this.as_object().as_symbol_object().sym());
+    }
+    Err(type_error(
+        vm,
+        &format!(
+            "Symbol.prototype.{} requires that 'this' be a Symbol",
+            method
+        ),
+    ))
+}
+
+/// 20.4.3.3 Symbol.prototype.toString ( )
+pub fn symbol_to_string(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let sym = this_symbol_value(vm, args, "toString")?;
+    let s = format!("Symbol({})", vm.description(sym));
+    Ok(JsValue::new(JsString::new(vm, s)))
+}
+
+/// 20.4.3.4 Symbol.prototype.valueOf ( )
+pub fn symbol_value_of(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let sym = this_symbol_value(vm, args, "valueOf")?;
+    Ok(JsValue::new(JsSymbol::new(vm, sym)))
+}
+
+/// 20.4.3.2 get Symbol.prototype.description
+pub fn symbol_description(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let sym = this_symbol_value(vm, args, "description")?;
+    match sym.description() {
+        Some(description) => Ok(JsValue::new(JsString::new(vm, description))),
+        None => Ok(JsValue::undefined()),
+    }
+}
+
+/// 20.4.3.5 Symbol.prototype [ @@toPrimitive ] ( hint )
+pub fn symbol_to_primitive(vm: &mut VirtualMachine, args: &Arguments) -> Result<JsValue, JsValue> {
+    let sym = this_symbol_value(vm, args, "[Symbol.toPrimitive]")?;
+    Ok(JsValue::new(JsSymbol::new(vm, sym)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::eval_ok;
+
+    #[test]
+    fn test_symbols_with_same_description_are_distinct() {
+        let result = eval_ok(
+            "
+            var a = Symbol('a');
+            var b = Symbol('a');
+            var o = {};
+            o[a] = 1;
+            o[b] = 2;
+            result = (a !== b) + ' ' + (a === a) + ' ' + o[a] + ' ' + o[b];
+            ",
+        );
+        assert_eq!(result, "true true 1 2");
+    }
+
+    #[test]
+    fn test_symbol_description() {
+        let result = eval_ok(
+            "
+            result = Symbol('a').description + ' ' + Symbol().description + ' '
+                + Symbol('b').toString() + ' ' + Symbol.iterator.toString();
+            ",
+        );
+        assert_eq!(result, "a undefined Symbol(b) Symbol(Symbol.iterator)");
+    }
+
+    #[test]
+    fn test_symbol_for() {
+        let result = eval_ok(
+            "
+            var a = Symbol.for('k');
+            result = (a === Symbol.for('k')) + ' ' + (a !== Symbol('k')) + ' '
+                + Symbol.keyFor(a) + ' ' + Symbol.keyFor(Symbol('k'));
+            ",
+        );
+        assert_eq!(result, "true true k undefined");
+    }
+}
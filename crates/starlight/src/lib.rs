@@ -1,9 +1,6 @@
 #![allow(dead_code, unused_mut, unused_variables)]
 #![cfg_attr(feature = "valgrind-gc", feature(llvm_asm, backtrace))]
 #![allow(
-    clippy::transmute_float_to_int,
-    clippy::transmute_int_to_float,
-    clippy::cast_ref_to_mut,
     clippy::float_cmp,
     clippy::float_arithmetic,
     clippy::identity_op,
@@ -20,7 +17,9 @@
     clippy::upper_case_acronyms,
     clippy::unnecessary_wraps,
     clippy::needless_return,
-    clippy::single_match,
+    clippy::explicit_auto_deref,
+    clippy::type_complexity,
+    clippy::missing_transmute_annotations,
     unknown_lints,
     unused_unsafe
 )]
@@ -34,6 +33,8 @@ pub mod interpreter;
 pub mod jsrt;
 pub mod runtime;
 pub mod symbol_table;
+#[cfg(test)]
+pub(crate) mod testing;
 pub mod utils;
 pub mod vm;
 
@@ -16,6 +16,7 @@ FLAGS:
   -h, --help            Prints help information
 OPTIONS:
     -d,--dump-bytecode  Dump bytecode to stderr
+    --seed <SEED>       Seed Math.random for reproducible runs
 ARGS:
   <INPUT>
 ";
@@ -23,6 +24,7 @@ ARGS:
 #[derive(Debug)]
 struct AppArgs {
     dump_bytecode: bool,
+    random_seed: Option<u32>,
     input: std::path::PathBuf,
 }
 fn main() {
@@ -36,6 +38,7 @@ fn main() {
     };
     let opts = Options {
         dump_bytecode: args.dump_bytecode,
+        random_seed: args.random_seed,
     };
     let file = std::fs::read(args.input);
     let contents = match file {
@@ -56,7 +59,9 @@ fn main() {
             let args = Arguments::new(&mut vm, JsValue::undefined(), 0);
             let mut args = Handle::new(vm.space(), args);
             args.this = JsValue::new(vm.global_object());
-            match func.as_function_mut().call(&mut vm, &mut args) {
+            let result = func.as_function_mut().call(&mut vm, &mut args);
+            // promise jobs run once the script is done.
+            match result.and_then(|_| vm.run_microtasks()) {
                 Ok(_) => {}
                 Err(e) => {
                     eprintln!(
@@ -98,6 +103,7 @@ fn parse_args() -> Result<AppArgs, pico_args::Error> {
     }
     let args = AppArgs {
         dump_bytecode: pargs.contains(["-d", "--dump-bytecode"]),
+        random_seed: pargs.opt_value_from_str("--seed")?,
         input: pargs.free_from_str()?,
     }; // It's up to the caller what to do with the remaining arguments.
     let remaining = pargs.finish();
@@ -6,15 +6,20 @@ pub mod accessor;
 pub mod arguments;
 pub mod array;
 pub mod attributes;
+pub mod boolean;
 pub mod env;
 pub mod error;
+pub mod for_in_iterator;
 pub mod function;
 pub mod gc_array;
+pub mod generator;
 pub mod global;
 pub mod indexed_elements;
+pub mod iterator;
 pub mod js_arguments;
 pub mod number;
 pub mod object;
+pub mod promise;
 pub mod property_descriptor;
 pub mod slot;
 pub mod storage;
@@ -3,7 +3,7 @@ use crate::{
     vm::VirtualMachine,
 };
 
-use super::{gc_array::GcArray, value::JsValue};
+use super::{gc_array::GcArray, structure::Structure, value::JsValue};
 
 pub struct Arguments {
     pub this: JsValue,
@@ -30,6 +30,15 @@ impl Arguments {
             panic!("Out of bounds arguments");
         }
     }
+    /// Structure of the object allocated by `new` for a constructor call. It carries the
+    /// prototype of the constructor invoked by `new`, subclasses included.
+    pub fn ctor_structure(&self) -> Option<Gc<Structure>> {
+        if self.ctor_call && self.this.is_object() {
+            Some(self.this.as_object().structure())
+        } else {
+            None
+        }
+    }
     pub fn at(&self, x: usize) -> JsValue {
         if x < self.size() {
             self.values[x]
@@ -17,6 +17,24 @@ impl JsArray {
         arr.elements.set_length(n);
         arr
     }
+
+    /// Create an array holding `values`, an empty value leaves a hole.
+    pub fn from_slice(vm: &mut VirtualMachine, values: &[JsValue]) -> Gc<JsObject> {
+        let mut arr = Self::new(vm, values.len() as u32);
+        if values.len() <= MAX_VECTOR_SIZE {
+            arr.elements
+                .vector
+                .resize(vm, values.len(), JsValue::empty());
+            arr.elements.vector.as_mut_slice().copy_from_slice(values);
+        } else {
+            for (ix, value) in values.iter().enumerate() {
+                if !value.is_empty() {
+                    let _ = arr.put(vm, Symbol::Indexed(ix as u32), *value, false);
+                }
+            }
+        }
+        arr
+    }
     define_jsclass!(JsArray, Array);
     pub fn GetPropertyNamesMethod(
         obj: Gc<JsObject>,
@@ -0,0 +1,31 @@
+use std::mem::ManuallyDrop;
+
+use crate::{gc::cell::Gc, vm::VirtualMachine};
+
+use super::object::JsObject;
+use super::{method_table::*, object::ObjectTag};
+
+pub struct JsBoolean {
+    value: bool,
+}
+
+impl JsBoolean {
+    define_jsclass_with_symbol!(JsObject, Boolean, Boolean);
+
+    pub fn new(vm: &mut VirtualMachine, value: bool) -> Gc<JsObject> {
+        unsafe {
+            let mut jsobject = JsObject::new(
+                vm,
+                vm.global_data().boolean_structure.unwrap(),
+                Self::get_class(),
+                ObjectTag::Boolean,
+            );
+
+            *jsobject.data::<Self>() = ManuallyDrop::new(Self { value });
+            jsobject
+        }
+    }
+    pub fn value(&self) -> bool {
+        self.value
+    }
+}
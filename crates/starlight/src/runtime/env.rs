@@ -7,6 +7,17 @@ use super::{
 };
 use super::{object::JsObject, symbol::Symbol, value::JsValue};
 use crate::{gc::cell::Gc, vm::VirtualMachine};
+/// ReferenceError thrown on access to `let`, `const` or `class` binding `name` before its
+/// declaration runs.
+pub(crate) fn uninitialized_error(vm: &mut VirtualMachine, name: Symbol) -> JsValue {
+    let desc = vm.description(name);
+    let msg = JsString::new(
+        vm,
+        format!("Cannot access '{}' before initialization", desc),
+    );
+    JsValue::new(JsReferenceError::new(vm, msg, None))
+}
+
 pub struct Env {
     pub record: Gc<JsObject>,
 }
@@ -24,40 +35,37 @@ impl Env {
         val: JsValue,
         strict: bool,
     ) -> Result<(Gc<JsObject>, Slot), JsValue> {
-        if self.record.has_own_property(vm, name) {
-            if !self.is_mutable(vm, name) && strict {
-                let msg = JsString::new(vm, "Assignment to constant variable");
-                return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
-            }
-            let mut slot = Slot::new();
-            self.record.put_slot(vm, name, val, &mut slot, strict)?;
-            return Ok((self.record, slot));
-        } else {
-            let mut current = self.record.prototype();
-            while let Some(mut cur) = current {
-                if cur.has_own_property(vm, name) {
-                    let prop = cur.get_property(vm, name);
-                    if !(prop.is_writable() && prop.raw != NONE) && strict {
-                        let msg = JsString::new(vm, "Assignment to constant variable");
-                        return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
-                    }
-                    let mut slot = Slot::new();
-                    cur.put_slot(vm, name, val, &mut slot, strict)?;
-                    return Ok((cur, slot));
+        let mut current = Some(self.record);
+        while let Some(mut cur) = current {
+            if cur.has_own_property(vm, name) {
+                let prop = cur.get_property(vm, name);
+                let writable = prop.is_writable() && prop.raw != NONE;
+                // Scopes other than the global object hold `let`, `const` and `class` bindings.
+                // Assignments to these throw even in sloppy mode.
+                let declarative = !Gc::ptr_eq(cur, vm.global_object());
+                if declarative && cur.get(vm, name)?.is_empty() {
+                    return Err(uninitialized_error(vm, name));
+                }
+                if !writable && (strict || declarative) {
+                    let msg = JsString::new(vm, "Assignment to constant variable");
+                    return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
                 }
-                current = cur.prototype();
-            }
-
-            if strict {
-                let desc = vm.description(name);
-                let msg = JsString::new(vm, format!("Variable '{}' does not exist", desc));
-                return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
-            } else {
                 let mut slot = Slot::new();
-                vm.global_object()
-                    .put_slot(vm, name, val, &mut slot, false)?;
-                return Ok((vm.global_object(), slot));
+                cur.put_slot(vm, name, val, &mut slot, strict)?;
+                return Ok((cur, slot));
             }
+            current = cur.prototype();
+        }
+
+        if strict {
+            let desc = vm.description(name);
+            let msg = JsString::new(vm, format!("Variable '{}' does not exist", desc));
+            Err(JsValue::new(JsTypeError::new(vm, msg, None)))
+        } else {
+            let mut slot = Slot::new();
+            vm.global_object()
+                .put_slot(vm, name, val, &mut slot, false)?;
+            Ok((vm.global_object(), slot))
         }
     }
     pub fn get_variable(
@@ -108,7 +116,8 @@ impl Env {
     ) -> Result<(), JsValue> {
         let desc = DataDescriptor::new(val, if mutable { W | C | E } else { C | E });
 
-        if self.has_own_variable(vm, name) {
+        // bindings hoisted to the start of their scope are initialized by their declaration.
+        if self.has_own_variable(vm, name) && !self.record.get(vm, name)?.is_empty() {
             let desc = vm.description(name);
             let msg = JsString::new(
                 vm,
@@ -121,3 +130,89 @@ impl Env {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{eval, eval_ok};
+
+    #[test]
+    fn test_assignment_before_top_level_let_is_reference_error() {
+        assert_eq!(
+            eval("x = 1; let x;"),
+            Err("ReferenceError: Cannot access 'x' before initialization".to_string())
+        );
+        assert_eq!(
+            eval("'use strict'; x = 1; let x;"),
+            Err("ReferenceError: Cannot access 'x' before initialization".to_string())
+        );
+        assert_eq!(
+            eval("function f() { x = 1; } f(); let x;"),
+            Err("ReferenceError: Cannot access 'x' before initialization".to_string())
+        );
+    }
+
+    #[test]
+    fn test_assignment_to_top_level_const_is_type_error() {
+        assert_eq!(
+            eval("const c = 1; c = 2;"),
+            Err("TypeError: Assignment to constant variable".to_string())
+        );
+        assert_eq!(
+            eval("const c = 1; function f() { c = 2; } f();"),
+            Err("TypeError: Assignment to constant variable".to_string())
+        );
+    }
+
+    #[test]
+    fn test_assignment_to_block_let_before_declaration_in_loop() {
+        // the first iteration caches the store, the second one stores before `let y` runs.
+        assert_eq!(
+            eval(
+                "for (var i = 0; i < 2; i = i + 1) {
+                    let f = function () { y = i; };
+                    if (i == 1) { f(); }
+                    let y = 0;
+                    f();
+                }"
+            ),
+            Err("ReferenceError: Cannot access 'y' before initialization".to_string())
+        );
+    }
+
+    #[test]
+    fn test_assignment_to_initialized_bindings() {
+        assert_eq!(
+            eval_ok("let x = 1; x = 2; var v = 1; v = 3; g = 4; result = x + v + g;"),
+            "9"
+        );
+    }
+
+    #[test]
+    fn test_declare_bindings_holding_cells() {
+        assert_eq!(
+            eval_ok("let s = 'a'; const o = {}; class K {} result = s + typeof o + typeof K;"),
+            "aobjectfunction"
+        );
+    }
+
+    #[test]
+    fn test_unused_read_before_let_is_reference_error() {
+        assert_eq!(
+            eval("q; let q = 1;"),
+            Err("ReferenceError: Cannot access 'q' before initialization".to_string())
+        );
+        assert_eq!(
+            eval_ok(
+                "{
+                    try { u; } catch (e) { result = e.name; }
+                    let u;
+                }"
+            ),
+            "ReferenceError"
+        );
+        assert_eq!(
+            eval_ok("undefined; NaN; Infinity; let w = 1; w; result = w;"),
+            "1"
+        );
+    }
+}
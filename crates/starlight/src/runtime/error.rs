@@ -13,6 +13,7 @@ pub struct JsReferenceError;
 pub struct JsSyntaxError;
 pub struct JsTypeError;
 pub struct JsURIError;
+pub struct JsAggregateError;
 impl JsError {
     pub fn new(
         vm: &mut VirtualMachine,
@@ -26,7 +27,7 @@ impl JsError {
             ObjectTag::Ordinary,
         );
 
-        if !s.as_str().is_empty() {
+        if !s.is_empty() {
             let _ = obj.define_own_property(
                 vm,
                 Symbol::message(),
@@ -53,7 +54,7 @@ impl JsEvalError {
             ObjectTag::Ordinary,
         );
 
-        if !s.as_str().is_empty() {
+        if !s.is_empty() {
             let _ = obj.define_own_property(
                 vm,
                 Symbol::message(),
@@ -80,7 +81,7 @@ impl JsRangeError {
             ObjectTag::Ordinary,
         );
 
-        if !s.as_str().is_empty() {
+        if !s.is_empty() {
             let _ = obj.define_own_property(
                 vm,
                 Symbol::message(),
@@ -107,7 +108,7 @@ impl JsReferenceError {
             ObjectTag::Ordinary,
         );
 
-        if !s.as_str().is_empty() {
+        if !s.is_empty() {
             let _ = obj.define_own_property(
                 vm,
                 Symbol::message(),
@@ -134,7 +135,7 @@ impl JsSyntaxError {
             ObjectTag::Ordinary,
         );
 
-        if !s.as_str().is_empty() {
+        if !s.is_empty() {
             let _ = obj.define_own_property(
                 vm,
                 Symbol::message(),
@@ -161,7 +162,7 @@ impl JsTypeError {
             ObjectTag::Ordinary,
         );
 
-        if !s.as_str().is_empty() {
+        if !s.is_empty() {
             let _ = obj.define_own_property(
                 vm,
                 Symbol::message(),
@@ -174,3 +175,34 @@ impl JsTypeError {
     }
     define_jsclass_with_symbol!(JsObject, Error, TypeError);
 }
+
+impl JsAggregateError {
+    /// Creates an AggregateError whose `errors` property holds `errors`.
+    pub fn new(
+        vm: &mut VirtualMachine,
+        s: Gc<JsString>,
+        errors: JsValue,
+        structure: Option<Gc<Structure>>,
+    ) -> Gc<JsObject> {
+        let mut obj = JsObject::new(
+            vm,
+            structure.unwrap_or_else(|| vm.global_data().aggregate_error_structure.unwrap()),
+            Self::get_class(),
+            ObjectTag::Ordinary,
+        );
+
+        if !s.is_empty() {
+            let _ = obj.define_own_property(
+                vm,
+                Symbol::message(),
+                &*DataDescriptor::new(JsValue::new(s), W | C),
+                false,
+            );
+        }
+        let name = vm.intern("errors");
+        let _ = obj.define_own_property(vm, name, &*DataDescriptor::new(errors, W | C), false);
+
+        obj
+    }
+    define_jsclass_with_symbol!(JsObject, Error, Error);
+}
@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+
+use super::{
+    object::{EnumerationMode, JsObject},
+    slot::Slot,
+    symbol::Symbol,
+};
+use crate::{
+    gc::cell::{Cell, Gc, Trace, Tracer},
+    vm::VirtualMachine,
+};
+
+/// State of a `for-in` loop, stored on the operand stack as the `OP_NEXT_PROP` handle.
+///
+/// Keys are collected once when the loop starts: own keys of the object first, then keys
+/// of its prototypes that are not shadowed. Keys deleted while the loop runs are skipped,
+/// keys added while the loop runs are not visited.
+pub struct ForInIterator {
+    object: Gc<JsObject>,
+    keys: Vec<Symbol>,
+    index: usize,
+}
+
+impl ForInIterator {
+    pub fn new(vm: &mut VirtualMachine, object: Gc<JsObject>) -> Gc<Self> {
+        let object = object.root(vm.space());
+        let mut keys = vec![];
+        let mut visited = HashSet::new();
+        let mut current = Some(*object);
+        while let Some(obj) = current {
+            for key in obj.get_own_property_keys(vm, EnumerationMode::IncludeNotEnumerable) {
+                // for-in only visits string keys.
+                if key.is_unique() {
+                    continue;
+                }
+                // non-enumerable properties still shadow enumerable ones down the chain.
+                if !visited.insert(key) {
+                    continue;
+                }
+                let mut slot = Slot::new();
+                if obj.get_own_property_slot(vm, key, &mut slot)
+                    && slot.attributes().is_enumerable()
+                {
+                    keys.push(key);
+                }
+            }
+            current = obj.prototype();
+        }
+        let this = Self {
+            object: *object,
+            keys,
+            index: 0,
+        };
+        vm.space().alloc(this)
+    }
+
+    /// Return next key that is still present on the object or `None` when iteration is done.
+    pub fn next(&mut self, vm: &mut VirtualMachine) -> Option<Symbol> {
+        while self.index < self.keys.len() {
+            let key = self.keys[self.index];
+            self.index += 1;
+            if self.object.has_property(vm, key) {
+                return Some(key);
+            }
+        }
+        None
+    }
+}
+
+impl Cell for ForInIterator {}
+
+unsafe impl Trace for ForInIterator {
+    fn trace(&self, tracer: &mut dyn Tracer) {
+        self.object.trace(tracer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::eval_ok;
+
+    const KEYS: &str = "
+        function keys(o) {
+            var s = '';
+            for (var k in o) {
+                s = s + k + ':' + o[k] + ' ';
+            }
+            return s;
+        }
+    ";
+
+    #[test]
+    fn test_for_in_indices_first_then_insertion_order() {
+        let result = eval_ok(&format!(
+            "{} var o = {{ b: 1, a: 2 }}; o[2] = 3; o[1] = 4; o.c = 5; result = keys(o);",
+            KEYS
+        ));
+        assert_eq!(result, "1:4 2:3 b:1 a:2 c:5 ");
+    }
+
+    #[test]
+    fn test_for_in_walks_prototype_chain() {
+        let result = eval_ok(&format!(
+            "{}
+            var proto = {{ x: 1, y: 2, z: 3 }};
+            var o = Object.create(proto);
+            o.y = 4;
+            Object.defineProperty(o, 'z', {{ value: 5, enumerable: false }});
+            o.w = 6;
+            result = keys(o);",
+            KEYS
+        ));
+        assert_eq!(result, "y:4 w:6 x:1 ");
+    }
+}
@@ -17,6 +17,7 @@ pub struct JsFunction {
 
 pub enum FuncType {
     Native(JsNativeFunction),
+    Closure(JsClosureFunction),
     User(JsVMFunction),
     Bound(JsBoundFunction),
 }
@@ -24,18 +25,14 @@ pub enum FuncType {
 impl JsFunction {
     pub fn is_strict(&self) -> bool {
         match self.ty {
-            FuncType::Native(_) => false,
+            FuncType::Native(_) | FuncType::Closure(_) => false,
             FuncType::User(ref x) => x.code.strict,
             FuncType::Bound(ref x) => x.target.as_function().is_strict(),
         }
     }
 
     pub fn is_bound(&self) -> bool {
-        if let FuncType::Bound(_) = self.ty {
-            true
-        } else {
-            false
-        }
+        matches!(self.ty, FuncType::Bound(_))
     }
     pub fn as_native(&self) -> &JsNativeFunction {
         match self.ty {
@@ -77,7 +74,7 @@ impl JsFunction {
         }
     }
 
-    pub fn construct<'a>(
+    pub fn construct(
         &mut self,
         vm: &mut VirtualMachine,
 
@@ -87,14 +84,26 @@ impl JsFunction {
         let structure = structure
             .unwrap_or_else(|| Structure::new_unique_indexed(vm, None, false))
             .root(vm.space());
+        args.ctor_call = true;
+        if let FuncType::User(ref x) = self.ty {
+            if x.code.generator || x.code.is_async || x.code.is_arrow || x.code.is_method {
+                let desc = vm.description(x.code.name);
+                let msg = JsString::new(vm, format!("{} is not a constructor", desc));
+                return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
+            }
+            if x.code.derived {
+                // `this` is created by the parent constructor called from `super(...)`
+                args.this = JsValue::empty();
+                return vm.perform_vm_call(x, JsValue::new(x.scope), args, Some(*structure));
+            }
+        }
         let obj = JsObject::new(vm, *structure, JsObject::get_class(), ObjectTag::Ordinary)
             .root(vm.space());
-        args.ctor_call = true;
         args.this = JsValue::new(*obj);
         self.call(vm, args)
     }
 
-    pub fn call<'a>(
+    pub fn call(
         &mut self,
         vm: &mut VirtualMachine,
 
@@ -102,7 +111,28 @@ impl JsFunction {
     ) -> Result<JsValue, JsValue> {
         match self.ty {
             FuncType::Native(ref x) => (x.func)(vm, args),
-            FuncType::User(ref x) => return vm.perform_vm_call(x, JsValue::new(x.scope), args),
+            FuncType::Closure(ref x) => (x.func)(vm, args, x.data),
+            FuncType::User(ref x) => {
+                if x.code.class_constructor && !args.ctor_call {
+                    let desc = vm.description(x.code.name);
+                    let msg = JsString::new(
+                        vm,
+                        format!("Class constructor {} cannot be invoked without 'new'", desc),
+                    );
+                    return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
+                }
+                if x.code.generator {
+                    let structure = match self.construct_struct {
+                        Some(structure) => structure,
+                        None => vm.global_data().generator_structure.unwrap(),
+                    };
+                    return vm.create_generator(x, JsValue::new(x.scope), args, structure);
+                }
+                if x.code.is_async {
+                    return vm.call_async(x, JsValue::new(x.scope), args);
+                }
+                vm.perform_vm_call(x, JsValue::new(x.scope), args, None)
+            }
             FuncType::Bound(ref x) => {
                 let mut args = Arguments {
                     values: x.args,
@@ -296,10 +326,39 @@ impl JsFunction {
             // prototype override
             function.construct_struct = None;
             slot.make_uncacheable();
+            if let FuncType::User(ref x) = function.ty {
+                if x.code.generator {
+                    return Self::define_generator_prototype(obj, vm, desc, slot, throwable);
+                }
+            }
         }
         JsObject::DefineOwnNonIndexedPropertySlotMethod(obj, vm, name, desc, slot, throwable)
     }
 
+    /// Define `prototype` of a generator function. Generator objects inherit from its value,
+    /// or from %GeneratorPrototype% if it is not an object, so their structure is cached
+    /// in `construct_struct`.
+    fn define_generator_prototype(
+        mut obj: Gc<JsObject>,
+        vm: &mut VirtualMachine,
+        desc: &PropertyDescriptor,
+        slot: &mut Slot,
+        throwable: bool,
+    ) -> Result<bool, JsValue> {
+        let name = Symbol::prototype();
+        let result =
+            JsObject::DefineOwnNonIndexedPropertySlotMethod(obj, vm, name, desc, slot, throwable)?;
+        let proto = obj.get(vm, name)?;
+        let proto = if proto.is_object() {
+            proto.as_object()
+        } else {
+            vm.global_data().generator_prototype.unwrap()
+        };
+        let structure = Structure::new_indexed(vm, Some(proto), false);
+        obj.as_function_mut().construct_struct = Some(structure);
+        Ok(result)
+    }
+
     pub fn GetIndexedPropertySlotMethod(
         obj: Gc<JsObject>,
         vm: &mut VirtualMachine,
@@ -310,6 +369,12 @@ impl JsFunction {
     }
 }
 pub type JsAPI = fn(vm: &mut VirtualMachine, arguments: &Arguments) -> Result<JsValue, JsValue>;
+/// Native function receiving values captured when it was created.
+pub type JsClosureAPI = fn(
+    vm: &mut VirtualMachine,
+    arguments: &Arguments,
+    data: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue>;
 #[derive(Clone, Copy)]
 #[allow(dead_code)]
 pub struct JsNativeFunction {
@@ -318,7 +383,6 @@ pub struct JsNativeFunction {
 
 impl JsNativeFunction {
     pub fn new(vm: &mut VirtualMachine, name: Symbol, f: JsAPI, n: u32) -> Gc<JsObject> {
-        let vm = vm;
         let mut func = JsFunction::new(vm, FuncType::Native(JsNativeFunction { func: f }), false)
             .root(vm.space());
         let l = Symbol::length();
@@ -344,7 +408,6 @@ impl JsNativeFunction {
         f: JsAPI,
         n: u32,
     ) -> Gc<JsObject> {
-        let vm = vm;
         let mut func = JsFunction::new_with_struct(
             vm,
             s,
@@ -369,6 +432,45 @@ impl JsNativeFunction {
     }
 }
 
+/// Native function with GC values it captured, used for functions that spec creates from
+/// abstract closures, e.g. promise resolving functions.
+#[derive(Clone, Copy)]
+pub struct JsClosureFunction {
+    func: JsClosureAPI,
+    pub(crate) data: Gc<GcArray<JsValue>>,
+}
+
+impl JsClosureFunction {
+    pub fn new(
+        vm: &mut VirtualMachine,
+        name: Symbol,
+        f: JsClosureAPI,
+        data: Gc<GcArray<JsValue>>,
+        n: u32,
+    ) -> Gc<JsObject> {
+        let mut func = JsFunction::new(
+            vm,
+            FuncType::Closure(JsClosureFunction { func: f, data }),
+            false,
+        )
+        .root(vm.space());
+        let l = Symbol::length();
+
+        let _ = func.define_own_property(
+            vm,
+            l,
+            &*DataDescriptor::new(JsValue::new(n as i32), NONE),
+            false,
+        );
+        let n = Symbol::name();
+        let k = vm.description(name);
+        let name = JsValue::new(JsString::new(vm, &k));
+        let _ = func.define_own_property(vm, n, &*DataDescriptor::new(name, NONE), false);
+
+        *func
+    }
+}
+
 unsafe impl Trace for JsFunction {
     fn trace(&self, tracer: &mut dyn Tracer) {
         self.construct_struct.trace(tracer);
@@ -377,6 +479,7 @@ unsafe impl Trace for JsFunction {
                 x.code.trace(tracer);
                 x.scope.trace(tracer);
             }
+            FuncType::Closure(ref x) => x.data.trace(tracer),
             _ => (),
         }
     }
@@ -401,22 +504,35 @@ impl JsVMFunction {
         let mut this = JsFunction::new(vm, FuncType::User(f), false)
             .root(vm.space())
             .root(vm.space());
-        let mut proto = JsObject::new_empty(vm).root(vm.space()).root(vm.space());
+        let mut proto = if code.generator {
+            // generator objects inherit from it, it has no `constructor` property.
+            let structure = Structure::new_indexed(vm, vm.global_data().generator_prototype, false);
+            JsObject::new(vm, structure, JsObject::get_class(), ObjectTag::Ordinary)
+                .root(vm.space())
+        } else {
+            JsObject::new_empty(vm).root(vm.space()).root(vm.space())
+        };
 
-        let _ = proto.define_own_property(
-            vm,
-            Symbol::constructor(),
-            &*DataDescriptor::new(JsValue::new(*this), W | C),
-            false,
-        );
+        if !code.generator {
+            let _ = proto.define_own_property(
+                vm,
+                Symbol::constructor(),
+                &*DataDescriptor::new(JsValue::new(*this), W | C),
+                false,
+            );
+        }
         let desc = vm.description(code.name);
         let s = JsString::new(vm, desc).root(vm.space());
-        let _ = this.define_own_property(
-            vm,
-            Symbol::prototype(),
-            &*DataDescriptor::new(JsValue::new(*proto), W),
-            false,
-        );
+        // async functions, arrows and methods are not constructors and have no `prototype`.
+        // Generator methods keep it for the generator objects they create.
+        if !code.is_async && !code.is_arrow && (!code.is_method || code.generator) {
+            let _ = this.define_own_property(
+                vm,
+                Symbol::prototype(),
+                &*DataDescriptor::new(JsValue::new(*proto), W),
+                false,
+            );
+        }
         let _ = this.define_own_property(
             vm,
             Symbol::name(),
@@ -435,7 +551,6 @@ impl Gc<JsObject> {
         assert_eq!(self.tag(), ObjectTag::Function);
         let func = self.as_function_mut();
 
-        let vm = vm;
         if let Some(s) = func.construct_struct {
             return Ok(s);
         }
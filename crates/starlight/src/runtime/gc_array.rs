@@ -10,7 +10,6 @@ fn clp2(number: usize) -> usize {
 use std::{
     mem::{size_of, MaybeUninit},
     ops::{Index, IndexMut},
-    usize,
 };
 
 use crate::{
@@ -83,7 +82,7 @@ impl<T: Cell> GcArray<T> {
         let mut cell = vm.space().alloc(val);
         for i in 0..cell.len() {
             unsafe {
-                cell.begin().add(i as usize).write(init.clone());
+                cell.begin().add(i).write(init.clone());
             }
         }
         cell
@@ -114,7 +113,7 @@ unsafe impl<T: Cell> Trace for GcArray<T> {
 
 impl<T: Cell> Cell for GcArray<T> {
     fn compute_size(&self) -> usize {
-        (self.length as usize * size_of::<T>()) + size_of::<Self>()
+        (self.length * size_of::<T>()) + size_of::<Self>()
         //size_of::<Self>()
     }
 }
@@ -236,6 +235,26 @@ impl<T: Cell> GcVec<T> {
     pub fn pop(&mut self) -> Option<T> {
         self.data.pop()
     }
+
+    /// Replace the elements in `range` with `replace_with`.
+    pub fn splice(
+        &mut self,
+        _vm: &mut VirtualMachine,
+        range: std::ops::Range<usize>,
+        replace_with: &[T],
+    ) where
+        T: Clone,
+    {
+        self.data.splice(range, replace_with.iter().cloned());
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
 }
 
 impl<T: Cell> Index<usize> for GcVec<T> {
@@ -0,0 +1,108 @@
+use std::mem::ManuallyDrop;
+
+use super::{
+    method_table::*,
+    object::{JsObject, ObjectTag},
+    structure::Structure,
+    value::JsValue,
+};
+use crate::{
+    bytecode::ByteCode,
+    gc::cell::{Gc, Trace, Tracer},
+    vm::VirtualMachine,
+};
+
+/// 27.5 Generator state.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GeneratorState {
+    SuspendedStart,
+    SuspendedYield,
+    Executing,
+    Completed,
+}
+
+/// How a generator is resumed, by `next`, `throw` or `return` method.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResumeMode {
+    Next,
+    Throw,
+    Return,
+}
+
+/// Generator instance data. Holds the interpreter frame of the generator function while
+/// it is suspended.
+pub struct JsGenerator {
+    pub(crate) state: GeneratorState,
+    pub(crate) code: Gc<ByteCode>,
+    pub(crate) scope: JsValue,
+    pub(crate) this: JsValue,
+    /// Address of the instruction the frame resumes at.
+    pub(crate) pc: *mut u8,
+    /// Operand stack of the suspended frame.
+    pub(crate) stack: Vec<JsValue>,
+    /// `try` handlers of the suspended frame. Operand stack pointers are stored as offsets
+    /// from the bottom of the frame's operand stack.
+    pub(crate) try_stack: Vec<(Gc<JsObject>, *const u8, usize, bool)>,
+    /// Iterator and its `next` method `yield*` is delegating to.
+    pub(crate) delegate: Option<(JsValue, JsValue)>,
+    /// Value passed to `return` method while `finally` blocks of the frame run.
+    pub(crate) return_value: JsValue,
+}
+
+impl JsGenerator {
+    define_jsclass_with_symbol!(JsObject, Generator, Iterator);
+
+    pub fn new(
+        vm: &mut VirtualMachine,
+        structure: Gc<Structure>,
+        code: Gc<ByteCode>,
+        scope: JsValue,
+        this: JsValue,
+    ) -> Gc<JsObject> {
+        unsafe {
+            let mut jsobject =
+                JsObject::new(vm, structure, Self::get_class(), ObjectTag::Generator);
+
+            *jsobject.data::<Self>() = ManuallyDrop::new(Self {
+                state: GeneratorState::SuspendedStart,
+                code,
+                scope,
+                this,
+                pc: code.code_start,
+                stack: vec![],
+                try_stack: vec![],
+                delegate: None,
+                return_value: JsValue::undefined(),
+            });
+            jsobject
+        }
+    }
+
+    /// Drop the saved frame once the generator is completed.
+    pub(crate) fn complete(&mut self) {
+        self.state = GeneratorState::Completed;
+        self.scope = JsValue::undefined();
+        self.this = JsValue::undefined();
+        self.stack.clear();
+        self.try_stack.clear();
+        self.delegate = None;
+        self.return_value = JsValue::undefined();
+    }
+}
+
+unsafe impl Trace for JsGenerator {
+    fn trace(&self, tracer: &mut dyn Tracer) {
+        self.code.trace(tracer);
+        self.scope.trace(tracer);
+        self.this.trace(tracer);
+        self.stack.iter().for_each(|val| val.trace(tracer));
+        self.try_stack
+            .iter()
+            .for_each(|(scope, _, _, _)| scope.trace(tracer));
+        self.return_value.trace(tracer);
+        if let Some((iterator, next)) = self.delegate {
+            iterator.trace(tracer);
+            next.trace(tracer);
+        }
+    }
+}
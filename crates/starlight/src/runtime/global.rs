@@ -17,7 +17,8 @@ pub struct JsGlobal {
 #[allow(non_snake_case)]
 impl JsGlobal {
     pub fn new(vm: &mut VirtualMachine) -> Gc<JsObject> {
-        let shape = Structure::new_unique_with_proto(vm, None, false);
+        let proto = vm.global_data().get_object_prototype();
+        let shape = Structure::new_unique_with_proto(vm, Some(proto), false);
         let mut js_object = JsObject::new(vm, shape, Self::get_class(), ObjectTag::Global);
         unsafe {
             *js_object.data::<JsGlobal>() = ManuallyDrop::new(Self {
@@ -32,7 +33,7 @@ impl JsGlobal {
     pub fn lookup_constant(&self, name: Symbol) -> Option<JsValue> {
         let _vm = self.vm;
         if name == Symbol::Infinity() {
-            Some(JsValue::new(std::f64::INFINITY))
+            Some(JsValue::new(f64::INFINITY))
         } else if name == Symbol::NaN() {
             Some(JsValue::new(pure_nan::pure_nan()))
         } else if name == Symbol::undefined() {
@@ -0,0 +1,190 @@
+use std::mem::ManuallyDrop;
+
+use super::{
+    arguments::Arguments,
+    error::JsTypeError,
+    method_table::*,
+    object::{JsObject, ObjectTag},
+    slot::Slot,
+    string::JsString,
+    symbol::Symbol,
+    value::JsValue,
+};
+use crate::{
+    gc::{
+        cell::{Gc, Trace, Tracer},
+        handle::Handle,
+    },
+    vm::VirtualMachine,
+};
+
+/// Kind of values produced by array iterator.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IterationKind {
+    Key,
+    Value,
+    KeyValue,
+}
+
+/// %ArrayIterator% instance data.
+pub struct JsArrayIterator {
+    /// Iterated object, set to `None` once iterator is exhausted.
+    pub(crate) iterated: Option<Gc<JsObject>>,
+    pub(crate) index: u32,
+    pub(crate) kind: IterationKind,
+}
+
+impl JsArrayIterator {
+    define_jsclass_with_symbol!(JsObject, ArrayIterator, Iterator);
+
+    pub fn new(
+        vm: &mut VirtualMachine,
+        iterated: Gc<JsObject>,
+        kind: IterationKind,
+    ) -> Gc<JsObject> {
+        unsafe {
+            let mut jsobject = JsObject::new(
+                vm,
+                vm.global_data().array_iterator_structure.unwrap(),
+                Self::get_class(),
+                ObjectTag::ArrayIterator,
+            );
+
+            *jsobject.data::<Self>() = ManuallyDrop::new(Self {
+                iterated: Some(iterated),
+                index: 0,
+                kind,
+            });
+            jsobject
+        }
+    }
+}
+
+unsafe impl Trace for JsArrayIterator {
+    fn trace(&self, tracer: &mut dyn Tracer) {
+        self.iterated.trace(tracer);
+    }
+}
+
+/// %StringIterator% instance data.
+pub struct JsStringIterator {
+    /// Iterated string, set to `None` once iterator is exhausted.
+    pub(crate) string: Option<Gc<JsString>>,
+    pub(crate) position: u32,
+}
+
+impl JsStringIterator {
+    define_jsclass_with_symbol!(JsObject, StringIterator, Iterator);
+
+    pub fn new(vm: &mut VirtualMachine, string: Gc<JsString>) -> Gc<JsObject> {
+        unsafe {
+            let mut jsobject = JsObject::new(
+                vm,
+                vm.global_data().string_iterator_structure.unwrap(),
+                Self::get_class(),
+                ObjectTag::StringIterator,
+            );
+
+            *jsobject.data::<Self>() = ManuallyDrop::new(Self {
+                string: Some(string),
+                position: 0,
+            });
+            jsobject
+        }
+    }
+}
+
+unsafe impl Trace for JsStringIterator {
+    fn trace(&self, tracer: &mut dyn Tracer) {
+        self.string.trace(tracer);
+    }
+}
+
+fn call(vm: &mut VirtualMachine, func: JsValue, this: JsValue) -> Result<JsValue, JsValue> {
+    let args = Arguments::new(vm, this, 0);
+    let mut args = Handle::new(vm.space(), args);
+    func.as_object().as_function_mut().call(vm, &mut args)
+}
+
+fn type_error(vm: &mut VirtualMachine, msg: &str) -> JsValue {
+    let msg = JsString::new(vm, msg).root(vm.space());
+    JsValue::new(JsTypeError::new(vm, *msg, None))
+}
+
+/// 7.4.14 CreateIterResultObject
+pub fn create_iter_result_object(
+    vm: &mut VirtualMachine,
+    value: JsValue,
+    done: bool,
+) -> Gc<JsObject> {
+    let mut obj = JsObject::new_empty(vm);
+    let _ = obj.put(vm, Symbol::value(), value, false);
+    let _ = obj.put(vm, Symbol::done(), JsValue::new(done), false);
+    obj
+}
+
+/// 7.4.1 GetIterator
+///
+/// Returns the iterator object and its `next` method.
+pub fn get_iterator(vm: &mut VirtualMachine, obj: JsValue) -> Result<(JsValue, JsValue), JsValue> {
+    let method = if obj.is_object() || obj.is_string() {
+        obj.get_slot(vm, Symbol::iterator(), &mut Slot::new())?
+    } else {
+        JsValue::undefined()
+    };
+    if !method.is_callable() {
+        let msg = format!("{} is not iterable", obj.type_of());
+        return Err(type_error(vm, &msg));
+    }
+    let iterator = call(vm, method, obj)?;
+    if !iterator.is_object() {
+        return Err(type_error(
+            vm,
+            "Result of the Symbol.iterator method is not an object",
+        ));
+    }
+    let iterator = iterator.as_object().root(vm.space());
+    let next = iterator.get(vm, Symbol::next())?;
+    Ok((JsValue::new(*iterator), next))
+}
+
+/// 7.4.6 IteratorStep followed by 7.4.4 IteratorValue
+///
+/// Returns `None` once the iterator is done.
+pub fn iterator_step(
+    vm: &mut VirtualMachine,
+    iterator: JsValue,
+    next: JsValue,
+) -> Result<Option<JsValue>, JsValue> {
+    if !next.is_callable() {
+        return Err(type_error(vm, "iterator.next is not a function"));
+    }
+    let result = call(vm, next, iterator)?;
+    if !result.is_object() {
+        return Err(type_error(vm, "Iterator result is not an object"));
+    }
+    let result = result.as_object().root(vm.space());
+    if result.get(vm, Symbol::done())?.to_boolean() {
+        return Ok(None);
+    }
+    result.get(vm, Symbol::value()).map(Some)
+}
+
+/// 7.4.8 IteratorClose
+///
+/// Calls `return` method of the iterator if it has one.
+pub fn iterator_close(vm: &mut VirtualMachine, iterator: JsValue) -> Result<(), JsValue> {
+    let name = vm.intern("return");
+    let method = iterator.as_object().get(vm, name)?;
+    if method.is_undefined_or_null() {
+        return Ok(());
+    }
+    if !method.is_callable() {
+        return Err(type_error(vm, "iterator.return is not a function"));
+    }
+    let result = call(vm, method, iterator)?;
+    if !result.is_object() {
+        return Err(type_error(vm, "Iterator result is not an object"));
+    }
+    Ok(())
+}
@@ -161,17 +161,16 @@ impl JsArguments {
         let v = JsObject::GetNonIndexedSlotMethod(obj, vm, name, slot);
         if name == Symbol::caller() {
             match v {
-                Ok(x) if x.is_callable() => {
-                    if x.as_cell()
-                        .downcast::<JsObject>()
-                        .unwrap()
-                        .as_function()
-                        .is_strict()
-                    {
-                        let msg =
-                            JsString::new(vm, "access to strict function 'caller' not allowed");
-                        return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
-                    }
+                Ok(x)
+                    if x.is_callable()
+                        && x.as_cell()
+                            .downcast::<JsObject>()
+                            .unwrap()
+                            .as_function()
+                            .is_strict() =>
+                {
+                    let msg = JsString::new(vm, "access to strict function 'caller' not allowed");
+                    return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
                 }
                 _ => (),
             }
@@ -276,6 +275,13 @@ impl JsArguments {
             //let _ = obj.put(vm, Symbol::Indexed(i as _), JsValue::undefined(), false);
         }
         let _ = obj.put(vm, Symbol::length(), JsValue::new(len as i32), false);
+        let values = vm.global_data().array_values.unwrap();
+        let _ = obj.define_own_property(
+            vm,
+            Symbol::iterator(),
+            &*DataDescriptor::new(JsValue::new(values), W | C),
+            false,
+        );
         obj.as_arguments_mut().mapping = mapping.into_boxed_slice();
         *obj
     }
@@ -1,8 +1,10 @@
 use std::mem::ManuallyDrop;
 
 use crate::{gc::cell::Gc, vm::VirtualMachine};
+use wtf_rs::pure_nan::pure_nan;
 
 use super::object::JsObject;
+use super::string::is_js_whitespace;
 use super::{method_table::*, object::ObjectTag};
 
 pub struct JsNumber {
@@ -29,3 +31,222 @@ impl JsNumber {
         self.value
     }
 }
+
+/// Decimal digits of a finite, positive `x` together with the exponent `n` such that
+/// `x = 0.d1d2...dk * 10^n`.
+///
+/// With `precision` set to `None` the digits are the shortest ones that read back as `x`,
+/// otherwise they are the exact value of `x` rounded half up to `precision` significant digits.
+pub fn decimal_digits(x: f64, precision: Option<usize>) -> (Vec<u8>, i32) {
+    debug_assert!(x.is_finite() && x > 0.0);
+    // `{:e}` prints the shortest round-trip digits, with a precision it prints exact digits;
+    // 1100 covers every digit a double can have.
+    let formatted = match precision {
+        None => format!("{:e}", x),
+        Some(_) => format!("{:.1100e}", x),
+    };
+    let e = formatted.find('e').unwrap();
+    let mut digits = formatted[..e]
+        .bytes()
+        .filter(|c| *c != b'.')
+        .collect::<Vec<u8>>();
+    let mut n = formatted[e + 1..].parse::<i32>().unwrap() + 1;
+    if let Some(precision) = precision {
+        let round_up = digits.len() > precision && digits[precision] >= b'5';
+        digits.truncate(precision);
+        if round_up {
+            let mut ix = precision;
+            loop {
+                if ix == 0 {
+                    digits.insert(0, b'1');
+                    digits.truncate(precision);
+                    n += 1;
+                    break;
+                }
+                ix -= 1;
+                if digits[ix] == b'9' {
+                    digits[ix] = b'0';
+                } else {
+                    digits[ix] += 1;
+                    break;
+                }
+            }
+        }
+        digits.resize(precision, b'0');
+    } else {
+        while digits.len() > 1 && digits.last() == Some(&b'0') {
+            digits.pop();
+        }
+    }
+    (digits, n)
+}
+
+/// 6.1.6.1.20 Number::toString ( x )
+pub fn format_number(x: f64) -> String {
+    if x.is_nan() {
+        return "NaN".to_owned();
+    }
+    if x == 0.0 {
+        return "0".to_owned();
+    }
+    if x < 0.0 {
+        return format!("-{}", format_number(-x));
+    }
+    if x.is_infinite() {
+        return "Infinity".to_owned();
+    }
+    let (digits, n) = decimal_digits(x, None);
+    let k = digits.len() as i32;
+    let digits = String::from_utf8(digits).unwrap();
+    if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat(-n as usize), digits)
+    } else {
+        let sign = if n - 1 < 0 { '-' } else { '+' };
+        if k == 1 {
+            format!("{}e{}{}", digits, sign, (n - 1).abs())
+        } else {
+            format!(
+                "{}.{}e{}{}",
+                &digits[..1],
+                &digits[1..],
+                sign,
+                (n - 1).abs()
+            )
+        }
+    }
+}
+
+/// 7.1.4.1.1 StringToNumber ( str )
+pub fn string_to_number(units: &[u16]) -> f64 {
+    let start = units
+        .iter()
+        .position(|unit| !is_js_whitespace(*unit))
+        .unwrap_or(units.len());
+    let end = units
+        .iter()
+        .rposition(|unit| !is_js_whitespace(*unit))
+        .map_or(start, |end| end + 1);
+    let units = &units[start..end];
+    if units.is_empty() {
+        return 0.0;
+    }
+    // a StringNumericLiteral is ASCII only
+    if units.iter().any(|unit| *unit >= 0x80) {
+        return pure_nan();
+    }
+    let s = units
+        .iter()
+        .map(|unit| *unit as u8 as char)
+        .collect::<String>();
+    let bytes = s.as_bytes();
+    let radix = match bytes {
+        [b'0', b'x', ..] | [b'0', b'X', ..] => 16,
+        [b'0', b'o', ..] | [b'0', b'O', ..] => 8,
+        [b'0', b'b', ..] | [b'0', b'B', ..] => 2,
+        _ => 10,
+    };
+    if radix != 10 {
+        let digits = &s[2..];
+        if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+            return pure_nan();
+        }
+        return digits.chars().fold(0.0, |n, c| {
+            n * radix as f64 + c.to_digit(radix).unwrap() as f64
+        });
+    }
+    let unsigned = s.strip_prefix(|c| c == '+' || c == '-').unwrap_or(&s);
+    if unsigned == "Infinity" {
+        return if bytes[0] == b'-' {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        };
+    }
+    // `f64::from_str` also takes `inf` and `nan`, only digits, `.` and an exponent are valid.
+    let mantissa = unsigned.split(['e', 'E']).next().unwrap_or("");
+    let valid = mantissa.chars().any(|c| c.is_ascii_digit())
+        && unsigned
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'));
+    if !valid {
+        return pure_nan();
+    }
+    s.parse::<f64>().unwrap_or_else(|_| pure_nan())
+}
+
+/// Number::toString ( x, radix ) for a radix other than 10. The fraction gets as many digits
+/// as are needed to tell `x` apart from its neighbouring doubles.
+pub fn format_number_radix(x: f64, radix: u32) -> String {
+    if x.is_nan() {
+        return "NaN".to_owned();
+    }
+    if x == 0.0 {
+        return "0".to_owned();
+    }
+    if x < 0.0 {
+        return format!("-{}", format_number_radix(-x, radix));
+    }
+    if x.is_infinite() {
+        return "Infinity".to_owned();
+    }
+    let radix_f = radix as f64;
+    let mut integer = x.floor();
+    let mut fraction = x - integer;
+    // half the distance to the next double, the precision the digits must reach.
+    let mut delta = (0.5 * (f64::from_bits(x.to_bits() + 1) - x)).max(f64::from_bits(1));
+    let mut fraction_digits = vec![];
+    if fraction >= delta {
+        loop {
+            fraction *= radix_f;
+            delta *= radix_f;
+            let digit = fraction as u32;
+            fraction_digits.push(digit);
+            fraction -= digit as f64;
+            if (fraction > 0.5 || (fraction == 0.5 && digit & 1 == 1)) && fraction + delta > 1.0 {
+                // round up, carrying into the integer part if every digit overflows.
+                loop {
+                    match fraction_digits.pop() {
+                        None => {
+                            integer += 1.0;
+                            break;
+                        }
+                        Some(digit) if digit + 1 < radix => {
+                            fraction_digits.push(digit + 1);
+                            break;
+                        }
+                        Some(_) => (),
+                    }
+                }
+                break;
+            }
+            if fraction < delta {
+                break;
+            }
+        }
+    }
+    let mut integer_digits = vec![];
+    // digits past the precision of a double are zeros.
+    while integer / radix_f >= 9007199254740992.0 {
+        integer /= radix_f;
+        integer_digits.push(0);
+    }
+    while integer >= 1.0 {
+        let digit = integer % radix_f;
+        integer_digits.push(digit as u32);
+        integer = (integer - digit) / radix_f;
+    }
+    if integer_digits.is_empty() {
+        integer_digits.push(0);
+    }
+    let to_char = |digit: &u32| std::char::from_digit(*digit, radix).unwrap();
+    let mut result = integer_digits.iter().rev().map(to_char).collect::<String>();
+    if !fraction_digits.is_empty() {
+        result.push('.');
+        result.extend(fraction_digits.iter().map(to_char));
+    }
+    result
+}
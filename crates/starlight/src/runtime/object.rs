@@ -3,13 +3,17 @@ use std::{mem::size_of, mem::ManuallyDrop};
 use super::{
     arguments::Arguments,
     attributes::*,
+    boolean::JsBoolean,
     class::Class,
     error::JsTypeError,
     function::JsFunction,
+    generator::JsGenerator,
     global::JsGlobal,
     indexed_elements::{IndexedElements, MAX_VECTOR_SIZE},
+    iterator::{JsArrayIterator, JsStringIterator},
     js_arguments::JsArguments,
     number::JsNumber,
+    promise::JsPromise,
     property_descriptor::{DataDescriptor, PropertyDescriptor, StoredSlot},
     slot::*,
     storage::FixedStorage,
@@ -19,7 +23,6 @@ use super::{
 };
 use super::{method_table::MethodTable, value::JsValue};
 use crate::{
-    define_jsclass,
     gc::cell::{Cell, Gc, Trace, Tracer},
     gc::handle::Handle,
     heap::addr::Address,
@@ -98,11 +101,11 @@ impl JsObject {
 impl Drop for JsObject {
     fn drop(&mut self) {
         match self.tag {
-            ObjectTag::Function => unsafe { ManuallyDrop::drop(&mut self.data::<JsFunction>()) },
-            ObjectTag::Global => unsafe { ManuallyDrop::drop(&mut self.data::<JsGlobal>()) },
-            ObjectTag::NormalArguments => unsafe {
-                ManuallyDrop::drop(&mut self.data::<JsArguments>())
-            },
+            ObjectTag::Function => unsafe { ManuallyDrop::drop(self.data::<JsFunction>()) },
+            ObjectTag::Global => unsafe { ManuallyDrop::drop(self.data::<JsGlobal>()) },
+            ObjectTag::NormalArguments => unsafe { ManuallyDrop::drop(self.data::<JsArguments>()) },
+            ObjectTag::Generator => unsafe { ManuallyDrop::drop(self.data::<JsGenerator>()) },
+            ObjectTag::Promise => unsafe { ManuallyDrop::drop(self.data::<JsPromise>()) },
             _ => (),
         }
     }
@@ -114,6 +117,14 @@ pub union ObjectData {
     pub global: ManuallyDrop<JsGlobal>,
     pub function: ManuallyDrop<JsFunction>,
     pub arguments: ManuallyDrop<JsArguments>,
+    pub number: ManuallyDrop<JsNumber>,
+    pub boolean: ManuallyDrop<JsBoolean>,
+    pub symbol: ManuallyDrop<JsSymbolObject>,
+    pub string: ManuallyDrop<JsStringObject>,
+    pub array_iterator: ManuallyDrop<JsArrayIterator>,
+    pub string_iterator: ManuallyDrop<JsStringIterator>,
+    pub generator: ManuallyDrop<JsGenerator>,
+    pub promise: ManuallyDrop<JsPromise>,
 }
 
 impl Cell for JsObject {
@@ -145,6 +156,10 @@ unsafe impl Trace for JsObject {
             ObjectTag::Function => self.as_function().trace(tracer),
             ObjectTag::String => self.as_string().value().trace(tracer),
             ObjectTag::NormalArguments => self.as_arguments().trace(tracer),
+            ObjectTag::ArrayIterator => self.as_array_iterator().trace(tracer),
+            ObjectTag::StringIterator => self.as_string_iterator().trace(tracer),
+            ObjectTag::Generator => self.as_generator().trace(tracer),
+            ObjectTag::Promise => self.as_promise().trace(tracer),
             _ => (),
         }
     }
@@ -155,6 +170,12 @@ pub enum EnumerationMode {
     Default,
     IncludeNotEnumerable,
 }
+/// Level of [`Gc<JsObject>::set_integrity_level`], `Object.seal` and `Object.freeze`.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum IntegrityLevel {
+    Sealed,
+    Frozen,
+}
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum JsHint {
     String,
@@ -322,7 +343,7 @@ impl JsObject {
         if index < MAX_VECTOR_SIZE as u32
             && obj.elements.dense()
             && obj.class.method_table.GetOwnIndexedPropertySlot as usize
-                == Self::GetOwnIndexedPropertySlotMethod as usize
+                == Self::GetOwnIndexedPropertySlotMethod as *const () as usize
             && (obj.prototype().is_none()
                 || obj.prototype().as_ref().unwrap().has_indexed_property())
         {
@@ -404,6 +425,11 @@ impl JsObject {
         (self.flags & OBJ_FLAG_EXTENSIBLE) != 0
     }
 
+    /// Make the object non-extensible, no new properties can be added to it afterwards.
+    pub fn prevent_extensions(&mut self) {
+        self.flags &= !OBJ_FLAG_EXTENSIBLE;
+    }
+
     pub fn set_callable(&mut self, val: bool) {
         if val {
             self.flags |= OBJ_FLAG_CALLABLE;
@@ -413,8 +439,7 @@ impl JsObject {
     }
 
     pub fn is_callable(&self) -> bool {
-        (self.flags & OBJ_FLAG_CALLABLE) != 0
-            || self.class as *const Class == JsFunction::get_class() as *const Class
+        (self.flags & OBJ_FLAG_CALLABLE) != 0 || std::ptr::eq(self.class, JsFunction::get_class())
     }
 
     // section 8.12.9 `[[DefineOwnProperty]]`
@@ -488,11 +513,9 @@ impl JsObject {
             .structure
             .add_property_transition(vm, name, stored.attributes(), &mut offset);
         obj.structure = s;
-
-        let s = obj.structure;
-
         obj.slots.resize(vm, s.get_slots_size(), JsValue::empty());
-        assert!(stored.value() == desc.value());
+        // accessors are stored as a freshly allocated `Accessor` cell.
+        assert!(!desc.is_data() || stored.value() == desc.value());
         *obj.direct_mut(offset as _) = stored.value();
         slot.mark_put_result(PutResultType::New, offset);
         //println!("add");
@@ -508,7 +531,7 @@ impl JsObject {
         throwable: bool,
     ) -> Result<bool, JsValue> {
         if obj.class.method_table.GetOwnIndexedPropertySlot as usize
-            != Self::GetOwnIndexedPropertySlotMethod as usize
+            != Self::GetOwnIndexedPropertySlotMethod as *const () as usize
         {
             // We should reject following case
             //   var str = new String('str');
@@ -644,7 +667,7 @@ impl JsObject {
         throwable: bool,
     ) -> Result<bool, JsValue> {
         if obj.class.method_table.GetOwnIndexedPropertySlot as usize
-            == Self::GetOwnIndexedPropertySlotMethod as usize
+            == Self::GetOwnIndexedPropertySlotMethod as *const () as usize
         {
             return obj.delete_indexed_internal(vm, index, throwable);
         }
@@ -770,6 +793,8 @@ impl JsObject {
         class: &'static Class,
         tag: ObjectTag,
     ) -> Gc<Self> {
+        // nothing roots the slots and elements storage until the object itself is allocated.
+        vm.space().defer_gc();
         let this = Self {
             structure,
             class,
@@ -780,7 +805,9 @@ impl JsObject {
             flags: OBJ_FLAG_EXTENSIBLE,
             tag,
         };
-        vm.space().alloc(this)
+        let this = vm.space().alloc(this);
+        vm.space().undefer_gc();
+        this
     }
 
     pub fn tag(&self) -> ObjectTag {
@@ -822,6 +849,26 @@ impl JsObject {
         unsafe { &mut *self.data::<JsNumber>() }
     }
 
+    pub fn as_boolean(&self) -> &JsBoolean {
+        assert_eq!(self.tag, ObjectTag::Boolean);
+        unsafe { &*self.data::<JsBoolean>() }
+    }
+
+    pub fn as_boolean_mut(&mut self) -> &mut JsBoolean {
+        assert_eq!(self.tag, ObjectTag::Boolean);
+        unsafe { &mut *self.data::<JsBoolean>() }
+    }
+
+    pub fn as_symbol_object(&self) -> &JsSymbolObject {
+        assert_eq!(self.tag, ObjectTag::Symbol);
+        unsafe { &*self.data::<JsSymbolObject>() }
+    }
+
+    pub fn as_symbol_object_mut(&mut self) -> &mut JsSymbolObject {
+        assert_eq!(self.tag, ObjectTag::Symbol);
+        unsafe { &mut *self.data::<JsSymbolObject>() }
+    }
+
     pub fn as_string(&self) -> &JsStringObject {
         assert_eq!(self.tag, ObjectTag::String);
         unsafe { &*self.data::<JsStringObject>() }
@@ -831,9 +878,125 @@ impl JsObject {
         assert_eq!(self.tag, ObjectTag::String);
         unsafe { &mut *self.data::<JsStringObject>() }
     }
+
+    pub fn as_array_iterator(&self) -> &JsArrayIterator {
+        assert_eq!(self.tag, ObjectTag::ArrayIterator);
+        unsafe { &*self.data::<JsArrayIterator>() }
+    }
+
+    pub fn as_array_iterator_mut(&mut self) -> &mut JsArrayIterator {
+        assert_eq!(self.tag, ObjectTag::ArrayIterator);
+        unsafe { &mut *self.data::<JsArrayIterator>() }
+    }
+
+    pub fn as_string_iterator(&self) -> &JsStringIterator {
+        assert_eq!(self.tag, ObjectTag::StringIterator);
+        unsafe { &*self.data::<JsStringIterator>() }
+    }
+
+    pub fn as_string_iterator_mut(&mut self) -> &mut JsStringIterator {
+        assert_eq!(self.tag, ObjectTag::StringIterator);
+        unsafe { &mut *self.data::<JsStringIterator>() }
+    }
+
+    pub fn as_generator(&self) -> &JsGenerator {
+        assert_eq!(self.tag, ObjectTag::Generator);
+        unsafe { &*self.data::<JsGenerator>() }
+    }
+
+    pub fn as_generator_mut(&mut self) -> &mut JsGenerator {
+        assert_eq!(self.tag, ObjectTag::Generator);
+        unsafe { &mut *self.data::<JsGenerator>() }
+    }
+
+    pub fn as_promise(&self) -> &JsPromise {
+        assert_eq!(self.tag, ObjectTag::Promise);
+        unsafe { &*self.data::<JsPromise>() }
+    }
+
+    pub fn as_promise_mut(&mut self) -> &mut JsPromise {
+        assert_eq!(self.tag, ObjectTag::Promise);
+        unsafe { &mut *self.data::<JsPromise>() }
+    }
 }
 
 impl Gc<JsObject> {
+    /// 10.1.2.1 OrdinarySetPrototypeOf
+    ///
+    /// Returns false if the object is not extensible or `proto` would make the prototype chain
+    /// circular.
+    pub fn set_prototype(&mut self, vm: &mut VirtualMachine, proto: Option<Gc<JsObject>>) -> bool {
+        let same = match (self.prototype(), proto) {
+            (Some(current), Some(proto)) => Gc::ptr_eq(current, proto),
+            (None, None) => true,
+            _ => false,
+        };
+        if same {
+            return true;
+        }
+        if !self.is_extensible() {
+            return false;
+        }
+        let mut p = proto;
+        while let Some(obj) = p {
+            if Gc::ptr_eq(obj, *self) {
+                return false;
+            }
+            p = obj.prototype();
+        }
+        self.structure = self.structure.change_prototype_transition(vm, proto);
+        true
+    }
+
+    /// 7.3.15 SetIntegrityLevel ( O, level )
+    ///
+    /// Every own property goes through [[DefineOwnProperty]], so named properties move to a
+    /// structure with the new attributes and indexed ones end up in the sparse map.
+    pub fn set_integrity_level(
+        &mut self,
+        vm: &mut VirtualMachine,
+        level: IntegrityLevel,
+    ) -> Result<(), JsValue> {
+        self.prevent_extensions();
+        for key in self.get_own_property_keys(vm, EnumerationMode::IncludeNotEnumerable) {
+            let mut slot = Slot::new();
+            if !self.get_own_property_slot(vm, key, &mut slot) {
+                continue;
+            }
+            let desc = if level == IntegrityLevel::Frozen && slot.attributes().is_data() {
+                PropertyDescriptor::data_descriptor(
+                    JsValue::undefined(),
+                    UNDEF_ENUMERABLE | UNDEF_VALUE,
+                )
+            } else {
+                PropertyDescriptor::generic(UNDEF_ENUMERABLE)
+            };
+            self.define_own_property(vm, key, &desc, true)?;
+        }
+        Ok(())
+    }
+
+    /// 7.3.16 TestIntegrityLevel ( O, level )
+    pub fn test_integrity_level(&self, vm: &mut VirtualMachine, level: IntegrityLevel) -> bool {
+        if self.is_extensible() {
+            return false;
+        }
+        for key in self.get_own_property_keys(vm, EnumerationMode::IncludeNotEnumerable) {
+            let mut slot = Slot::new();
+            if !self.get_own_property_slot(vm, key, &mut slot) {
+                continue;
+            }
+            let attributes = slot.attributes();
+            if attributes.is_configurable() {
+                return false;
+            }
+            if level == IntegrityLevel::Frozen && attributes.is_data() && attributes.is_writable() {
+                return false;
+            }
+        }
+        true
+    }
+
     pub fn get_own_property_names(
         &self,
         vm: &mut VirtualMachine,
@@ -842,6 +1005,34 @@ impl Gc<JsObject> {
     ) {
         (self.class.method_table.GetOwnPropertyNames)(*self, vm, collector, mode)
     }
+    /// Collect own property keys in the order mandated by OrdinaryOwnPropertyKeys: integer
+    /// indices in ascending order, then string keys and finally symbol keys, each in insertion
+    /// order.
+    pub fn get_own_property_keys(
+        &self,
+        vm: &mut VirtualMachine,
+        mode: EnumerationMode,
+    ) -> Vec<Symbol> {
+        let mut indices = vec![];
+        let mut names = vec![];
+        self.get_own_property_names(
+            vm,
+            &mut |sym, order| match sym {
+                Symbol::Indexed(index) => indices.push(index),
+                _ => names.push((order, sym)),
+            },
+            mode,
+        );
+        indices.sort_unstable();
+        indices.dedup();
+        names.sort_by_key(|(order, sym)| (sym.is_unique(), *order));
+        indices
+            .into_iter()
+            .map(Symbol::Indexed)
+            .chain(names.into_iter().map(|(_, sym)| sym))
+            .collect()
+    }
+
     pub fn get_property_names(
         &self,
         vm: &mut VirtualMachine,
@@ -882,11 +1073,17 @@ impl Gc<JsObject> {
                 let args = Arguments::new(vm, JsValue::new(obj), 1);
                 let mut args = Handle::new(vm.space(), args);
                 *args.at_mut(0) = match hint {
-                    JsHint::Number | JsHint::None => JsValue::new(JsString::new(vm, "number")),
+                    JsHint::Number => JsValue::new(JsString::new(vm, "number")),
                     JsHint::String => JsValue::new(JsString::new(vm, "string")),
+                    JsHint::None => JsValue::new(JsString::new(vm, "default")),
                 };
 
-                f.call(vm, &mut args)
+                let result = f.call(vm, &mut args)?;
+                if result.is_object() {
+                    let msg = JsString::new(vm, "Cannot convert object to primitive value");
+                    return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
+                }
+                Ok(result)
             }
             _ => (self.class.method_table.DefaultValue)(obj, vm, hint),
         }
@@ -1056,12 +1253,9 @@ impl Gc<JsObject> {
     ) -> bool {
         if self.get_non_indexed_property_slot(vm, name, slot) {
             if slot.attributes().is_accessor() {
-                if slot.attributes().is_accessor() {
-                    return slot.accessor().setter().is_cell()
-                        && !slot.accessor().setter().is_empty();
-                } else {
-                    return slot.attributes().is_writable();
-                }
+                return slot.accessor().setter().is_cell() && !slot.accessor().setter().is_empty();
+            } else {
+                return slot.attributes().is_writable();
             }
         }
         self.is_extensible()
@@ -1303,6 +1497,14 @@ pub fn object_size_with_tag(tag: ObjectTag) -> usize {
         ObjectTag::Global => size + size_of::<JsGlobal>(),
         ObjectTag::NormalArguments => size + size_of::<JsArguments>(),
         ObjectTag::Function => size + size_of::<JsFunction>(),
+        ObjectTag::Number => size + size_of::<JsNumber>(),
+        ObjectTag::Boolean => size + size_of::<JsBoolean>(),
+        ObjectTag::Symbol => size + size_of::<JsSymbolObject>(),
+        ObjectTag::String => size + size_of::<JsStringObject>(),
+        ObjectTag::ArrayIterator => size + size_of::<JsArrayIterator>(),
+        ObjectTag::StringIterator => size + size_of::<JsStringIterator>(),
+        ObjectTag::Generator => size + size_of::<JsGenerator>(),
+        ObjectTag::Promise => size + size_of::<JsPromise>(),
         _ => size,
     }
 }
@@ -1316,6 +1518,8 @@ pub enum ObjectTag {
     String,
     Map,
     Number,
+    Boolean,
+    Symbol,
     Error,
     Global,
     Json,
@@ -1339,7 +1543,9 @@ pub enum ObjectTag {
     MapIterator,
     SetIterator,
     StringIterator,
+    Generator,
     ForInIterator,
+    Promise,
     WeakMap,
     WeakSet,
 
@@ -1411,4 +1617,48 @@ mod tests {
             VirtualMachineRef::dispose(vm);
         }
     }
+
+    #[test]
+    fn test_delete_reuses_slot_and_keeps_key_order() {
+        let mut vm = VirtualMachine::new(Options::default());
+        for unique in [false, true] {
+            let my_struct = if unique {
+                Structure::new_unique_indexed(&mut vm, None, false)
+            } else {
+                Structure::new_indexed(&mut vm, None, false)
+            };
+            let obj = JsObject::new(
+                &mut vm,
+                my_struct,
+                JsObject::get_class(),
+                ObjectTag::Ordinary,
+            );
+            let mut obj = Handle::new(vm.space(), obj);
+            let [a, b, c, d] = [
+                vm.intern("a"),
+                vm.intern("b"),
+                vm.intern("c"),
+                vm.intern("d"),
+            ];
+            for (i, key) in [a, b, c].iter().enumerate() {
+                assert!(obj
+                    .put(&mut vm, *key, JsValue::new(i as i32), false)
+                    .is_ok());
+            }
+            assert!(matches!(obj.delete(&mut vm, a, false), Ok(true)));
+            assert!(obj.put(&mut vm, d, JsValue::new(3), false).is_ok());
+            assert!(obj.put(&mut vm, a, JsValue::new(4), false).is_ok());
+            vm.space().gc();
+
+            // `d` took the slot of the deleted `a`, yet keys stay in insertion order.
+            assert_eq!(obj.structure.get_slots_size(), 4);
+            let keys = obj.get_own_property_keys(&mut vm, EnumerationMode::Default);
+            assert!(keys == vec![b, c, d, a]);
+            for (key, value) in [(a, 4), (b, 1), (c, 2), (d, 3)].iter() {
+                let val = obj.get_property(&mut vm, *key);
+                assert_eq!(val.value().as_int32(), *value);
+            }
+        }
+        VirtualMachineRef::dispose(vm);
+    }
 }
@@ -0,0 +1,471 @@
+use std::mem::ManuallyDrop;
+
+use super::{
+    arguments::Arguments,
+    error::JsTypeError,
+    function::{JsClosureAPI, JsClosureFunction},
+    gc_array::GcArray,
+    method_table::*,
+    object::{JsObject, ObjectTag},
+    slot::Slot,
+    string::JsString,
+    structure::Structure,
+    symbol::Symbol,
+    value::JsValue,
+};
+use crate::{
+    gc::{
+        cell::{Gc, Trace, Tracer},
+        handle::Handle,
+    },
+    vm::VirtualMachine,
+};
+
+/// 27.2.6 Properties of Promise Instances, [[PromiseState]].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PromiseState {
+    Pending,
+    Fulfilled,
+    Rejected,
+}
+
+/// [[Type]] of a PromiseReaction Record.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReactionKind {
+    Fulfill,
+    Reject,
+}
+
+/// 27.2.1.1 PromiseCapability Records
+#[derive(Clone, Copy)]
+pub struct PromiseCapability {
+    pub promise: JsValue,
+    pub resolve: JsValue,
+    pub reject: JsValue,
+}
+
+/// 27.2.1.2 PromiseReaction Records
+#[derive(Clone, Copy)]
+pub struct PromiseReaction {
+    pub capability: Option<PromiseCapability>,
+    pub kind: ReactionKind,
+    /// Reaction handler, undefined if none was passed to `then`.
+    pub handler: JsValue,
+}
+
+/// Promise instance data.
+pub struct JsPromise {
+    pub(crate) state: PromiseState,
+    /// Fulfillment value or rejection reason once the promise is settled.
+    pub(crate) result: JsValue,
+    pub(crate) fulfill_reactions: Vec<PromiseReaction>,
+    pub(crate) reject_reactions: Vec<PromiseReaction>,
+    pub(crate) is_handled: bool,
+}
+
+impl JsPromise {
+    define_jsclass_with_symbol!(JsObject, Promise, Object);
+
+    pub fn new(vm: &mut VirtualMachine, structure: Option<Gc<Structure>>) -> Gc<JsObject> {
+        unsafe {
+            let mut jsobject = JsObject::new(
+                vm,
+                structure.unwrap_or_else(|| vm.global_data().promise_structure.unwrap()),
+                Self::get_class(),
+                ObjectTag::Promise,
+            );
+
+            *jsobject.data::<Self>() = ManuallyDrop::new(Self {
+                state: PromiseState::Pending,
+                result: JsValue::undefined(),
+                fulfill_reactions: vec![],
+                reject_reactions: vec![],
+                is_handled: false,
+            });
+            jsobject
+        }
+    }
+}
+
+unsafe impl Trace for PromiseCapability {
+    fn trace(&self, tracer: &mut dyn Tracer) {
+        self.promise.trace(tracer);
+        self.resolve.trace(tracer);
+        self.reject.trace(tracer);
+    }
+}
+
+unsafe impl Trace for PromiseReaction {
+    fn trace(&self, tracer: &mut dyn Tracer) {
+        self.capability.trace(tracer);
+        self.handler.trace(tracer);
+    }
+}
+
+unsafe impl Trace for JsPromise {
+    fn trace(&self, tracer: &mut dyn Tracer) {
+        self.result.trace(tracer);
+        self.fulfill_reactions.trace(tracer);
+        self.reject_reactions.trace(tracer);
+    }
+}
+
+/// Job of the VM microtask queue.
+#[derive(Clone, Copy)]
+pub enum Job {
+    /// 27.2.2.1 NewPromiseReactionJob
+    Reaction(PromiseReaction, JsValue),
+    /// 27.2.2.2 NewPromiseResolveThenableJob
+    ResolveThenable {
+        promise: JsValue,
+        thenable: JsValue,
+        then: JsValue,
+    },
+}
+
+unsafe impl Trace for Job {
+    fn trace(&self, tracer: &mut dyn Tracer) {
+        match self {
+            Job::Reaction(reaction, argument) => {
+                reaction.trace(tracer);
+                argument.trace(tracer);
+            }
+            Job::ResolveThenable {
+                promise,
+                thenable,
+                then,
+            } => {
+                promise.trace(tracer);
+                thenable.trace(tracer);
+                then.trace(tracer);
+            }
+        }
+    }
+}
+
+pub(crate) fn type_error(vm: &mut VirtualMachine, msg: &str) -> JsValue {
+    let msg = JsString::new(vm, msg).root(vm.space());
+    JsValue::new(JsTypeError::new(vm, *msg, None))
+}
+
+/// 7.3.13 Call
+pub fn call(
+    vm: &mut VirtualMachine,
+    func: JsValue,
+    this: JsValue,
+    argv: &[JsValue],
+) -> Result<JsValue, JsValue> {
+    if !func.is_callable() {
+        return Err(type_error(vm, "object is not a function"));
+    }
+    let func = Handle::new(vm.space(), func);
+    let this = Handle::new(vm.space(), this);
+    let argv = Handle::new(vm.space(), argv.to_vec());
+    let args = Arguments::new(vm, *this, argv.len());
+    let mut args = Handle::new(vm.space(), args);
+    for (ix, val) in argv.iter().enumerate() {
+        *args.at_mut(ix) = *val;
+    }
+    func.as_object().as_function_mut().call(vm, &mut args)
+}
+
+/// 7.3.21 Invoke
+pub fn invoke(
+    vm: &mut VirtualMachine,
+    this: JsValue,
+    name: Symbol,
+    argv: &[JsValue],
+) -> Result<JsValue, JsValue> {
+    let func = this.get_slot(vm, name, &mut Slot::new())?;
+    call(vm, func, this, argv)
+}
+
+/// Create a closure function of `f` capturing `data`.
+pub(crate) fn closure(
+    vm: &mut VirtualMachine,
+    f: JsClosureAPI,
+    data: &[JsValue],
+    n: u32,
+) -> Gc<JsObject> {
+    let data = Handle::new(vm.space(), data.to_vec());
+    let mut array = GcArray::new(vm, data.len(), JsValue::undefined()).root(vm.space());
+    for (ix, val) in data.iter().enumerate() {
+        array[ix] = *val;
+    }
+    let name = vm.intern("");
+    JsClosureFunction::new(vm, name, f, *array, n)
+}
+
+/// 27.2.1.3 CreateResolvingFunctions
+///
+/// Both functions share `[promise, alreadyResolved]` record.
+pub fn create_resolving_functions(
+    vm: &mut VirtualMachine,
+    promise: Gc<JsObject>,
+) -> (Gc<JsObject>, Gc<JsObject>) {
+    let mut record = GcArray::new(vm, 2, JsValue::undefined()).root(vm.space());
+    record[0] = JsValue::new(promise);
+    record[1] = JsValue::new(false);
+    let name = vm.intern("");
+    let resolve =
+        JsClosureFunction::new(vm, name, promise_resolve_function, *record, 1).root(vm.space());
+    let reject = JsClosureFunction::new(vm, name, promise_reject_function, *record, 1);
+    (*resolve, reject)
+}
+
+/// 27.2.1.3.2 Promise Resolve Functions
+fn promise_resolve_function(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    mut record: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue> {
+    if record[1].to_boolean() {
+        return Ok(JsValue::undefined());
+    }
+    record[1] = JsValue::new(true);
+    resolve_promise(vm, record[0].as_object(), args.at(0));
+    Ok(JsValue::undefined())
+}
+
+/// 27.2.1.3.1 Promise Reject Functions
+fn promise_reject_function(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    mut record: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue> {
+    if record[1].to_boolean() {
+        return Ok(JsValue::undefined());
+    }
+    record[1] = JsValue::new(true);
+    reject_promise(vm, record[0].as_object(), args.at(0));
+    Ok(JsValue::undefined())
+}
+
+/// Steps of 27.2.1.3.2 Promise Resolve Functions after the `alreadyResolved` check: fulfill
+/// `promise` with `resolution` or, if it is a thenable, follow it.
+pub fn resolve_promise(vm: &mut VirtualMachine, promise: Gc<JsObject>, resolution: JsValue) {
+    if resolution.is_object() && Gc::ptr_eq(resolution.as_object(), promise) {
+        let error = type_error(vm, "Chaining cycle detected for promise");
+        return reject_promise(vm, promise, error);
+    }
+    if !resolution.is_object() {
+        return fulfill_promise(vm, promise, resolution);
+    }
+    let name = vm.intern("then");
+    let then = match resolution.as_object().get(vm, name) {
+        Ok(then) => then,
+        Err(error) => return reject_promise(vm, promise, error),
+    };
+    if !then.is_callable() {
+        return fulfill_promise(vm, promise, resolution);
+    }
+    vm.enqueue_job(Job::ResolveThenable {
+        promise: JsValue::new(promise),
+        thenable: resolution,
+        then,
+    });
+}
+
+/// 27.2.1.4 FulfillPromise
+pub fn fulfill_promise(vm: &mut VirtualMachine, mut promise: Gc<JsObject>, value: JsValue) {
+    let data = promise.as_promise_mut();
+    assert_eq!(data.state, PromiseState::Pending);
+    let reactions = std::mem::take(&mut data.fulfill_reactions);
+    data.reject_reactions.clear();
+    data.result = value;
+    data.state = PromiseState::Fulfilled;
+    trigger_promise_reactions(vm, reactions, value);
+}
+
+/// 27.2.1.7 RejectPromise
+pub fn reject_promise(vm: &mut VirtualMachine, mut promise: Gc<JsObject>, reason: JsValue) {
+    let data = promise.as_promise_mut();
+    assert_eq!(data.state, PromiseState::Pending);
+    let reactions = std::mem::take(&mut data.reject_reactions);
+    data.fulfill_reactions.clear();
+    data.result = reason;
+    data.state = PromiseState::Rejected;
+    trigger_promise_reactions(vm, reactions, reason);
+}
+
+/// 27.2.1.8 TriggerPromiseReactions
+fn trigger_promise_reactions(
+    vm: &mut VirtualMachine,
+    reactions: Vec<PromiseReaction>,
+    argument: JsValue,
+) {
+    for reaction in reactions {
+        vm.enqueue_job(Job::Reaction(reaction, argument));
+    }
+}
+
+/// 27.2.1.5 NewPromiseCapability
+pub fn new_promise_capability(
+    vm: &mut VirtualMachine,
+    constructor: JsValue,
+) -> Result<PromiseCapability, JsValue> {
+    if !constructor.is_callable() {
+        return Err(type_error(vm, "Promise resolver is not a constructor"));
+    }
+    let intrinsic = vm.global_data().promise_constructor.unwrap();
+    if Gc::ptr_eq(constructor.as_object(), intrinsic) {
+        // %Promise% is not observable, create the promise directly.
+        let promise = JsPromise::new(vm, None).root(vm.space());
+        let (resolve, reject) = create_resolving_functions(vm, *promise);
+        return Ok(PromiseCapability {
+            promise: JsValue::new(*promise),
+            resolve: JsValue::new(resolve),
+            reject: JsValue::new(reject),
+        });
+    }
+
+    let record = GcArray::new(vm, 2, JsValue::undefined()).root(vm.space());
+    let name = vm.intern("");
+    let executor =
+        JsClosureFunction::new(vm, name, get_capabilities_executor, *record, 2).root(vm.space());
+    let mut ctor = constructor.as_object().root(vm.space());
+    let structure = ctor.func_construct_map(vm)?;
+    let args = Arguments::new(vm, JsValue::undefined(), 1);
+    let mut args = Handle::new(vm.space(), args);
+    *args.at_mut(0) = JsValue::new(*executor);
+    let promise = ctor
+        .as_function_mut()
+        .construct(vm, &mut args, Some(structure))?;
+    if !record[0].is_callable() || !record[1].is_callable() {
+        return Err(type_error(
+            vm,
+            "Promise resolve or reject function is not callable",
+        ));
+    }
+    Ok(PromiseCapability {
+        promise,
+        resolve: record[0],
+        reject: record[1],
+    })
+}
+
+/// 27.2.1.5.1 GetCapabilitiesExecutor Functions
+fn get_capabilities_executor(
+    vm: &mut VirtualMachine,
+    args: &Arguments,
+    mut record: Gc<GcArray<JsValue>>,
+) -> Result<JsValue, JsValue> {
+    if !record[0].is_undefined() || !record[1].is_undefined() {
+        return Err(type_error(vm, "Promise executor has already been invoked"));
+    }
+    record[0] = args.at(0);
+    record[1] = args.at(1);
+    Ok(JsValue::undefined())
+}
+
+/// 27.2.4.7.1 PromiseResolve
+pub fn promise_resolve(
+    vm: &mut VirtualMachine,
+    constructor: JsValue,
+    value: JsValue,
+) -> Result<JsValue, JsValue> {
+    let value = Handle::new(vm.space(), value);
+    if value.is_object() && value.as_object().tag() == ObjectTag::Promise {
+        let value_ctor = value.as_object().get(vm, Symbol::constructor())?;
+        if JsValue::same_value(value_ctor, constructor) {
+            return Ok(*value);
+        }
+    }
+    let capability = new_promise_capability(vm, constructor)?;
+    let capability = Handle::new(vm.space(), capability);
+    call(vm, capability.resolve, JsValue::undefined(), &[*value])?;
+    Ok(capability.promise)
+}
+
+/// 27.2.5.4.1 PerformPromiseThen
+pub fn perform_promise_then(
+    vm: &mut VirtualMachine,
+    mut promise: Gc<JsObject>,
+    on_fulfilled: JsValue,
+    on_rejected: JsValue,
+    capability: Option<PromiseCapability>,
+) -> JsValue {
+    let fulfill_reaction = PromiseReaction {
+        capability,
+        kind: ReactionKind::Fulfill,
+        handler: if on_fulfilled.is_callable() {
+            on_fulfilled
+        } else {
+            JsValue::undefined()
+        },
+    };
+    let reject_reaction = PromiseReaction {
+        capability,
+        kind: ReactionKind::Reject,
+        handler: if on_rejected.is_callable() {
+            on_rejected
+        } else {
+            JsValue::undefined()
+        },
+    };
+    let data = promise.as_promise_mut();
+    match data.state {
+        PromiseState::Pending => {
+            data.fulfill_reactions.push(fulfill_reaction);
+            data.reject_reactions.push(reject_reaction);
+        }
+        PromiseState::Fulfilled => {
+            let value = data.result;
+            vm.enqueue_job(Job::Reaction(fulfill_reaction, value));
+        }
+        PromiseState::Rejected => {
+            let reason = data.result;
+            vm.enqueue_job(Job::Reaction(reject_reaction, reason));
+        }
+    }
+    promise.as_promise_mut().is_handled = true;
+    match capability {
+        Some(capability) => capability.promise,
+        None => JsValue::undefined(),
+    }
+}
+
+/// Run microtask `job`.
+pub fn run_job(vm: &mut VirtualMachine, job: Job) -> Result<(), JsValue> {
+    match job {
+        Job::Reaction(reaction, argument) => {
+            let result = if reaction.handler.is_undefined() {
+                match reaction.kind {
+                    ReactionKind::Fulfill => Ok(argument),
+                    ReactionKind::Reject => Err(argument),
+                }
+            } else {
+                call(vm, reaction.handler, JsValue::undefined(), &[argument])
+            };
+            match reaction.capability {
+                Some(capability) => match result {
+                    Ok(value) => call(vm, capability.resolve, JsValue::undefined(), &[value]),
+                    Err(error) => call(vm, capability.reject, JsValue::undefined(), &[error]),
+                }
+                .map(|_| ()),
+                None => result.map(|_| ()),
+            }
+        }
+        Job::ResolveThenable {
+            promise,
+            thenable,
+            then,
+        } => {
+            let (resolve, reject) = create_resolving_functions(vm, promise.as_object());
+            let resolve = resolve.root(vm.space());
+            let reject = reject.root(vm.space());
+            let result = call(
+                vm,
+                then,
+                thenable,
+                &[JsValue::new(*resolve), JsValue::new(*reject)],
+            );
+            match result {
+                Ok(_) => Ok(()),
+                Err(error) => {
+                    call(vm, JsValue::new(*reject), JsValue::undefined(), &[error]).map(|_| ())
+                }
+            }
+        }
+    }
+}
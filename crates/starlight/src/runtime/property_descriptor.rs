@@ -54,7 +54,7 @@ impl PropertyDescriptor {
     pub fn accessor_setter(setter: JsValue, attrs: u32) -> Self {
         Self {
             attrs: AttrExternal::new(Some(
-                attrs | ACCESSOR | UNDEF_VALUE | UNDEF_SETTER | UNDEF_WRITABLE,
+                attrs | ACCESSOR | UNDEF_VALUE | UNDEF_GETTER | UNDEF_WRITABLE,
             )),
             value: PropertyLayout {
                 accessors: (JsValue::undefined(), setter),
@@ -428,9 +428,9 @@ impl StoredSlot {
                 ac
             };
             if accs.is_getter_absent() {
-                ac.set_getter(accs.get());
-            } else if accs.is_setter_absent() {
                 ac.set_setter(accs.set());
+            } else if accs.is_setter_absent() {
+                ac.set_getter(accs.get());
             } else {
                 ac.set_getter(accs.get());
                 ac.set_setter(accs.set());
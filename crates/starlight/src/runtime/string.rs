@@ -1,3 +1,10 @@
+use std::{
+    borrow::Cow,
+    cell::UnsafeCell,
+    cmp::Ordering,
+    mem::{size_of, ManuallyDrop},
+};
+
 use super::{attributes::*, error::JsTypeError, slot::*};
 use super::{
     method_table::MethodTable, object::EnumerationMode, property_descriptor::PropertyDescriptor,
@@ -8,57 +15,300 @@ use super::{
     value::JsValue,
 };
 use crate::{
-    gc::cell::{Cell, Gc, Trace},
+    gc::cell::{Cell, Gc, Trace, Tracer},
     vm::VirtualMachine,
 };
 
+/// Concatenations shorter than this are copied right away instead of building a rope.
+const MIN_ROPE_LENGTH: usize = 13;
+/// Flattening recurses once per level of nesting on the right-hand side of a rope, a rope
+/// nested deeper than this is flattened as soon as it is built.
+const MAX_ROPE_DEPTH: u32 = 1024;
+
+/// WhiteSpace and LineTerminator code points.
+pub(crate) fn is_js_whitespace(unit: u16) -> bool {
+    matches!(
+        unit,
+        0x09 | 0x0a | 0x0b | 0x0c | 0x0d | 0x20 | 0xa0 | 0x1680 | 0x2000
+            ..=0x200a | 0x2028 | 0x2029 | 0x202f | 0x205f | 0x3000 | 0xfeff
+    )
+}
+
+/// Lazy concatenation of two strings, see [`JsString::concat`].
+enum Rope {
+    Pending {
+        left: Gc<JsString>,
+        right: Gc<JsString>,
+        depth: u32,
+    },
+    Latin1(Box<[u8]>),
+    Utf16(Box<[u16]>),
+}
+
+/// Immutable sequence of UTF-16 code units, as seen by JS code. Units are stored inline after
+/// the cell; when every unit fits in a byte the string is stored as Latin-1 instead.
+///
+/// Strings built by [`JsString::concat`] are ropes: they keep both operands and only copy their
+/// units into a buffer of their own the first time the units are read.
 #[repr(C)]
 pub struct JsString {
-    str: String,
+    len: u32,
+    is_8bit: bool,
+    rope: Option<Box<UnsafeCell<Rope>>>,
+    data: [u16; 0],
 }
 
 impl JsString {
     pub fn is_empty(&self) -> bool {
-        self.str.is_empty()
+        self.len == 0
+    }
+
+    fn allocate(vm: &mut VirtualMachine, len: usize, is_8bit: bool) -> Gc<Self> {
+        vm.space().alloc(Self {
+            len: len as _,
+            is_8bit,
+            rope: None,
+            data: [],
+        })
     }
+
     pub fn new(vm: &mut VirtualMachine, as_str: impl AsRef<str>) -> Gc<Self> {
         let str = as_str.as_ref();
-        let proto = Self {
-           str:str.to_string()
-            // len: str.len() as _,
-            //data: [],
-        };
-        let mut cell = vm.space().alloc(proto);
+        if str.chars().all(|c| (c as u32) <= 0xff) {
+            let len = str.chars().count();
+            let cell = Self::allocate(vm, len, true);
+            let data = cell.data.as_ptr() as *mut u8;
+            for (ix, c) in str.chars().enumerate() {
+                unsafe {
+                    data.add(ix).write(c as u8);
+                }
+            }
+            cell
+        } else {
+            let units = str.encode_utf16().collect::<Vec<_>>();
+            Self::new_utf16(vm, &units)
+        }
+    }
+
+    /// Create a string from UTF-16 code units. Lone surrogates are kept as they are.
+    pub fn from_utf16(vm: &mut VirtualMachine, units: &[u16]) -> Gc<Self> {
+        if units.iter().all(|unit| *unit <= 0xff) {
+            let cell = Self::allocate(vm, units.len(), true);
+            let data = cell.data.as_ptr() as *mut u8;
+            for (ix, unit) in units.iter().enumerate() {
+                unsafe {
+                    data.add(ix).write(*unit as u8);
+                }
+            }
+            cell
+        } else {
+            Self::new_utf16(vm, units)
+        }
+    }
 
-        /*unsafe {
-            cell.len = str.len() as _;
+    fn new_utf16(vm: &mut VirtualMachine, units: &[u16]) -> Gc<Self> {
+        let cell = Self::allocate(vm, units.len(), false);
+        unsafe {
             std::ptr::copy_nonoverlapping(
-                str.as_bytes().as_ptr(),
-                cell.data.as_mut_ptr(),
-                str.len(),
+                units.as_ptr(),
+                cell.data.as_ptr() as *mut u16,
+                units.len(),
             );
-        }*/
+        }
+        cell
+    }
 
+    /// Concatenate `left` and `right`. Unless the result is short, no units are copied until
+    /// the result is read, so appending to a string in a loop takes linear time.
+    pub fn concat(vm: &mut VirtualMachine, left: Gc<Self>, right: Gc<Self>) -> Gc<Self> {
+        if left.is_empty() {
+            return right;
+        }
+        if right.is_empty() {
+            return left;
+        }
+        let len = left.len as usize + right.len as usize;
+        if len < MIN_ROPE_LENGTH {
+            let units = left.iter().chain(right.iter()).collect::<Vec<_>>();
+            return Self::from_utf16(vm, &units);
+        }
+        let depth = left.depth().max(right.depth() + 1);
+        // the operands are only reachable from the rope once it is allocated.
+        vm.space().defer_gc();
+        let cell = vm.space().alloc(Self {
+            len: len as _,
+            is_8bit: left.is_8bit && right.is_8bit,
+            rope: Some(Box::new(UnsafeCell::new(Rope::Pending {
+                left,
+                right,
+                depth,
+            }))),
+            data: [],
+        });
+        vm.space().undefer_gc();
+        if depth > MAX_ROPE_DEPTH {
+            cell.flatten();
+        }
         cell
     }
 
-    pub fn as_str(&self) -> &str {
-        &self.str
-        /*unsafe {
-            std::str::from_utf8_unchecked(std::slice::from_raw_parts(
-                self.data.as_ptr(),
-                self.len as _,
-            ))
-        }*/
+    fn rope(&self) -> Option<&Rope> {
+        self.rope.as_ref().map(|rope| unsafe { &*rope.get() })
+    }
+
+    fn depth(&self) -> u32 {
+        match self.rope() {
+            Some(Rope::Pending { depth, .. }) => *depth,
+            _ => 0,
+        }
+    }
+
+    /// Copy the units of a pending rope into its own buffer and let go of its operands.
+    fn flatten(&self) {
+        let (left, right) = match self.rope() {
+            Some(Rope::Pending { left, right, .. }) => (*left, *right),
+            _ => return,
+        };
+        let mut units = Vec::with_capacity(self.len as usize);
+        Self::write_units(left, &mut units);
+        Self::write_units(right, &mut units);
+        let flat = if self.is_8bit {
+            Rope::Latin1(units.iter().map(|unit| *unit as u8).collect())
+        } else {
+            Rope::Utf16(units.into_boxed_slice())
+        };
+        unsafe {
+            *self.rope.as_ref().unwrap().get() = flat;
+        }
+    }
+
+    /// Append the units of `string` to `out` without flattening the ropes it is made of. Left
+    /// operands are walked in a loop, only right operands recurse.
+    fn write_units(string: Gc<Self>, out: &mut Vec<u16>) {
+        let mut rights = vec![];
+        let mut current = string;
+        while let Some(Rope::Pending { left, right, .. }) = current.rope() {
+            let (left, right) = (*left, *right);
+            rights.push(right);
+            current = left;
+        }
+        out.extend(current.iter());
+        for right in rights.into_iter().rev() {
+            Self::write_units(right, out);
+        }
+    }
+
+    fn data_ptr(&self) -> *const u8 {
+        self.flatten();
+        match self.rope() {
+            Some(Rope::Latin1(units)) => units.as_ptr(),
+            Some(Rope::Utf16(units)) => units.as_ptr() as *const u8,
+            _ => self.data.as_ptr() as *const u8,
+        }
+    }
+
+    /// Whether the string is stored as Latin-1, see [`JsString::latin1`].
+    pub fn is_8bit(&self) -> bool {
+        self.is_8bit
+    }
+
+    /// Units of a Latin-1 string, empty for a 16-bit one.
+    pub fn latin1(&self) -> &[u8] {
+        if !self.is_8bit {
+            return &[];
+        }
+        unsafe { std::slice::from_raw_parts(self.data_ptr(), self.len as _) }
+    }
+
+    /// Units of a 16-bit string, empty for a Latin-1 one.
+    pub fn utf16(&self) -> &[u16] {
+        if self.is_8bit {
+            return &[];
+        }
+        unsafe { std::slice::from_raw_parts(self.data_ptr() as *const u16, self.len as _) }
+    }
+
+    /// Code unit at `index`, which must be less than [`JsString::len`].
+    pub fn at(&self, index: u32) -> u16 {
+        assert!(index < self.len);
+        if self.is_8bit {
+            self.latin1()[index as usize] as u16
+        } else {
+            self.utf16()[index as usize]
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u16> + '_ {
+        self.latin1()
+            .iter()
+            .map(|unit| *unit as u16)
+            .chain(self.utf16().iter().copied())
+    }
+
+    pub fn to_utf16(&self) -> Cow<'_, [u16]> {
+        if self.is_8bit {
+            Cow::Owned(self.iter().collect())
+        } else {
+            Cow::Borrowed(self.utf16())
+        }
+    }
+
+    /// Convert to a Rust string, lone surrogates are replaced with U+FFFD.
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        if self.is_8bit {
+            let latin1 = self.latin1();
+            if latin1.is_ascii() {
+                return Cow::Borrowed(unsafe { std::str::from_utf8_unchecked(latin1) });
+            }
+            return Cow::Owned(latin1.iter().map(|unit| *unit as char).collect());
+        }
+        Cow::Owned(String::from_utf16_lossy(self.utf16()))
     }
 
+    /// Length in UTF-16 code units.
     pub fn len(&self) -> u32 {
-        self.str.len() as _
+        self.len
     }
 }
 
-impl Cell for JsString {}
-unsafe impl Trace for JsString {}
+impl PartialEq for JsString {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for JsString {}
+
+impl PartialOrd for JsString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Code unit order, the order of IsLessThan for two strings.
+impl Ord for JsString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl Cell for JsString {
+    fn compute_size(&self) -> usize {
+        if self.rope.is_some() {
+            return size_of::<Self>();
+        }
+        let unit = if self.is_8bit { 1 } else { 2 };
+        size_of::<Self>() + self.len as usize * unit
+    }
+}
+unsafe impl Trace for JsString {
+    fn trace(&self, tracer: &mut dyn Tracer) {
+        if let Some(Rope::Pending { left, right, .. }) = self.rope() {
+            left.trace(tracer);
+            right.trace(tracer);
+        }
+    }
+}
 
 pub struct JsStringObject {
     value: Gc<JsString>,
@@ -71,14 +321,17 @@ impl JsStringObject {
     }
 
     pub fn new(vm: &mut VirtualMachine, value: Gc<JsString>) -> Gc<JsObject> {
-        let mut jsobject = JsObject::new(
-            vm,
-            vm.global_data().string_structure.unwrap(),
-            Self::get_class(),
-            ObjectTag::String,
-        );
+        unsafe {
+            let mut jsobject = JsObject::new(
+                vm,
+                vm.global_data().string_structure.unwrap(),
+                Self::get_class(),
+                ObjectTag::String,
+            );
 
-        jsobject
+            *jsobject.data::<Self>() = ManuallyDrop::new(Self { value });
+            jsobject
+        }
     }
 
     define_jsclass!(JsStringObject, String);
@@ -115,15 +368,7 @@ impl JsStringObject {
     ) -> bool {
         let value = obj.as_string().value();
         if index < value.len() {
-            let nstr = JsString::new(
-                vm,
-                value
-                    .as_str()
-                    .chars()
-                    .nth(index as usize)
-                    .unwrap()
-                    .to_string(),
-            );
+            let nstr = JsString::from_utf16(vm, &[value.at(index)]);
             slot.set_1(JsValue::new(nstr), string_indexed(), Some(obj.as_dyn()));
             return true;
         }
@@ -158,9 +403,8 @@ impl JsStringObject {
         if mode == EnumerationMode::IncludeNotEnumerable {
             collector(Symbol::length(), 0);
         }
-        let value = obj.as_string();
-        for x in 0..obj.as_string().value().as_str().len() {
-            collector(Symbol::Indexed(x as _), x as u32);
+        for x in 0..obj.as_string().value().len() {
+            collector(Symbol::Indexed(x as _), x);
         }
         JsObject::GetOwnPropertyNamesMethod(obj, vm, collector, mode)
     }
@@ -224,7 +468,7 @@ impl JsStringObject {
     ) -> bool {
         if name == Symbol::length() {
             slot.set_1(
-                JsValue::new(obj.as_string().value.as_str().len() as i32),
+                JsValue::new(obj.as_string().value.len() as i32),
                 string_length(),
                 Some(obj.as_dyn()),
             );
@@ -262,3 +506,62 @@ impl JsStringObject {
         JsObject::GetIndexedPropertySlotMethod(obj, vm, index, slot)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::eval_ok;
+
+    #[test]
+    fn test_length_and_index_count_utf16_code_units() {
+        let result = eval_ok(
+            "
+            var s = 'h\u{e9}\u{1F600}';
+            result = s.length + ' ' + s[1] + ' ' + s.charCodeAt(2) + ' ' + s.charCodeAt(3)
+                + ' ' + (s[1] + s[2] + s[3] === s.substring(1));
+            ",
+        );
+        assert_eq!(result, "4 \u{e9} 55357 56832 true");
+    }
+
+    #[test]
+    fn test_rope_operands_survive_collection() {
+        let result = eval_ok(
+            "
+            var s = '';
+            for (var i = 0; i < 500; i = i + 1) {
+                s = '[' + s + ']';
+                // garbage ropes of the same size, to reuse the memory of freed ones.
+                var junk = s + 'abcdefghijklmnop' + i + 'abcdefghijklmnop';
+            }
+            var ok = s.length === 1000;
+            for (var i = 0; i < 1000; i = i + 1) {
+                var unit = 93;
+                if (i < 500) {
+                    unit = 91;
+                }
+                ok = ok && s.charCodeAt(i) === unit;
+            }
+            result = ok;
+            ",
+        );
+        assert_eq!(result, "true");
+    }
+
+    #[test]
+    fn test_repeated_concatenation() {
+        let source = "var s = '', p = '';
+            for (var i = 0; i < 20000; i = i + 1) { s += 'ab'; }
+            for (var i = 0; i < 3000; i = i + 1) { p = 'x' + p; }
+            result = s.length + ' ' + s[39999] + s.charAt(0) + ' ' + (s.slice(0, 4) === 'abab') +
+                ' ' + s.indexOf('ba') + ' ' + p.length + p.slice(0, 2);";
+        assert_eq!(eval_ok(source), "40000 ba true 1 3000xx");
+    }
+
+    #[test]
+    fn test_ropes_compare_and_key_by_contents() {
+        let source = "var a = 'x' + 'y', b = 'xy', o = {};
+            o[a] = 1;
+            result = (a === b) + ' ' + o.xy + ' ' + (a + a == 'xyxy') + ' ' + JSON.stringify(a + 'z');";
+        assert_eq!(eval_ok(source), "true 1 true \"xyz\"");
+    }
+}
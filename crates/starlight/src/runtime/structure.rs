@@ -48,6 +48,8 @@ pub struct Structure {
     prototype: Option<Gc<JsObject>>,
     calculated_size: u32,
     transit_count: u32,
+    /// Insertion order given to the next property added to this structure.
+    next_order: u32,
 }
 
 pub type StructureID = u32;
@@ -56,6 +58,9 @@ pub type StructureID = u32;
 pub struct MapEntry {
     pub offset: u32,
     pub attrs: AttrSafe,
+    /// Position of the property in insertion order. Slots of deleted properties are reused so
+    /// `offset` does not follow insertion order.
+    pub order: u32,
 }
 
 impl MapEntry {
@@ -63,6 +68,7 @@ impl MapEntry {
         Self {
             offset: u32::MAX,
             attrs: AttrSafe::not_found(),
+            order: u32::MAX,
         }
     }
 
@@ -229,6 +235,7 @@ impl DeletedEntryHolder {
             offset,
         });
         self.entry = Some(entry);
+        self.size += 1;
     }
     pub fn pop(&mut self) -> u32 {
         let res = unwrap_unchecked(self.entry).offset;
@@ -273,16 +280,11 @@ impl Structure {
             },
             transitions: TransitionsTable::new(!unique, previous.transitions.is_indexed()),
             deleted: previous.deleted,
-            added: (
-                DUMMY_SYMBOL,
-                MapEntry {
-                    offset: u32::MAX,
-                    attrs: AttrSafe::not_found(),
-                },
-            ),
+            added: (DUMMY_SYMBOL, MapEntry::not_found()),
             id: 0,
             calculated_size: 0,
             transit_count: 0,
+            next_order: previous.next_order,
         });
         this.calculated_size = this.get_slots_size() as _;
         assert!(this.previous.is_some());
@@ -304,16 +306,11 @@ impl Structure {
                 entry: None,
                 size: 0,
             },
-            added: (
-                DUMMY_SYMBOL,
-                MapEntry {
-                    offset: u32::MAX,
-                    attrs: AttrSafe::not_found(),
-                },
-            ),
+            added: (DUMMY_SYMBOL, MapEntry::not_found()),
             id: 0,
             calculated_size: 0,
             transit_count: 0,
+            next_order: 0,
         })
     }
     #[allow(dead_code)]
@@ -327,6 +324,7 @@ impl Structure {
         let mut this = Self::ctor1(vm, prototype, unique, indexed);
         this.table = table;
         this.calculated_size = this.get_slots_size() as _;
+        this.next_order = this.calculated_size;
         this
     }
 
@@ -342,18 +340,14 @@ impl Structure {
                 entry: None,
                 size: 0,
             },
-            added: (
-                DUMMY_SYMBOL,
-                MapEntry {
-                    offset: u32::MAX,
-                    attrs: AttrSafe::not_found(),
-                },
-            ),
+            added: (DUMMY_SYMBOL, MapEntry::not_found()),
             id: 0,
             calculated_size: 0,
             transit_count: 0,
+            next_order: 0,
         });
         this.calculated_size = this.get_slots_size() as _;
+        this.next_order = this.calculated_size;
         this
     }
 
@@ -381,11 +375,7 @@ impl Structure {
         unique: bool,
         indexed: bool,
     ) -> Gc<Structure> {
-        let table = if let Some(table) = table {
-            Some(vm.space().alloc(table))
-        } else {
-            None
-        };
+        let table = table.map(|table| vm.space().alloc(table));
 
         Self::ctor2(vm, table, prototype, unique, indexed)
     }
@@ -599,6 +589,7 @@ impl Gc<Structure> {
         map
     }
 
+    /// Pass own property keys along with their insertion order to `collector`.
     pub fn get_own_property_names(
         &mut self,
         vm: &mut VirtualMachine,
@@ -615,7 +606,7 @@ impl Gc<Structure> {
                     continue;
                 }*/
                 if include || entry.1.attrs.is_enumerable() {
-                    collector(*entry.0, entry.1.offset);
+                    collector(*entry.0, entry.1.order);
                 }
             }
         }
@@ -631,6 +622,7 @@ impl Gc<Structure> {
         let mut entry = MapEntry {
             offset: 0,
             attrs: attributes,
+            order: 0,
         };
 
         if self.is_unique() {
@@ -652,6 +644,8 @@ impl Gc<Structure> {
             } else {
                 entry.offset = self.get_slots_size() as _;
             }
+            entry.order = map.next_order;
+            map.next_order += 1;
             unwrap_unchecked(map.table.as_mut()).insert(name, entry);
             *offset = entry.offset;
             return map;
@@ -684,6 +678,7 @@ impl Gc<Structure> {
                 MapEntry {
                     offset: slot,
                     attrs: attributes,
+                    order: map.next_order,
                 },
             );
             map.calculated_size = self.get_slots_size() as _;
@@ -693,10 +688,12 @@ impl Gc<Structure> {
                 MapEntry {
                     offset: self.get_slots_size() as _,
                     attrs: attributes,
+                    order: map.next_order,
                 },
             );
             map.calculated_size = self.get_slots_size() as u32 + 1;
         }
+        map.next_order += 1;
         map.transit_count += 1;
         self.transitions.insert(vm, name, attributes, map);
         *offset = map.added.1.offset;
@@ -778,6 +775,7 @@ impl StructureBuilder {
             MapEntry {
                 offset: index as _,
                 attrs: attributes,
+                order: index as _,
             },
         );
     }
@@ -788,6 +786,7 @@ impl StructureBuilder {
         let entry = MapEntry {
             offset: index as _,
             attrs: attributes,
+            order: index as _,
         };
         self.table.insert(symbol, entry);
         entry
@@ -1,6 +1,10 @@
-use crate::gc::cell::{Cell, Trace};
+use super::method_table::*;
+use super::object::{JsObject, ObjectTag};
+use crate::gc::cell::{Cell, Gc, Trace};
 use crate::heap::Allocator;
+use crate::vm::VirtualMachine;
 use std::hash::{Hash, Hasher};
+use std::mem::ManuallyDrop;
 pub const DUMMY_SYMBOL: Symbol = Symbol::Key("");
 /// Runtime symbol type.
 ///
@@ -13,12 +17,33 @@ pub enum Symbol {
     Indexed(u32),
     /// Interned string.
     Key(&'static str),
+    /// Key of a symbol value: the id that is the identity of the symbol and its interned
+    /// description.
+    Unique(u32, Option<&'static str>),
 }
 impl Symbol {
     pub fn as_string(&self) -> String {
         match self {
             Self::Indexed(x) => x.to_string(),
             Self::Key(x) => x.to_string(),
+            Self::Unique(_, x) => x.unwrap_or("").to_string(),
+        }
+    }
+
+    /// Create a new unique symbol key.
+    pub fn new_unique(vm: &mut VirtualMachine, description: Option<&str>) -> Self {
+        vm.interner.unique(description)
+    }
+
+    pub fn is_unique(&self) -> bool {
+        matches!(self, Self::Unique(..))
+    }
+
+    /// `[[Description]]` of a unique symbol key.
+    pub fn description(&self) -> Option<&'static str> {
+        match self {
+            Self::Unique(_, x) => *x,
+            _ => None,
         }
     }
 }
@@ -27,6 +52,7 @@ impl PartialEq for Symbol {
         match (self, other) {
             (Self::Indexed(x), Self::Indexed(y)) => x == y,
             (Self::Key(x), Self::Key(y)) => x.as_ptr() == y.as_ptr(),
+            (Self::Unique(x, _), Self::Unique(y, _)) => x == y,
             _ => false,
         }
     }
@@ -43,6 +69,10 @@ impl Hash for Symbol {
                 state.write_u8(0xfa);
                 state.write_usize(x.as_ptr() as _);
             }
+            Symbol::Unique(x, _) => {
+                state.write_u8(0xfb);
+                state.write_u32(*x);
+            }
         }
     }
 }
@@ -68,6 +98,33 @@ impl JsSymbol {
 unsafe impl Trace for JsSymbol {}
 impl Cell for JsSymbol {}
 
+/// Symbol wrapper object, the result of `Object(symbol)`.
+pub struct JsSymbolObject {
+    sym: Symbol,
+}
+
+impl JsSymbolObject {
+    define_jsclass_with_symbol!(JsObject, Symbol, Symbol);
+
+    pub fn new(vm: &mut VirtualMachine, sym: Symbol) -> Gc<JsObject> {
+        unsafe {
+            let mut jsobject = JsObject::new(
+                vm,
+                vm.global_data().symbol_structure.unwrap(),
+                Self::get_class(),
+                ObjectTag::Symbol,
+            );
+
+            *jsobject.data::<Self>() = ManuallyDrop::new(Self { sym });
+            jsobject
+        }
+    }
+
+    pub fn sym(&self) -> Symbol {
+        self.sym
+    }
+}
+
 macro_rules! default_symbols {
     ($f: ident) => {
         $f! {
@@ -129,7 +186,6 @@ macro_rules! default_symbols {
         buffer,
         byteOffset,
         add,
-        toPrimitive,
         __param_mapping
             }
     };
@@ -150,3 +206,66 @@ macro_rules! def_syms {
 }
 
 default_symbols!(def_syms);
+
+macro_rules! well_known_symbols {
+    ($($name: ident),*) => {
+        /// Ids of the well-known symbols.
+        #[allow(non_camel_case_types)]
+        enum WellKnownSymbol {
+            $($name),*
+        }
+
+        impl Symbol {
+            $(
+                /// Well-known symbol, the `Symbol` constructor property of the same name.
+                #[allow(non_snake_case)]
+                pub fn $name() -> Self {
+                    Self::Unique(
+                        WellKnownSymbol::$name as u32,
+                        Some(concat!("Symbol.", stringify!($name))),
+                    )
+                }
+            )*
+        }
+
+        /// Names of the well-known symbols, which are also the names of the `Symbol`
+        /// constructor properties holding them.
+        pub const WELL_KNOWN_SYMBOLS: &[(&str, fn() -> Symbol)] = &[
+            $((stringify!($name), Symbol::$name)),*
+        ];
+    };
+}
+
+well_known_symbols!(
+    asyncIterator,
+    hasInstance,
+    isConcatSpreadable,
+    iterator,
+    species,
+    toPrimitive,
+    toStringTag,
+    unscopables
+);
+
+impl Symbol {
+    /// Binding of the class scope holding the class constructor. It is not a valid
+    /// identifier so user code can not refer to it.
+    pub fn class_constructor() -> Self {
+        static SYM: &str = "[[ClassConstructor]]";
+        Self::Key(SYM)
+    }
+
+    /// Binding of the class scope holding the prototype object of the class, the home object
+    /// of its constructor and instance methods.
+    pub fn home_object() -> Self {
+        static SYM: &str = "[[HomeObject]]";
+        Self::Key(SYM)
+    }
+
+    /// Binding of the arrow function scope holding `this` of the enclosing function at the
+    /// time the arrow function was created.
+    pub fn lexical_this() -> Self {
+        static SYM: &str = "[[LexicalThis]]";
+        Self::Key(SYM)
+    }
+}
@@ -1,11 +1,16 @@
-use super::{attributes::string_length, object::ObjectTag, slot::*, string::JsStringObject};
+use super::{
+    attributes::{string_indexed, string_length},
+    number::string_to_number,
+    object::ObjectTag,
+    slot::*,
+    string::JsStringObject,
+};
 #[cfg(feature = "compressed-ptrs")]
 use crate::gc::compressed_gc::Compressed;
 use crate::{
     gc::cell::{Cell, Gc, Trace, Tracer},
     vm::VirtualMachine,
 };
-use std::mem::transmute;
 use wtf_rs::{object_offsetof, pure_nan};
 pub const CMP_FALSE: i32 = 0;
 pub const CMP_TRUE: i32 = 1;
@@ -265,7 +270,7 @@ impl JsValue {
     #[inline(always)]
     pub fn as_double(self) -> f64 {
         //  assert!(self.is_double());
-        unsafe { transmute(self.u.as_int64 - Self::DOUBLE_ENCODE_OFFSET as i64) }
+        unsafe { f64::from_bits((self.u.as_int64 - Self::DOUBLE_ENCODE_OFFSET as i64) as u64) }
     }
 
     pub fn is_number(self) -> bool {
@@ -303,12 +308,7 @@ impl JsValue {
         } else if self.is_cell() && self.as_cell().is::<JsString>() {
             unsafe {
                 let s = self.as_cell().downcast_unchecked::<JsString>();
-                if let Ok(n) = s.as_str().parse::<i32>() {
-                    return Ok(n as f64);
-                }
-                Ok(s.as_str()
-                    .parse::<f64>()
-                    .unwrap_or_else(|_| pure_nan::pure_nan()))
+                Ok(string_to_number(&s.to_utf16()))
             }
         } else if self.is_boolean() {
             if self.as_boolean() {
@@ -320,13 +320,12 @@ impl JsValue {
             Ok(0.0)
         } else if self.is_undefined() {
             Ok(pure_nan::pure_nan())
+        } else if self.is_symbol() {
+            let msg = JsString::new(_vm, "Cannot convert a Symbol value to a number");
+            Err(JsValue::new(JsTypeError::new(_vm, msg, None)))
         } else if self.is_cell() && self.as_cell().is::<JsObject>() {
-            let obj = unsafe { self.as_cell().downcast_unchecked::<JsObject>() };
-            match (obj.get_class_value().unwrap().method_table.DefaultValue)(
-                obj,
-                _vm,
-                JsHint::Number,
-            ) {
+            let mut obj = unsafe { self.as_cell().downcast_unchecked::<JsObject>() };
+            match obj.to_primitive(_vm, JsHint::Number) {
                 Ok(val) => val.to_number(_vm),
                 Err(e) => Err(e),
             }
@@ -335,6 +334,30 @@ impl JsValue {
             todo!()
         }
     }
+    /// 7.1.5 ToIntegerOrInfinity
+    pub fn to_integer_or_infinity(self, vm: &mut VirtualMachine) -> Result<f64, JsValue> {
+        let n = self.to_number(vm)?;
+        if n.is_nan() || n == 0.0 {
+            return Ok(0.0);
+        }
+        Ok(n.trunc())
+    }
+    /// 7.1.7 ToUint32
+    pub fn to_uint32(self, vm: &mut VirtualMachine) -> Result<u32, JsValue> {
+        let n = self.to_number(vm)?;
+        if !n.is_finite() {
+            return Ok(0);
+        }
+        Ok(n.trunc().rem_euclid(4294967296.0) as u32)
+    }
+    /// ToString, keeping the result a JS string so lone surrogates survive.
+    pub fn to_js_string(self, vm: &mut VirtualMachine) -> Result<Gc<JsString>, JsValue> {
+        if self.is_string() {
+            return Ok(self.as_string());
+        }
+        let s = self.to_string(vm)?;
+        Ok(JsString::new(vm, s))
+    }
     pub fn is_callable(self) -> bool {
         !self.is_empty()
             && self.is_cell()
@@ -356,7 +379,7 @@ impl JsValue {
             Ok(if self.is_int32() {
                 self.as_int32().to_string()
             } else {
-                self.as_double().to_string()
+                super::number::format_number(self.as_double())
             })
         } else if self.is_null() {
             Ok("null".to_owned())
@@ -367,7 +390,7 @@ impl JsValue {
         } else if self.is_cell() && !self.is_empty() {
             let cell = self.as_cell();
             if let Some(jsstr) = cell.downcast::<JsString>() {
-                return Ok(jsstr.as_str().to_owned());
+                return Ok(jsstr.to_string_lossy().into_owned());
             } else if let Some(mut obj) = cell.downcast::<JsObject>() {
                 return match obj.to_primitive(vm, JsHint::String) {
                     Ok(val) => val.to_string(vm),
@@ -411,7 +434,7 @@ impl JsValue {
     }
 
     pub fn is_symbol(self) -> bool {
-        self.is_cell() && self.as_cell().is::<JsSymbol>()
+        self.is_cell() && !self.is_empty() && self.as_cell().is::<JsSymbol>()
     }
 
     pub fn as_object(self) -> Gc<JsObject> {
@@ -458,6 +481,13 @@ impl JsValue {
                     );
                     return Ok(slot.value());
                 }
+                if let Symbol::Indexed(index) = name {
+                    if index < s.len() {
+                        let nstr = JsString::from_utf16(vm, &[s.at(index)]);
+                        slot.set_1(JsValue::new(nstr), string_indexed(), Some(s.as_dyn()));
+                        return Ok(slot.value());
+                    }
+                }
             }
 
             let proto = self.get_primitive_proto(vm);
@@ -487,7 +517,11 @@ impl JsValue {
         }
 
         if self.is_string() {
-            return Ok(vm.intern(self.as_string().as_str()));
+            let s = self.as_string();
+            if s.is_8bit() {
+                return Ok(vm.intern_or_known_symbol(&s.to_string_lossy()));
+            }
+            return Ok(vm.intern_utf16(s.utf16()));
         }
         if self.is_symbol() {
             return Ok(self.as_symbol().sym());
@@ -516,8 +550,7 @@ impl JsValue {
         name: Symbol,
         slot: &mut Slot,
     ) -> Result<bool, JsValue> {
-        let obj;
-        if !self.is_object() {
+        let obj = if !self.is_object() {
             if self.is_undefined_or_null() {
                 let msg = JsString::new(vm, "null or undefined has no properties");
                 return Err(JsValue::new(JsTypeError::new(vm, msg, None)));
@@ -532,11 +565,18 @@ impl JsValue {
                     );
                     return Ok(true);
                 }
+                if let Symbol::Indexed(index) = name {
+                    if index < s.len() {
+                        let nstr = JsString::from_utf16(vm, &[s.at(index)]);
+                        slot.set_1(JsValue::new(nstr), string_indexed(), Some(s.as_dyn()));
+                        return Ok(true);
+                    }
+                }
             }
-            obj = self.get_primitive_proto(vm);
+            self.get_primitive_proto(vm)
         } else {
-            obj = self.as_object();
-        }
+            self.as_object()
+        };
         Ok(obj.get_property_slot(vm, name, slot))
     }
     pub fn abstract_equal(self, other: JsValue, vm: &mut VirtualMachine) -> Result<bool, JsValue> {
@@ -555,13 +595,13 @@ impl JsValue {
             }
 
             if lhs.is_string() && rhs.is_string() {
-                return Ok(lhs.as_string().as_str() == rhs.as_string().as_str());
+                return Ok(*lhs.as_string() == *rhs.as_string());
             }
             if lhs.is_object() && rhs.is_object() {
                 return Ok(Gc::ptr_eq(self.as_object(), rhs.as_object()));
             }
             if lhs.is_symbol() && rhs.is_symbol() {
-                return Ok((*rhs.as_symbol()) == (*rhs.as_symbol()));
+                return Ok((*lhs.as_symbol()) == (*rhs.as_symbol()));
             }
 
             // conversion phase
@@ -585,11 +625,11 @@ impl JsValue {
                 continue;
             }
 
-            if (lhs.is_string() || lhs.is_number()) && rhs.is_object() {
+            if (lhs.is_string() || lhs.is_number() || lhs.is_symbol()) && rhs.is_object() {
                 rhs = rhs.to_primitive(vm, JsHint::None)?;
                 continue;
             }
-            if lhs.is_object() && (rhs.is_string() || rhs.is_number()) {
+            if lhs.is_object() && (rhs.is_string() || rhs.is_number() || rhs.is_symbol()) {
                 lhs = lhs.to_primitive(vm, JsHint::None)?;
                 continue;
             }
@@ -611,9 +651,13 @@ impl JsValue {
                 self.as_cell().downcast::<JsString>(),
                 other.as_cell().downcast::<JsString>(),
             ) {
-                (Some(x), Some(y)) => return x.as_str() == y.as_str(),
+                (Some(x), Some(y)) => return *x == *y,
                 _ => (),
             }
+            // Symbol values of the same key may live in different cells.
+            if self.is_symbol() && other.is_symbol() {
+                return self.as_symbol().sym() == other.as_symbol().sym();
+            }
         }
 
         unsafe { self.u.as_int64 == other.u.as_int64 }
@@ -645,17 +689,21 @@ impl JsValue {
             return lhsn.is_nan() && rhsn.is_nan();
         }
 
-        if !lhs.is_cell() || !rhs.is_cell() {
+        // the empty value of uninitialized bindings is encoded as a null cell
+        if !lhs.is_cell() || !rhs.is_cell() || lhs.is_empty() || rhs.is_empty() {
             return unsafe { lhs.u.as_int64 == rhs.u.as_int64 };
         }
         if (lhs.is_cell() && lhs.as_cell().is::<JsString>())
             && (rhs.is_cell() && rhs.as_cell().is::<JsString>())
         {
             return unsafe {
-                lhs.as_cell().downcast_unchecked::<JsString>().as_str()
-                    == rhs.as_cell().downcast_unchecked::<JsString>().as_str()
+                *lhs.as_cell().downcast_unchecked::<JsString>()
+                    == *rhs.as_cell().downcast_unchecked::<JsString>()
             };
         }
+        if lhs.is_symbol() && rhs.is_symbol() {
+            return lhs.as_symbol().sym() == rhs.as_symbol().sym();
+        }
         unsafe { lhs.u.as_int64 == rhs.u.as_int64 }
     }
 
@@ -688,7 +736,7 @@ impl JsValue {
         } else if self.is_string() {
             "string"
         } else if self.is_symbol() {
-            "Symbol"
+            "symbol"
         } else if self.is_object() {
             if self.is_callable() {
                 "function"
@@ -713,15 +761,13 @@ impl JsValue {
                 self.as_double() != 0.0 && !self.as_double().is_nan()
             }
         } else if self.is_cell() && self.as_cell().is::<JsString>() {
-            self.as_cell().downcast::<JsString>().unwrap().len() != 0
-        } else if self.is_undefined() {
-            false
-        } else if self.is_null() {
+            !self.as_cell().downcast::<JsString>().unwrap().is_empty()
+        } else if self.is_undefined_or_null() {
             false
         } else if self.is_boolean() {
             self.as_boolean()
         } else {
-            true && !self.is_empty() && !self.is_undefined_or_null()
+            !self.is_empty()
         }
     }
     fn number_compare(x: f64, y: f64) -> i32 {
@@ -766,7 +812,7 @@ impl JsValue {
                 CMP_FALSE
             })
         } else if px.is_string() && py.is_string() {
-            if px.as_string().as_str() < py.as_string().as_str() {
+            if *px.as_string() < *py.as_string() {
                 Ok(CMP_TRUE)
             } else {
                 Ok(CMP_FALSE)
@@ -793,6 +839,13 @@ impl JsValue {
             return Ok(JsStringObject::new(vm, self.as_string()));
         } else if self.is_number() {
             return Ok(super::number::JsNumber::new(vm, self.number()));
+        } else if self.is_boolean() {
+            return Ok(super::boolean::JsBoolean::new(vm, self.as_boolean()));
+        } else if self.is_symbol() {
+            return Ok(super::symbol::JsSymbolObject::new(
+                vm,
+                self.as_symbol().sym(),
+            ));
         }
         todo!()
     }
@@ -832,10 +885,11 @@ impl<T: Cell + ?Sized> From<Gc<T>> for JsValue {
 
 impl From<f64> for JsValue {
     fn from(d: f64) -> Self {
-        if d as i32 as f64 == d {
+        // -0 is kept as a double, int32 has no negative zero.
+        if d as i32 as f64 == d && !(d == 0.0 && d.is_sign_negative()) {
             return Self::new(d as i32);
         }
-        let int = unsafe { std::mem::transmute::<_, i64>(d) };
+        let int = d.to_bits() as i64;
         Self {
             u: EncodedValueDescriptor {
                 as_int64: int + Self::DOUBLE_ENCODE_OFFSET as i64,
@@ -897,6 +951,21 @@ impl PartialEq for JsValue {
     }
 }
 
+impl Cell for JsValue {}
+unsafe impl Trace for JsValue {
+    fn trace(&self, tracer: &mut dyn Tracer) {
+        if self.is_cell() && !self.is_empty() {
+            self.as_cell().trace(tracer);
+        }
+    }
+}
+
+impl Default for JsValue {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -913,19 +982,39 @@ mod tests {
         assert!(val.is_number() && val.is_double());
         assert_eq!(val.as_double(), 42.5);
     }
-}
 
-impl Cell for JsValue {}
-unsafe impl Trace for JsValue {
-    fn trace(&self, tracer: &mut dyn Tracer) {
-        if self.is_cell() && !self.is_empty() {
-            self.as_cell().trace(tracer);
-        }
+    #[test]
+    fn test_lone_surrogate_keys_stay_distinct() {
+        let result = crate::testing::eval_ok(
+            "
+            var high = String.fromCharCode(0xD800);
+            var low = String.fromCharCode(0xDC00);
+            var replacement = String.fromCharCode(0xFFFD);
+            var o = {};
+            o[high] = 1;
+            o[low] = 2;
+            o[replacement] = 3;
+            var keys = Object.keys(o);
+            var s = '';
+            for (var k in o) {
+                s = s + k.charCodeAt(0) + ' ';
+            }
+            result = o[high] + ' ' + o[low] + ' ' + o[replacement] + ' ' + keys.length + ' '
+                + (keys[0] === high) + ' ' + s;
+            ",
+        );
+        assert_eq!(result, "1 2 3 3 true 55296 56320 65533 ");
     }
-}
 
-impl Default for JsValue {
-    fn default() -> Self {
-        Self::empty()
+    #[test]
+    fn test_negative_zero_is_a_double() {
+        assert!(JsValue::new(-0.0f64).is_double());
+        assert!(JsValue::new(0.0f64).is_int32());
+        let source = "result = [1 / -0, 1 / (0 * -1), 1 / (-1 * 0), 0 * 5, -(-2147483648),
+                Object.is(-0, 0), -0 === 0, String(-0)].join();";
+        assert_eq!(
+            crate::testing::eval_ok(source),
+            "-Infinity,-Infinity,-Infinity,0,2147483648,false,true,0"
+        );
     }
 }
@@ -1,33 +1,82 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use crate::runtime::symbol::Symbol;
+use crate::runtime::symbol::{Symbol, WELL_KNOWN_SYMBOLS};
 
 pub struct SymbolTable {
     set: HashSet<&'static str>,
+    /// Keys of the strings that are not valid UTF-16, by their code units. Each one gets a
+    /// key of its own whose text is the lossy conversion of the code units, as the keys
+    /// compare by address the conversion does not merge them.
+    units: HashMap<Vec<u16>, &'static str>,
+    /// Code units of the keys in `units`, by the address of the key.
+    keys: HashMap<usize, Vec<u16>>,
+    /// Id of the next unique symbol, the ids below it are taken by the well-known symbols
+    /// and the unique symbols created so far.
+    next_unique: u32,
 }
 
 impl SymbolTable {
     pub fn new() -> Self {
         Self {
             set: HashSet::new(),
+            units: HashMap::new(),
+            keys: HashMap::new(),
+            next_unique: WELL_KNOWN_SYMBOLS.len() as u32,
         }
     }
+
+    /// Create a new unique symbol key. Its description is interned, so symbols with the same
+    /// description share it.
+    pub fn unique(&mut self, description: Option<&str>) -> Symbol {
+        let id = self.next_unique;
+        self.next_unique = id.checked_add(1).expect("Too many unique symbols");
+        Symbol::Unique(id, description.map(|s| self.intern_str(s)))
+    }
+
+    fn intern_str(&mut self, s: &str) -> &'static str {
+        if let Some(val) = self.set.get(s) {
+            return val;
+        }
+        let val: &'static str = Box::leak(s.to_string().into_boxed_str());
+        self.set.insert(val);
+        val
+    }
     #[allow(clippy::transmute_ptr_to_ptr)]
     pub fn lookup(&mut self, s: impl AsRef<str>) -> Symbol {
         let s = s.as_ref();
         if let Ok(uint) = s.parse::<u32>() {
             return Symbol::Indexed(uint);
         }
-        let s: &'static str = Box::leak(s.to_string().into_boxed_str());
-        if let Some(val) = self.set.get(&s) {
-            Symbol::Key(*val)
-        } else {
-            let str = s.to_string();
-            let s: &'static str = Box::leak(s.to_string().into_boxed_str());
-            let val = s;
-            std::mem::forget(str);
-            self.set.insert(val);
-            Symbol::Key(val)
+        if s.is_empty() {
+            // empty strings all share one dangling pointer, the one of `DUMMY_SYMBOL` too,
+            // so the empty key points into a real static instead.
+            static EMPTY: &str = "\0";
+            return Symbol::Key(&EMPTY[..0]);
+        }
+        Symbol::Key(self.intern_str(s))
+    }
+
+    /// Like [`SymbolTable::lookup`] but for a string of UTF-16 code units, which may contain
+    /// lone surrogates.
+    pub fn lookup_utf16(&mut self, units: &[u16]) -> Symbol {
+        if let Ok(s) = String::from_utf16(units) {
+            return self.lookup(s);
+        }
+        if let Some(key) = self.units.get(units) {
+            return Symbol::Key(key);
+        }
+        let key: &'static str = Box::leak(String::from_utf16_lossy(units).into_boxed_str());
+        self.units.insert(units.to_vec(), key);
+        self.keys.insert(key.as_ptr() as usize, units.to_vec());
+        Symbol::Key(key)
+    }
+
+    /// Code units of `key` if it was interned by [`SymbolTable::lookup_utf16`] from a string
+    /// that is not valid UTF-16.
+    pub fn units(&self, key: Symbol) -> Option<&[u16]> {
+        match key {
+            Symbol::Key(key) => self.keys.get(&(key.as_ptr() as usize)).map(|x| &x[..]),
+            _ => None,
         }
     }
 }
@@ -40,5 +89,10 @@ impl Drop for SymbolTable {
             }
             false
         });
+        for (_, key) in self.units.drain() {
+            unsafe {
+                String::from_raw_parts(key.as_ptr() as *mut u8, key.len(), key.len());
+            }
+        }
     }
 }
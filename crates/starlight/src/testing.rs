@@ -0,0 +1,57 @@
+//! Helpers for tests running JavaScript source on a fresh VM.
+use crate::{
+    gc::handle::Handle,
+    jsrt::jsrt_init,
+    runtime::{arguments::Arguments, value::JsValue},
+    vm::{Options, VirtualMachine, VirtualMachineRef},
+};
+
+/// Run `source` as a script, then the queued promise jobs, and return the global `result`
+/// converted to a string. A thrown value is returned as `Err`, converted the same way.
+///
+/// Top-level `var` bindings live in the scope of the script, so `result` is assigned
+/// without a declaration to end up on the global object.
+pub fn eval(source: &str) -> Result<String, String> {
+    eval_with(Options::default(), source)
+}
+
+/// Like [`eval`] on a VM created with `options`.
+pub fn eval_with(options: Options, source: &str) -> Result<String, String> {
+    let mut vm = VirtualMachine::new(options);
+    jsrt_init(&mut vm);
+    let result = run(&mut vm, source);
+    let is_ok = result.is_ok();
+    // `to_string` may collect garbage, the value is rooted meanwhile.
+    let value = Handle::new(vm.space(), result.unwrap_or_else(|error| error));
+    let string = value
+        .to_string(&mut vm)
+        .unwrap_or_else(|_| "<error>".to_string());
+    drop(value);
+    VirtualMachineRef::dispose(vm);
+
+    if is_ok {
+        Ok(string)
+    } else {
+        Err(string)
+    }
+}
+
+/// Like [`eval`] but panics with the thrown value if the script throws.
+pub fn eval_ok(source: &str) -> String {
+    match eval(source) {
+        Ok(result) => result,
+        Err(error) => panic!("uncaught exception: {}\nscript: {}", error, source),
+    }
+}
+
+fn run(vm: &mut VirtualMachine, source: &str) -> Result<JsValue, JsValue> {
+    let func = vm.compile(false, source, "<test>")?;
+    let mut func = Handle::new(vm.space(), func);
+    let args = Arguments::new(vm, JsValue::undefined(), 0);
+    let mut args = Handle::new(vm.space(), args);
+    args.this = JsValue::new(vm.global_object());
+    func.as_function_mut().call(vm, &mut args)?;
+    vm.run_microtasks()?;
+    let name = vm.intern("result");
+    vm.global_object().get(vm, name)
+}
@@ -1,10 +1,20 @@
 use super::runtime::attributes::*;
 use crate::jsrt::{
-    array::{array_from, array_join, array_of, array_pop, array_push},
+    array::*,
+    boolean::*,
     error::range_error_constructor,
     function::{function_bind, function_prototype, function_to_string},
+    generator::{generator_next, generator_return, generator_throw},
+    iterator::{array_iterator_next, iterator_prototype_iterator, string_iterator_next},
+    json::{json_parse, json_stringify, JsJSON},
+    math::*,
+    number::*,
     object::*,
+    promise::*,
+    string::*,
+    symbol::*,
 };
+use std::collections::{HashMap, VecDeque};
 use std::{fmt::Display, io::Write, sync::RwLock};
 use std::{ops::DerefMut, ptr::null_mut};
 use swc_common::{
@@ -13,7 +23,7 @@ use swc_common::{
 };
 use swc_common::{FileName, SourceMap};
 use swc_ecmascript::parser::*;
-use wtf_rs::{object_offsetof, unwrap_unchecked};
+use wtf_rs::{object_offsetof, unwrap_unchecked, weak_random::WeakRandom};
 
 #[derive(Clone, Default)]
 pub(crate) struct BufferedError(std::sync::Arc<RwLock<String>>);
@@ -59,35 +69,36 @@ use crate::{
     jsrt::{
         array::{array_ctor, array_is_array},
         error::{
-            error_constructor, error_to_string, eval_error_constructor,
-            reference_error_constructor, type_error_constructor,
+            aggregate_error_constructor, error_constructor, error_to_string,
+            eval_error_constructor, reference_error_constructor, syntax_error_constructor,
+            type_error_constructor,
         },
     },
     runtime::{
         arguments::Arguments,
-        error::{JsError, JsEvalError, JsReferenceError, JsTypeError},
-        function::{JsNativeFunction, JsVMFunction},
+        error::{
+            JsAggregateError, JsError, JsEvalError, JsReferenceError, JsSyntaxError, JsTypeError,
+        },
+        function::{JsAPI, JsNativeFunction, JsVMFunction},
         global::JsGlobal,
         object::{JsObject, ObjectTag},
-        property_descriptor::DataDescriptor,
+        promise::{run_job, Job},
+        property_descriptor::{AccessorDescriptor, DataDescriptor},
         string::JsString,
         structure::Structure,
-        symbol::Symbol,
+        symbol::{JsSymbol, Symbol, WELL_KNOWN_SYMBOLS},
         value::JsValue,
     },
     symbol_table::SymbolTable,
 };
 use lexer::Lexer;
 
+#[derive(Default)]
 pub struct Options {
     pub dump_bytecode: bool,
-}
-impl Default for Options {
-    fn default() -> Self {
-        Self {
-            dump_bytecode: false,
-        }
-    }
+    /// Seed of the generator behind `Math.random`. A fixed seed makes runs reproducible,
+    /// `None` seeds it from the OS.
+    pub random_seed: Option<u32>,
 }
 
 #[repr(C)]
@@ -101,12 +112,18 @@ pub struct VirtualMachine {
     pub(crate) stack_end: *mut JsValue,
     pub(crate) stack: *mut JsValue,
     space: *mut Heap,
-    interner: SymbolTable,
+    pub(crate) interner: SymbolTable,
     stacktrace: Option<String>,
     global_data: Box<GlobalData>,
     #[cfg(not(miri))]
     stack_map: memmap2::MmapMut,
     pub(crate) frame: *mut FrameBase,
+    /// Pending promise jobs, run by [`VirtualMachine::run_microtasks`].
+    job_queue: VecDeque<Job>,
+    /// Generator behind `Math.random`.
+    weak_random: WeakRandom,
+    /// GlobalSymbolRegistry, the symbols returned by `Symbol.for` by their key.
+    symbol_registry: HashMap<String, Symbol>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
@@ -212,7 +229,14 @@ impl VirtualMachine {
         };
         self.space().defer_gc();
         let vmref = VirtualMachineRef(self);
-        let mut code = Handle::new(self.space(), Compiler::compile_script(vmref, &script));
+        let code = match Compiler::compile_script(vmref, &script) {
+            Ok(code) => code,
+            Err(error) => {
+                self.space().undefer_gc();
+                return Err(error);
+            }
+        };
+        let mut code = Handle::new(self.space(), code);
         code.strict = code.strict || force_strict;
         code.name = self.intern_or_known_symbol(name);
 
@@ -253,7 +277,8 @@ impl VirtualMachine {
             };
             let vmref = VirtualMachineRef(self);
 
-            let mut code = Handle::new(self.space(), Compiler::compile_script(vmref, &script));
+            let code = Compiler::compile_script(vmref, &script)?;
+            let mut code = Handle::new(self.space(), code);
             code.strict = code.strict || force_strict;
             code.display_to(&mut OutBuf).unwrap();
 
@@ -272,6 +297,27 @@ impl VirtualMachine {
         match sym {
             Symbol::Key(x) => unsafe { (*x).to_string() },
             Symbol::Indexed(x) => x.to_string(),
+            Symbol::Unique(_, x) => x.unwrap_or("").to_string(),
+        }
+    }
+
+    /// Symbol of the GlobalSymbolRegistry with the given key, created on first use.
+    pub(crate) fn symbol_for(&mut self, key: String) -> Symbol {
+        if let Some(sym) = self.symbol_registry.get(&key) {
+            return *sym;
+        }
+        let sym = Symbol::new_unique(self, Some(&key));
+        self.symbol_registry.insert(key, sym);
+        sym
+    }
+
+    /// Key of `sym` in the GlobalSymbolRegistry, if it was created by `Symbol.for`.
+    pub(crate) fn symbol_key_for(&self, sym: Symbol) -> Option<&'static str> {
+        let key = sym.description()?;
+        if self.symbol_registry.get(key) == Some(&sym) {
+            Some(key)
+        } else {
+            None
         }
     }
 
@@ -312,7 +358,7 @@ impl VirtualMachine {
             "next" => Symbol::next(),
             "eval" => Symbol::eval(),
             "done" => Symbol::done(),
-            "configrable" => Symbol::configurable(),
+            "configurable" => Symbol::configurable(),
             "writable" => Symbol::writable(),
             "enumerable" => Symbol::enumerable(),
             "lastIndex" => Symbol::lastIndex(),
@@ -325,7 +371,6 @@ impl VirtualMachine {
             "undefined" => Symbol::undefined(),
             "compare" => Symbol::compare(),
             "join" => Symbol::join(),
-            "toPrimitive" => Symbol::toPrimitive(),
             _ => self.intern(s),
         }
     }
@@ -333,6 +378,25 @@ impl VirtualMachine {
         val.into_symbol(self)
     }
 
+    /// Intern a string of UTF-16 code units. Strings with lone surrogates are kept apart
+    /// from each other and from their lossy conversion.
+    pub fn intern_utf16(&mut self, units: &[u16]) -> Symbol {
+        match String::from_utf16(units) {
+            Ok(s) => self.intern_or_known_symbol(&s),
+            Err(_) => self.interner.lookup_utf16(units),
+        }
+    }
+
+    /// String value of the non-symbol property key `key`, the inverse of `ToPropertyKey`.
+    pub fn key_string(&mut self, key: Symbol) -> Gc<JsString> {
+        if let Some(units) = self.interner.units(key) {
+            let units = units.to_vec();
+            return JsString::from_utf16(self, &units);
+        }
+        let desc = self.description(key);
+        JsString::new(self, desc)
+    }
+
     pub fn push(&mut self, val: JsValue) {
         unsafe {
             if self.stack == self.stack_end {
@@ -350,9 +414,8 @@ impl VirtualMachine {
                 panic!("Stack underflow");
             }
             self.stack = self.stack.sub(1);
-            let val = self.stack.read();
 
-            val
+            self.stack.read()
         }
     }
     #[inline(always)]
@@ -378,6 +441,38 @@ impl VirtualMachine {
     pub fn global_data(&self) -> &GlobalData {
         &self.global_data
     }
+
+    /// 9.5.4 HostEnqueuePromiseJob
+    pub(crate) fn enqueue_job(&mut self, job: Job) {
+        self.job_queue.push_back(job);
+    }
+
+    /// Run queued promise jobs until the queue is empty, including jobs enqueued while
+    /// running. A job that throws does not stop the others: the error of the first one is
+    /// returned once the queue is drained, errors of later ones are reported on stderr.
+    pub fn run_microtasks(&mut self) -> Result<(), JsValue> {
+        let mut first_error = Handle::new(self.space(), None);
+        // The job stays in the traced queue while it runs, so its handler, capability and
+        // argument survive collections triggered by the job itself.
+        while let Some(&job) = self.job_queue.front() {
+            let result = run_job(self, job);
+            self.job_queue.pop_front();
+            if let Err(error) = result {
+                if first_error.is_none() {
+                    *first_error = Some(error);
+                } else {
+                    let msg = error
+                        .to_string(self)
+                        .unwrap_or_else(|_| "cannot get error".to_string());
+                    eprintln!("Unhandled: {}", msg);
+                }
+            }
+        }
+        match *first_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
     pub fn new(opts: Options) -> VirtualMachineRef {
         let space = Heap::new();
 
@@ -405,10 +500,11 @@ impl VirtualMachine {
             stack_end = unsafe { stack_start.add(16 * 1024) };
             std::mem::forget(stack);
         };
+        let weak_random = WeakRandom::new(opts.random_seed);
         let mut this = VirtualMachineRef(Box::into_raw(Box::new(Self {
             space: Box::into_raw(space),
             stack: stack_start,
-            stack_start: stack_start,
+            stack_start,
             #[cfg(not(miri))]
             stack_map: stack,
             options: opts,
@@ -423,6 +519,9 @@ impl VirtualMachine {
             stacktrace: None,
             stack_end,
             acc: JsValue::undefined(),
+            job_queue: VecDeque::new(),
+            weak_random,
+            symbol_registry: HashMap::new(),
         })));
         let c = this;
         this.space().add_constraint(SimpleMarkingConstraint::new(
@@ -433,6 +532,7 @@ impl VirtualMachine {
                 (*vm).global_object.trace(tracer);
                 (*vm).thrown_error.trace(tracer);
                 (*vm).return_value.trace(tracer);
+                (*vm).job_queue.iter().for_each(|job| job.trace(tracer));
                 unsafe {
                     let mut current = (*vm).frame as *const FrameBase;
                     while !current.is_null() {
@@ -440,7 +540,7 @@ impl VirtualMachine {
                         current = (*current).prev;
                     }
                     let mut scan = vm.stack_start;
-                    while scan < vm.stack.sub(1) {
+                    while scan < vm.stack {
                         let val = scan.read();
                         if !val.is_empty() {
                             val.trace(tracer);
@@ -464,6 +564,10 @@ impl VirtualMachine {
             .empty_object_struct
             .unwrap()
             .change_prototype_with_no_transition(proto);
+        this.global_data
+            .normal_arguments_structure
+            .unwrap()
+            .change_prototype_with_no_transition(proto);
 
         let name = this.intern_or_known_symbol("Object");
         let mut obj_constructor = JsNativeFunction::new(&mut this, name, object_constructor, 1);
@@ -487,12 +591,46 @@ impl VirtualMachine {
             &*DataDescriptor::new(JsValue::new(obj_to_string), W | C),
             false,
         );
+        let methods: [(&str, JsAPI, u32); 9] = [
+            ("__defineGetter__", object_define_getter, 2),
+            ("__defineSetter__", object_define_setter, 2),
+            ("__lookupGetter__", object_lookup_getter, 1),
+            ("__lookupSetter__", object_lookup_setter, 1),
+            ("hasOwnProperty", object_has_own_property, 1),
+            ("isPrototypeOf", object_is_prototype_of, 1),
+            ("propertyIsEnumerable", object_property_is_enumerable, 1),
+            ("toLocaleString", object_to_locale_string, 0),
+            ("valueOf", object_value_of, 0),
+        ];
+        for (name, f, argc) in methods.iter() {
+            let name = this.intern_or_known_symbol(name);
+            let f = JsNativeFunction::new(&mut this, name, *f, *argc);
+            let _ = proto.define_own_property(
+                &mut this,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+        let name = this.intern("__proto__");
+        let getter = JsNativeFunction::new(&mut this, name, object_proto_getter, 0);
+        let setter = JsNativeFunction::new(&mut this, name, object_proto_setter, 1);
+        let _ = proto.define_own_property(
+            &mut this,
+            name,
+            &*AccessorDescriptor::new(JsValue::new(getter), JsValue::new(setter), C),
+            false,
+        );
+        this.init_object(obj_constructor);
         let name = this.intern("Object");
         this.global_data
             .empty_object_struct
             .unwrap()
             .change_prototype_with_no_transition(proto);
         this.global_data.number_structure = Some(Structure::new_indexed(&mut this, None, false));
+        this.global_data.boolean_structure = Some(Structure::new_indexed(&mut this, None, false));
+        this.global_data.symbol_structure = Some(Structure::new_indexed(&mut this, None, false));
+        this.global_data.string_structure = Some(Structure::new_indexed(&mut this, None, false));
         this.init_error(proto);
         let _ = this.global_object().define_own_property(
             &mut this,
@@ -502,6 +640,14 @@ impl VirtualMachine {
         );
         this.init_array(proto);
         this.init_func(proto);
+        this.init_string(proto);
+        this.init_number(proto);
+        this.init_boolean(proto);
+        this.init_symbol(proto);
+        this.init_iterator(proto);
+        this.init_promise(proto);
+        this.init_math(proto);
+        this.init_json(proto);
         this.space().undefer_gc();
         //this.space().gc();
         this
@@ -513,6 +659,17 @@ impl VirtualMachine {
         unsafe { &mut *self.space }
     }
 
+    /// Reseed the generator behind `Math.random`, so the numbers it returns from now on
+    /// only depend on `seed`.
+    pub fn set_random_seed(&mut self, seed: u32) {
+        self.weak_random.set_seed(seed);
+    }
+
+    /// Next number of the `Math.random` sequence, in `[0, 1)`.
+    pub(crate) fn random(&mut self) -> f64 {
+        self.weak_random.get()
+    }
+
     pub fn space_offset() -> usize {
         object_offsetof!(Self, space)
     }
@@ -541,7 +698,49 @@ impl VirtualMachine {
             self.global_object.unwrap()
         }
     }
-    fn init_object(&mut self) {}
+    fn init_object(&mut self, mut constructor: Gc<JsObject>) {
+        let statics: [(&str, JsAPI, u32); 21] = [
+            ("assign", object_assign, 2),
+            ("create", object_create, 2),
+            ("defineProperties", object_define_properties, 2),
+            ("defineProperty", object_define_property, 3),
+            ("entries", object_entries, 1),
+            ("freeze", object_freeze, 1),
+            ("fromEntries", object_from_entries, 1),
+            (
+                "getOwnPropertyDescriptor",
+                object_get_own_property_descriptor,
+                2,
+            ),
+            (
+                "getOwnPropertyDescriptors",
+                object_get_own_property_descriptors,
+                1,
+            ),
+            ("getOwnPropertyNames", object_get_own_property_names, 1),
+            ("getOwnPropertySymbols", object_get_own_property_symbols, 1),
+            ("getPrototypeOf", object_get_prototype_of, 1),
+            ("is", object_is, 2),
+            ("isExtensible", object_is_extensible, 1),
+            ("isFrozen", object_is_frozen, 1),
+            ("isSealed", object_is_sealed, 1),
+            ("keys", object_keys, 1),
+            ("preventExtensions", object_prevent_extensions, 1),
+            ("seal", object_seal, 1),
+            ("setPrototypeOf", object_set_prototype_of, 2),
+            ("values", object_values, 1),
+        ];
+        for (name, f, argc) in statics.iter() {
+            let name = self.intern(*name);
+            let f = JsNativeFunction::new(self, name, *f, *argc);
+            let _ = constructor.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+    }
     fn init_func(&mut self, obj_proto: Gc<JsObject>) {
         let structure = Structure::new_unique_indexed(self, Some(obj_proto), false);
         let name = self.intern_or_known_symbol("Function");
@@ -552,6 +751,12 @@ impl VirtualMachine {
             .change_prototype_with_no_transition(func_proto);
         self.global_data.func_prototype = Some(func_proto);
         let mut func_ctor = JsNativeFunction::new(self, name, function_prototype, 1);
+        let _ = func_ctor.define_own_property(
+            self,
+            Symbol::prototype(),
+            &*DataDescriptor::new(JsValue::new(func_proto), NONE),
+            false,
+        );
 
         let _ = self
             .global_object()
@@ -583,6 +788,637 @@ impl VirtualMachine {
             false,
         );
     }
+    fn init_string(&mut self, obj_proto: Gc<JsObject>) {
+        let structure = Structure::new_unique_with_proto(self, Some(obj_proto), false);
+        let mut proto = JsObject::new(self, structure, JsObject::get_class(), ObjectTag::Ordinary);
+        self.global_data
+            .string_structure
+            .unwrap()
+            .change_prototype_with_no_transition(proto);
+        self.global_data.string_prototype = Some(proto);
+
+        let name = self.intern("String");
+        let mut constructor = JsNativeFunction::new(self, name, string_constructor, 1);
+        let _ = constructor.define_own_property(
+            self,
+            Symbol::prototype(),
+            &*DataDescriptor::new(JsValue::new(proto), NONE),
+            false,
+        );
+        let _ = proto.define_own_property(
+            self,
+            Symbol::constructor(),
+            &*DataDescriptor::new(JsValue::new(constructor), W | C),
+            false,
+        );
+
+        let methods: [(&str, JsAPI, u32); 26] = [
+            ("at", string_at, 1),
+            ("charAt", string_char_at, 1),
+            ("charCodeAt", string_char_code_at, 1),
+            ("codePointAt", string_code_point_at, 1),
+            ("concat", string_concat, 1),
+            ("endsWith", string_ends_with, 1),
+            ("includes", string_includes, 1),
+            ("indexOf", string_index_of, 1),
+            ("lastIndexOf", string_last_index_of, 1),
+            ("padEnd", string_pad_end, 1),
+            ("padStart", string_pad_start, 1),
+            ("repeat", string_repeat, 1),
+            ("slice", string_slice, 2),
+            ("split", string_split, 2),
+            ("startsWith", string_starts_with, 1),
+            ("substr", string_substr, 2),
+            ("substring", string_substring, 2),
+            ("toLocaleLowerCase", string_to_lower_case, 0),
+            ("toLocaleUpperCase", string_to_upper_case, 0),
+            ("toLowerCase", string_to_lower_case, 0),
+            ("toString", string_to_string, 0),
+            ("toUpperCase", string_to_upper_case, 0),
+            ("trim", string_trim, 0),
+            ("trimEnd", string_trim_end, 0),
+            ("trimStart", string_trim_start, 0),
+            ("valueOf", string_value_of, 0),
+        ];
+        for (name, f, argc) in methods.iter() {
+            let name = self.intern_or_known_symbol(name);
+            let f = JsNativeFunction::new(self, name, *f, *argc);
+            let _ = proto.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+        // Annex B names, the very same functions as trimStart and trimEnd.
+        for (alias, name) in [("trimLeft", "trimStart"), ("trimRight", "trimEnd")].iter() {
+            let name = self.intern(*name);
+            let f = proto
+                .get(self, name)
+                .unwrap_or_else(|_| JsValue::undefined());
+            let alias = self.intern(*alias);
+            let _ = proto.define_own_property(self, alias, &*DataDescriptor::new(f, W | C), false);
+        }
+
+        let statics: [(&str, JsAPI, u32); 3] = [
+            ("fromCharCode", string_from_char_code, 1),
+            ("fromCodePoint", string_from_code_point, 1),
+            ("raw", string_raw, 1),
+        ];
+        for (name, f, argc) in statics.iter() {
+            let name = self.intern(*name);
+            let f = JsNativeFunction::new(self, name, *f, *argc);
+            let _ = constructor.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+
+        let name = self.intern("String");
+        let _ = self.global_object().define_own_property(
+            self,
+            name,
+            &*DataDescriptor::new(JsValue::new(constructor), W | C),
+            false,
+        );
+    }
+    fn init_number(&mut self, obj_proto: Gc<JsObject>) {
+        let structure = Structure::new_unique_with_proto(self, Some(obj_proto), false);
+        let mut proto = JsObject::new(self, structure, JsObject::get_class(), ObjectTag::Ordinary);
+        self.global_data
+            .number_structure
+            .unwrap()
+            .change_prototype_with_no_transition(proto);
+        self.global_data.number_prototype = Some(proto);
+
+        let name = self.intern("Number");
+        let mut constructor = JsNativeFunction::new(self, name, number_constructor, 1);
+        let _ = constructor.define_own_property(
+            self,
+            Symbol::prototype(),
+            &*DataDescriptor::new(JsValue::new(proto), NONE),
+            false,
+        );
+        let _ = proto.define_own_property(
+            self,
+            Symbol::constructor(),
+            &*DataDescriptor::new(JsValue::new(constructor), W | C),
+            false,
+        );
+
+        let methods: [(&str, JsAPI, u32); 6] = [
+            ("toExponential", number_to_exponential, 1),
+            ("toFixed", number_to_fixed, 1),
+            ("toLocaleString", number_to_locale_string, 0),
+            ("toPrecision", number_to_precision, 1),
+            ("toString", number_to_string, 1),
+            ("valueOf", number_value_of, 0),
+        ];
+        for (name, f, argc) in methods.iter() {
+            let name = self.intern_or_known_symbol(name);
+            let f = JsNativeFunction::new(self, name, *f, *argc);
+            let _ = proto.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+
+        let constants: [(&str, f64); 8] = [
+            ("EPSILON", f64::EPSILON),
+            ("MAX_SAFE_INTEGER", 9007199254740991.0),
+            ("MAX_VALUE", f64::MAX),
+            ("MIN_SAFE_INTEGER", -9007199254740991.0),
+            ("MIN_VALUE", 5e-324),
+            ("NaN", f64::NAN),
+            ("NEGATIVE_INFINITY", f64::NEG_INFINITY),
+            ("POSITIVE_INFINITY", f64::INFINITY),
+        ];
+        for (name, value) in constants.iter() {
+            let name = self.intern_or_known_symbol(name);
+            let _ = constructor.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(*value), NONE),
+                false,
+            );
+        }
+
+        let statics: [(&str, JsAPI, u32); 4] = [
+            ("isFinite", number_is_finite, 1),
+            ("isInteger", number_is_integer, 1),
+            ("isNaN", number_is_nan, 1),
+            ("isSafeInteger", number_is_safe_integer, 1),
+        ];
+        for (name, f, argc) in statics.iter() {
+            let name = self.intern(*name);
+            let f = JsNativeFunction::new(self, name, *f, *argc);
+            let _ = constructor.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+
+        let globals: [(&str, JsAPI, u32); 4] = [
+            ("isFinite", is_finite, 1),
+            ("isNaN", is_nan, 1),
+            ("parseFloat", parse_float, 1),
+            ("parseInt", parse_int, 2),
+        ];
+        for (name, f, argc) in globals.iter() {
+            let name = self.intern(*name);
+            let f = JsNativeFunction::new(self, name, *f, *argc);
+            let _ = self.global_object().define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+        // Number.parseFloat and Number.parseInt are the very same functions as the globals.
+        for name in ["parseFloat", "parseInt"].iter() {
+            let name = self.intern(*name);
+            let f = self
+                .global_object()
+                .get(self, name)
+                .unwrap_or_else(|_| JsValue::undefined());
+            let _ =
+                constructor.define_own_property(self, name, &*DataDescriptor::new(f, W | C), false);
+        }
+
+        let name = self.intern("Number");
+        let _ = self.global_object().define_own_property(
+            self,
+            name,
+            &*DataDescriptor::new(JsValue::new(constructor), W | C),
+            false,
+        );
+    }
+    fn init_boolean(&mut self, obj_proto: Gc<JsObject>) {
+        let structure = Structure::new_unique_with_proto(self, Some(obj_proto), false);
+        let mut proto = JsObject::new(self, structure, JsObject::get_class(), ObjectTag::Ordinary);
+        self.global_data
+            .boolean_structure
+            .unwrap()
+            .change_prototype_with_no_transition(proto);
+        self.global_data.boolean_prototype = Some(proto);
+
+        let name = self.intern("Boolean");
+        let mut constructor = JsNativeFunction::new(self, name, boolean_constructor, 1);
+        let _ = constructor.define_own_property(
+            self,
+            Symbol::prototype(),
+            &*DataDescriptor::new(JsValue::new(proto), NONE),
+            false,
+        );
+        let _ = proto.define_own_property(
+            self,
+            Symbol::constructor(),
+            &*DataDescriptor::new(JsValue::new(constructor), W | C),
+            false,
+        );
+
+        let methods: [(&str, JsAPI, u32); 2] = [
+            ("toString", boolean_to_string, 0),
+            ("valueOf", boolean_value_of, 0),
+        ];
+        for (name, f, argc) in methods.iter() {
+            let name = self.intern_or_known_symbol(name);
+            let f = JsNativeFunction::new(self, name, *f, *argc);
+            let _ = proto.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+
+        let _ = self.global_object().define_own_property(
+            self,
+            name,
+            &*DataDescriptor::new(JsValue::new(constructor), W | C),
+            false,
+        );
+    }
+    fn init_symbol(&mut self, obj_proto: Gc<JsObject>) {
+        let structure = Structure::new_unique_with_proto(self, Some(obj_proto), false);
+        let mut proto = JsObject::new(self, structure, JsObject::get_class(), ObjectTag::Ordinary);
+        self.global_data
+            .symbol_structure
+            .unwrap()
+            .change_prototype_with_no_transition(proto);
+        self.global_data.symbol_prototype = Some(proto);
+
+        let name = self.intern("Symbol");
+        let mut constructor = JsNativeFunction::new(self, name, symbol_constructor, 0);
+        let _ = constructor.define_own_property(
+            self,
+            Symbol::prototype(),
+            &*DataDescriptor::new(JsValue::new(proto), NONE),
+            false,
+        );
+        let _ = proto.define_own_property(
+            self,
+            Symbol::constructor(),
+            &*DataDescriptor::new(JsValue::new(constructor), W | C),
+            false,
+        );
+
+        let statics: [(&str, JsAPI, u32); 2] =
+            [("for", symbol_for, 1), ("keyFor", symbol_key_for, 1)];
+        for (name, f, argc) in statics.iter() {
+            let name = self.intern(*name);
+            let f = JsNativeFunction::new(self, name, *f, *argc);
+            let _ = constructor.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+        for (name, sym) in WELL_KNOWN_SYMBOLS.iter() {
+            let name = self.intern(*name);
+            let sym = JsSymbol::new(self, sym());
+            let _ = constructor.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(sym), NONE),
+                false,
+            );
+        }
+
+        let methods: [(&str, JsAPI, u32); 2] = [
+            ("toString", symbol_to_string, 0),
+            ("valueOf", symbol_value_of, 0),
+        ];
+        for (name, f, argc) in methods.iter() {
+            let name = self.intern_or_known_symbol(name);
+            let f = JsNativeFunction::new(self, name, *f, *argc);
+            let _ = proto.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+        let name = self.intern("description");
+        let getter = JsNativeFunction::new(self, name, symbol_description, 0);
+        let _ = proto.define_own_property(
+            self,
+            name,
+            &*AccessorDescriptor::new(JsValue::new(getter), JsValue::undefined(), C),
+            false,
+        );
+        let name = self.intern("[Symbol.toPrimitive]");
+        let f = JsNativeFunction::new(self, name, symbol_to_primitive, 1);
+        let _ = proto.define_own_property(
+            self,
+            Symbol::toPrimitive(),
+            &*DataDescriptor::new(JsValue::new(f), C),
+            false,
+        );
+        let tag = JsString::new(self, "Symbol");
+        let _ = proto.define_own_property(
+            self,
+            Symbol::toStringTag(),
+            &*DataDescriptor::new(JsValue::new(tag), C),
+            false,
+        );
+
+        let name = self.intern("Symbol");
+        let _ = self.global_object().define_own_property(
+            self,
+            name,
+            &*DataDescriptor::new(JsValue::new(constructor), W | C),
+            false,
+        );
+    }
+    fn init_iterator(&mut self, obj_proto: Gc<JsObject>) {
+        let structure = Structure::new_unique_with_proto(self, Some(obj_proto), false);
+        let mut iter_proto =
+            JsObject::new(self, structure, JsObject::get_class(), ObjectTag::Ordinary);
+        let name = self.intern("[Symbol.iterator]");
+        let f = JsNativeFunction::new(self, name, iterator_prototype_iterator, 0);
+        let _ = iter_proto.define_own_property(
+            self,
+            Symbol::iterator(),
+            &*DataDescriptor::new(JsValue::new(f), W | C),
+            false,
+        );
+        self.global_data.iterator_prototype = Some(iter_proto);
+
+        let structure = Structure::new_unique_with_proto(self, Some(iter_proto), false);
+        let mut proto = JsObject::new(self, structure, JsObject::get_class(), ObjectTag::Ordinary);
+        let f = JsNativeFunction::new(self, Symbol::next(), array_iterator_next, 0);
+        let _ = proto.define_own_property(
+            self,
+            Symbol::next(),
+            &*DataDescriptor::new(JsValue::new(f), W | C),
+            false,
+        );
+        self.global_data.array_iterator_structure =
+            Some(Structure::new_indexed(self, Some(proto), false));
+        self.global_data.array_iterator_prototype = Some(proto);
+
+        let structure = Structure::new_unique_with_proto(self, Some(iter_proto), false);
+        let mut proto = JsObject::new(self, structure, JsObject::get_class(), ObjectTag::Ordinary);
+        let f = JsNativeFunction::new(self, Symbol::next(), string_iterator_next, 0);
+        let _ = proto.define_own_property(
+            self,
+            Symbol::next(),
+            &*DataDescriptor::new(JsValue::new(f), W | C),
+            false,
+        );
+        self.global_data.string_iterator_structure =
+            Some(Structure::new_indexed(self, Some(proto), false));
+        self.global_data.string_iterator_prototype = Some(proto);
+
+        let structure = Structure::new_unique_with_proto(self, Some(iter_proto), false);
+        let mut proto = JsObject::new(self, structure, JsObject::get_class(), ObjectTag::Ordinary);
+        let f = JsNativeFunction::new(self, Symbol::next(), generator_next, 1);
+        let _ = proto.define_own_property(
+            self,
+            Symbol::next(),
+            &*DataDescriptor::new(JsValue::new(f), W | C),
+            false,
+        );
+        let name = self.intern("return");
+        let f = JsNativeFunction::new(self, name, generator_return, 1);
+        let _ = proto.define_own_property(
+            self,
+            name,
+            &*DataDescriptor::new(JsValue::new(f), W | C),
+            false,
+        );
+        let name = self.intern("throw");
+        let f = JsNativeFunction::new(self, name, generator_throw, 1);
+        let _ = proto.define_own_property(
+            self,
+            name,
+            &*DataDescriptor::new(JsValue::new(f), W | C),
+            false,
+        );
+        self.global_data.generator_structure =
+            Some(Structure::new_indexed(self, Some(proto), false));
+        self.global_data.generator_prototype = Some(proto);
+
+        let mut array_proto = self.global_data.array_prototype.unwrap();
+        let name = self.intern("values");
+        let values = JsNativeFunction::new(self, name, array_values, 0);
+        let _ = array_proto.define_own_property(
+            self,
+            name,
+            &*DataDescriptor::new(JsValue::new(values), W | C),
+            false,
+        );
+        let _ = array_proto.define_own_property(
+            self,
+            Symbol::iterator(),
+            &*DataDescriptor::new(JsValue::new(values), W | C),
+            false,
+        );
+        self.global_data.array_values = Some(values);
+        let methods: [(&str, JsAPI, u32); 2] =
+            [("entries", array_entries, 0), ("keys", array_keys, 0)];
+        for (name, f, argc) in methods.iter() {
+            let name = self.intern(*name);
+            let f = JsNativeFunction::new(self, name, *f, *argc);
+            let _ = array_proto.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+
+        let mut string_proto = self.global_data.string_prototype.unwrap();
+        let name = self.intern("[Symbol.iterator]");
+        let f = JsNativeFunction::new(self, name, string_iterator, 0);
+        let _ = string_proto.define_own_property(
+            self,
+            Symbol::iterator(),
+            &*DataDescriptor::new(JsValue::new(f), W | C),
+            false,
+        );
+    }
+
+    fn init_promise(&mut self, obj_proto: Gc<JsObject>) {
+        let structure = Structure::new_unique_with_proto(self, Some(obj_proto), false);
+        let mut proto = JsObject::new(self, structure, JsObject::get_class(), ObjectTag::Ordinary);
+        self.global_data.promise_structure = Some(Structure::new_indexed(self, Some(proto), false));
+        self.global_data.promise_prototype = Some(proto);
+
+        let name = self.intern("Promise");
+        let mut constructor = JsNativeFunction::new(self, name, promise_constructor, 1);
+        self.global_data.promise_constructor = Some(constructor);
+        let _ = constructor.define_own_property(
+            self,
+            Symbol::prototype(),
+            &*DataDescriptor::new(JsValue::new(proto), NONE),
+            false,
+        );
+        let _ = proto.define_own_property(
+            self,
+            Symbol::constructor(),
+            &*DataDescriptor::new(JsValue::new(constructor), W | C),
+            false,
+        );
+
+        let methods: [(&str, JsAPI, u32); 3] = [
+            ("then", promise_then, 2),
+            ("catch", promise_catch, 1),
+            ("finally", promise_finally, 1),
+        ];
+        for (name, f, argc) in methods.iter() {
+            let name = self.intern(*name);
+            let f = JsNativeFunction::new(self, name, *f, *argc);
+            let _ = proto.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+
+        let statics: [(&str, JsAPI); 6] = [
+            ("resolve", promise_resolve_static),
+            ("reject", promise_reject_static),
+            ("all", promise_all),
+            ("allSettled", promise_all_settled),
+            ("any", promise_any),
+            ("race", promise_race),
+        ];
+        for (name, f) in statics.iter() {
+            let name = self.intern(*name);
+            let f = JsNativeFunction::new(self, name, *f, 1);
+            let _ = constructor.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+
+        let _ = self.global_object().define_own_property(
+            self,
+            name,
+            &*DataDescriptor::new(JsValue::new(constructor), W | C),
+            false,
+        );
+    }
+
+    fn init_math(&mut self, obj_proto: Gc<JsObject>) {
+        let structure = Structure::new_unique_with_proto(self, Some(obj_proto), false);
+        let mut math = JsObject::new(self, structure, JsMath::get_class(), ObjectTag::Ordinary);
+
+        let constants: [(&str, f64); 8] = [
+            ("E", std::f64::consts::E),
+            ("LN10", std::f64::consts::LN_10),
+            ("LN2", std::f64::consts::LN_2),
+            ("LOG10E", std::f64::consts::LOG10_E),
+            ("LOG2E", std::f64::consts::LOG2_E),
+            ("PI", std::f64::consts::PI),
+            ("SQRT1_2", std::f64::consts::FRAC_1_SQRT_2),
+            ("SQRT2", std::f64::consts::SQRT_2),
+        ];
+        for (name, value) in constants.iter() {
+            let name = self.intern(*name);
+            let _ = math.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(*value), NONE),
+                false,
+            );
+        }
+
+        let functions: [(&str, JsAPI, u32); 35] = [
+            ("abs", math_abs, 1),
+            ("acos", math_acos, 1),
+            ("acosh", math_acosh, 1),
+            ("asin", math_asin, 1),
+            ("asinh", math_asinh, 1),
+            ("atan", math_atan, 1),
+            ("atanh", math_atanh, 1),
+            ("atan2", math_atan2, 2),
+            ("cbrt", math_cbrt, 1),
+            ("ceil", math_ceil, 1),
+            ("clz32", math_clz32, 1),
+            ("cos", math_cos, 1),
+            ("cosh", math_cosh, 1),
+            ("exp", math_exp, 1),
+            ("expm1", math_expm1, 1),
+            ("floor", math_floor, 1),
+            ("fround", math_fround, 1),
+            ("hypot", math_hypot, 2),
+            ("imul", math_imul, 2),
+            ("log", math_log, 1),
+            ("log1p", math_log1p, 1),
+            ("log10", math_log10, 1),
+            ("log2", math_log2, 1),
+            ("max", math_max, 2),
+            ("min", math_min, 2),
+            ("pow", math_pow, 2),
+            ("random", math_random, 0),
+            ("round", math_round, 1),
+            ("sign", math_sign, 1),
+            ("sin", math_sin, 1),
+            ("sinh", math_sinh, 1),
+            ("sqrt", math_sqrt, 1),
+            ("tan", math_tan, 1),
+            ("tanh", math_tanh, 1),
+            ("trunc", math_trunc, 1),
+        ];
+        for (name, f, argc) in functions.iter() {
+            let name = self.intern(*name);
+            let f = JsNativeFunction::new(self, name, *f, *argc);
+            let _ = math.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+
+        let name = self.intern("Math");
+        let _ = self.global_object().define_own_property(
+            self,
+            name,
+            &*DataDescriptor::new(JsValue::new(math), W | C),
+            false,
+        );
+    }
+
+    fn init_json(&mut self, obj_proto: Gc<JsObject>) {
+        let structure = Structure::new_unique_with_proto(self, Some(obj_proto), false);
+        let mut json = JsObject::new(self, structure, JsJSON::get_class(), ObjectTag::Ordinary);
+
+        let functions: [(&str, JsAPI, u32); 2] =
+            [("parse", json_parse, 2), ("stringify", json_stringify, 3)];
+        for (name, f, argc) in functions.iter() {
+            let name = self.intern(*name);
+            let f = JsNativeFunction::new(self, name, *f, *argc);
+            let _ = json.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
+
+        let name = self.intern("JSON");
+        let _ = self.global_object().define_own_property(
+            self,
+            name,
+            &*DataDescriptor::new(JsValue::new(json), W | C),
+            false,
+        );
+    }
+
     fn init_array(&mut self, obj_proto: Gc<JsObject>) {
         let structure = Structure::new_indexed(self, None, true);
         self.global_data.array_structure = Some(structure);
@@ -626,7 +1462,7 @@ impl VirtualMachine {
         let _ = proto.define_own_property(
             self,
             name,
-            &*DataDescriptor::new(JsValue::new(join), W | C | E),
+            &*DataDescriptor::new(JsValue::new(join), W | C),
             false,
         );
 
@@ -635,7 +1471,7 @@ impl VirtualMachine {
         let _ = proto.define_own_property(
             self,
             name,
-            &*DataDescriptor::new(JsValue::new(to_string), W | C | E),
+            &*DataDescriptor::new(JsValue::new(to_string), W | C),
             false,
         );
 
@@ -644,7 +1480,7 @@ impl VirtualMachine {
         let _ = proto.define_own_property(
             self,
             name,
-            &*DataDescriptor::new(JsValue::new(push), W | C | E),
+            &*DataDescriptor::new(JsValue::new(push), W | C),
             false,
         );
         let name = self.intern_or_known_symbol("pop");
@@ -652,9 +1488,46 @@ impl VirtualMachine {
         let _ = proto.define_own_property(
             self,
             name,
-            &*DataDescriptor::new(JsValue::new(pop), W | C | E),
+            &*DataDescriptor::new(JsValue::new(pop), W | C),
             false,
         );
+
+        let methods: [(&str, JsAPI, u32); 24] = [
+            ("at", array_at, 1),
+            ("concat", array_concat, 1),
+            ("copyWithin", array_copy_within, 2),
+            ("every", array_every, 1),
+            ("fill", array_fill, 1),
+            ("filter", array_filter, 1),
+            ("find", array_find, 1),
+            ("findIndex", array_find_index, 1),
+            ("flat", array_flat, 0),
+            ("flatMap", array_flat_map, 1),
+            ("forEach", array_for_each, 1),
+            ("includes", array_includes, 1),
+            ("indexOf", array_index_of, 1),
+            ("lastIndexOf", array_last_index_of, 1),
+            ("map", array_map, 1),
+            ("reduce", array_reduce, 1),
+            ("reduceRight", array_reduce_right, 1),
+            ("reverse", array_reverse, 0),
+            ("shift", array_shift, 0),
+            ("slice", array_slice, 2),
+            ("some", array_some, 1),
+            ("sort", array_sort, 1),
+            ("splice", array_splice, 2),
+            ("unshift", array_unshift, 1),
+        ];
+        for (name, f, argc) in methods.iter() {
+            let name = self.intern_or_known_symbol(name);
+            let f = JsNativeFunction::new(self, name, *f, *argc);
+            let _ = proto.define_own_property(
+                self,
+                name,
+                &*DataDescriptor::new(JsValue::new(f), W | C),
+                false,
+            );
+        }
         self.global_data.array_prototype = Some(proto);
         let arr = self.intern("Array");
         let _ = self.global_object().define_own_property(
@@ -671,6 +1544,9 @@ impl VirtualMachine {
         self.global_data.reference_error_structure =
             Some(Structure::new_indexed(self, None, false));
         self.global_data.type_error_structure = Some(Structure::new_indexed(self, None, false));
+        self.global_data.syntax_error_structure = Some(Structure::new_indexed(self, None, false));
+        self.global_data.aggregate_error_structure =
+            Some(Structure::new_indexed(self, None, false));
         let structure = Structure::new_unique_with_proto(self, Some(obj_proto), false);
         let mut proto = JsObject::new(self, structure, JsError::get_class(), ObjectTag::Ordinary);
         let e = self.intern("Error");
@@ -849,6 +1725,68 @@ impl VirtualMachine {
             self.global_data.type_error = Some(sub_proto);
         }
 
+        // syntax error
+        {
+            let structure = Structure::new_unique_with_proto(self, Some(proto), false);
+            let mut sub_proto = JsObject::new(
+                self,
+                structure,
+                JsSyntaxError::get_class(),
+                ObjectTag::Ordinary,
+            );
+
+            self.global_data
+                .syntax_error_structure
+                .unwrap()
+                .change_prototype_with_no_transition(sub_proto);
+            let sym = self.intern("SyntaxError");
+            let mut sub_ctor = JsNativeFunction::new(self, sym, syntax_error_constructor, 1);
+            let _ = sub_ctor.define_own_property(
+                self,
+                Symbol::prototype(),
+                &*DataDescriptor::new(JsValue::new(sub_proto), NONE),
+                false,
+            );
+            let _ = sub_proto.define_own_property(
+                self,
+                Symbol::constructor(),
+                &*DataDescriptor::new(JsValue::new(sub_ctor), W | C),
+                false,
+            );
+
+            let n = Symbol::name();
+            let s = JsString::new(self, "SyntaxError");
+            let e = JsString::new(self, "");
+            let m = Symbol::message();
+            let _ = sub_proto.define_own_property(
+                self,
+                n,
+                &*DataDescriptor::new(JsValue::new(s), W | C),
+                false,
+            );
+            let _ = sub_proto.define_own_property(
+                self,
+                m,
+                &*DataDescriptor::new(JsValue::new(e), W | C),
+                false,
+            );
+            let to_str = JsNativeFunction::new(self, Symbol::toString(), error_to_string, 0);
+            let _ = sub_proto.define_own_property(
+                self,
+                Symbol::toString(),
+                &*DataDescriptor::new(JsValue::new(to_str), W | C),
+                false,
+            );
+            let _ = self.global_object().define_own_property(
+                self,
+                sym,
+                &*DataDescriptor::new(JsValue::new(sub_ctor), W | C),
+                false,
+            );
+
+            self.global_data.syntax_error = Some(sub_proto);
+        }
+
         {
             let structure = Structure::new_unique_with_proto(self, Some(proto), false);
             let mut sub_proto = JsObject::new(
@@ -969,12 +1907,76 @@ impl VirtualMachine {
             let _ = self.global_object().define_own_property(
                 self,
                 sym,
-                &*DataDescriptor::new(JsValue::new(sub_proto), W | C),
+                &*DataDescriptor::new(JsValue::new(sub_ctor), W | C),
                 false,
             );
 
             self.global_data.range_error = Some(sub_proto);
         }
+
+        // aggregate error
+        {
+            let structure = Structure::new_unique_with_proto(self, Some(proto), false);
+            let mut sub_proto = JsObject::new(
+                self,
+                structure,
+                JsAggregateError::get_class(),
+                ObjectTag::Ordinary,
+            );
+
+            self.global_data
+                .aggregate_error_structure
+                .unwrap()
+                .change_prototype_with_no_transition(sub_proto);
+            let sym = self.intern("AggregateError");
+            let mut sub_ctor = JsNativeFunction::new(self, sym, aggregate_error_constructor, 2);
+            let _ = sub_ctor.define_own_property(
+                self,
+                Symbol::prototype(),
+                &*DataDescriptor::new(JsValue::new(sub_proto), NONE),
+                false,
+            );
+            let _ = sub_proto.define_own_property(
+                self,
+                Symbol::constructor(),
+                &*DataDescriptor::new(JsValue::new(sub_ctor), W | C),
+                false,
+            );
+
+            let n = Symbol::name();
+            let s = JsString::new(self, "AggregateError");
+            let e = JsString::new(self, "");
+            let m = Symbol::message();
+            let _ = sub_proto.define_own_property(
+                self,
+                n,
+                &*DataDescriptor::new(JsValue::new(s), W | C),
+                false,
+            );
+
+            let _ = sub_proto.define_own_property(
+                self,
+                m,
+                &*DataDescriptor::new(JsValue::new(e), W | C),
+                false,
+            );
+            let to_str = JsNativeFunction::new(self, Symbol::toString(), error_to_string, 0);
+            let _ = sub_proto.define_own_property(
+                self,
+                Symbol::toString(),
+                &*DataDescriptor::new(JsValue::new(to_str), W | C),
+                false,
+            );
+
+            let _ = self.global_object().define_own_property(
+                self,
+                sym,
+                &*DataDescriptor::new(JsValue::new(sub_ctor), W | C),
+                false,
+            );
+
+            self.global_data.aggregate_error = Some(sub_proto);
+        }
     }
 }
 
@@ -1004,17 +2006,35 @@ pub struct GlobalData {
     pub(crate) syntax_error: Option<Gc<JsObject>>,
     pub(crate) internal_error: Option<Gc<JsObject>>,
     pub(crate) eval_error: Option<Gc<JsObject>>,
+    pub(crate) aggregate_error: Option<Gc<JsObject>>,
     pub(crate) array_prototype: Option<Gc<JsObject>>,
     pub(crate) func_prototype: Option<Gc<JsObject>>,
+    pub(crate) iterator_prototype: Option<Gc<JsObject>>,
+    pub(crate) array_iterator_prototype: Option<Gc<JsObject>>,
+    pub(crate) string_iterator_prototype: Option<Gc<JsObject>>,
+    /// %GeneratorPrototype%, the default prototype of generator objects.
+    pub(crate) generator_prototype: Option<Gc<JsObject>>,
+    pub(crate) promise_prototype: Option<Gc<JsObject>>,
+    /// %Promise%, used when a promise has to be created by the runtime.
+    pub(crate) promise_constructor: Option<Gc<JsObject>>,
+    /// %Array.prototype.values%, also used as `@@iterator` of arguments objects.
+    pub(crate) array_values: Option<Gc<JsObject>>,
     pub(crate) string_structure: Option<Gc<Structure>>,
     pub(crate) number_structure: Option<Gc<Structure>>,
+    pub(crate) boolean_structure: Option<Gc<Structure>>,
+    pub(crate) symbol_structure: Option<Gc<Structure>>,
     pub(crate) array_structure: Option<Gc<Structure>>,
+    pub(crate) array_iterator_structure: Option<Gc<Structure>>,
+    pub(crate) string_iterator_structure: Option<Gc<Structure>>,
+    pub(crate) generator_structure: Option<Gc<Structure>>,
+    pub(crate) promise_structure: Option<Gc<Structure>>,
     pub(crate) error_structure: Option<Gc<Structure>>,
     pub(crate) range_error_structure: Option<Gc<Structure>>,
     pub(crate) reference_error_structure: Option<Gc<Structure>>,
     pub(crate) syntax_error_structure: Option<Gc<Structure>>,
     pub(crate) type_error_structure: Option<Gc<Structure>>,
     pub(crate) uri_error_structure: Option<Gc<Structure>>,
+    pub(crate) aggregate_error_structure: Option<Gc<Structure>>,
     pub(crate) eval_error_structure: Option<Gc<Structure>>,
 }
 
@@ -1043,3 +2063,46 @@ impl AsMut<Heap> for VirtualMachineRef {
         unsafe { &mut *self.space }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jsrt::jsrt_init;
+
+    #[test]
+    fn test_run_microtasks_drains_after_failing_job() {
+        let mut vm = VirtualMachine::new(Options::default());
+        jsrt_init(&mut vm);
+        // the species constructor gives `then` a capability whose resolve function throws,
+        // so the reaction job of the first promise fails.
+        let source = "
+            function Ctor(executor) { executor(function () { throw 'job'; }, function () {}); }
+            Ctor[Symbol.species] = Ctor;
+            var p = Promise.resolve(1);
+            p.constructor = Ctor;
+            p.then(function () {});
+            Promise.resolve(2).then(function (v) { result = v; });";
+        let func = vm
+            .compile(false, source, "<test>")
+            .unwrap_or_else(|_| panic!());
+        let mut func = func.root(vm.space());
+        let args = Arguments::new(&mut vm, JsValue::undefined(), 0);
+        let mut args = Handle::new(vm.space(), args);
+        args.this = JsValue::new(vm.global_object());
+        assert!(func.as_function_mut().call(&mut vm, &mut args).is_ok());
+
+        let error = vm.run_microtasks().unwrap_err();
+        let error = Handle::new(vm.space(), error);
+        assert_eq!(error.to_string(&mut vm).ok(), Some("job".to_string()));
+        let name = vm.intern("result");
+        let result = vm
+            .global_object()
+            .get(&mut vm, name)
+            .unwrap_or_else(|_| panic!());
+        assert_eq!(result.to_number(&mut vm).ok(), Some(2.0));
+        drop(error);
+        drop(args);
+        drop(func);
+        VirtualMachineRef::dispose(vm);
+    }
+}
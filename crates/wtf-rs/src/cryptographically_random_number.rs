@@ -1,4 +1,4 @@
-use core::{cell::UnsafeCell, mem::MaybeUninit};
+use core::cell::UnsafeCell;
 use once_cell::sync::Lazy;
 use parking_lot::{lock_api::RawMutex, RawMutex as Lock};
 
@@ -12,7 +12,7 @@ struct ARC4Stream {
 }
 
 impl ARC4Stream {
-    #[allow(clippy::clippy::needless_range_loop)]
+    #[allow(clippy::needless_range_loop)]
     fn new() -> Self {
         let mut slice = [0; 256];
         for n in 0..256 {
@@ -62,7 +62,7 @@ impl ARC4RandomNumberGenerator {
         let sj = self.stream.s[self.stream.j as usize];
         self.stream.s[self.stream.i as usize] = sj;
         self.stream.s[self.stream.j as usize] = si;
-        self.stream.s[(si.wrapping_add(sj) as usize)]
+        self.stream.s[si.wrapping_add(sj) as usize]
     }
 
     fn get_word(&mut self) -> u32 {
@@ -73,19 +73,16 @@ impl ARC4RandomNumberGenerator {
         val |= self.get_byte() as u32;
         val
     }
-    #[allow(clippy::uninit_assumed_init)]
     fn stir(&mut self) {
-        unsafe {
-            let mut randomness: [u8; 128] = MaybeUninit::uninit().assume_init();
-            cryptographically_random_number_from_os(&mut randomness);
-            self.add_random_data(&randomness);
-            // Discard early keystream, as per recommendations in:
-            // http://www.wisdom.weizmann.ac.il/~itsik/RC4/Papers/Rc4_ksa.ps
-            for _ in 0..256 {
-                self.get_byte();
-            }
-            self.count = 1600000;
+        let mut randomness = [0u8; 128];
+        cryptographically_random_number_from_os(&mut randomness);
+        self.add_random_data(&randomness);
+        // Discard early keystream, as per recommendations in:
+        // http://www.wisdom.weizmann.ac.il/~itsik/RC4/Papers/Rc4_ksa.ps
+        for _ in 0..256 {
+            self.get_byte();
         }
+        self.count = 1600000;
     }
 
     pub fn random_number(&mut self) -> u32 {
@@ -126,7 +123,7 @@ unsafe impl Sync for Handle {}
 pub fn shared_random_number_generator() -> &'static mut ARC4RandomNumberGenerator {
     static GEN: Lazy<Handle> =
         Lazy::new(|| Handle(UnsafeCell::new(ARC4RandomNumberGenerator::new())));
-    unsafe { &mut *(*GEN).0.get() }
+    unsafe { &mut *GEN.0.get() }
 }
 
 pub fn cryptographically_random_number() -> u32 {
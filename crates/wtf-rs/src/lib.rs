@@ -1,7 +1,4 @@
 #![allow(
-    clippy::transmute_float_to_int,
-    clippy::transmute_int_to_float,
-    clippy::cast_ref_to_mut,
     clippy::float_cmp,
     clippy::float_arithmetic,
     clippy::identity_op,
@@ -74,9 +71,8 @@ macro_rules! _memoffset__raw_const {
 #[doc(hidden)]
 macro_rules! _memoffset__raw_const {
     ($path:expr) => {{
-        // This is UB because we create an intermediate reference to uninitialized memory.
-        // Nothing we can do about that without `raw_const!` though.
-        &$path as *const _
+        // `addr_of!` avoids an intermediate reference to uninitialized or unaligned memory.
+        core::ptr::addr_of!($path)
     }};
 }
 
@@ -140,7 +136,6 @@ macro_rules! raw_field {
 ///
 /// The `base` pointer *must not* be dangling, but it *may* point to
 /// uninitialized memory.
-
 #[macro_export(local_inner_macros)]
 macro_rules! raw_field_tuple {
     ($base:expr, $parent:ty, $field:tt) => {{
@@ -208,7 +203,6 @@ macro_rules! object_offsetof {
 ///     assert!(offset_of_tuple!((u8, u32), 1) >= 0, "Tuples do not have a defined layout");
 /// }
 /// ```
-
 #[macro_export(local_inner_macros)]
 macro_rules! offset_of_tuple {
     ($parent:ty, $field:tt) => {{
@@ -331,7 +325,7 @@ static mut SINK: usize = 0;
 /// to global variable so compiler does not optimize `value` out.
 pub fn keep_on_stack_noop(value: usize) {
     unsafe {
-        core::ptr::write_volatile(&mut SINK, value);
+        core::ptr::write_volatile(core::ptr::addr_of_mut!(SINK), value);
         core::sync::atomic::fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
@@ -365,3 +359,9 @@ pub unsafe fn copy_backwards<T>(first: *const T, mut last: *const T, mut d_last:
     }
     d_last
 }
+
+// Declared after the macros above, `weak_random` uses `object_offsetof!`.
+pub mod cryptographically_random_number;
+pub mod random_device;
+pub mod random_number;
+pub mod weak_random;
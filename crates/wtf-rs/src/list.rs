@@ -622,7 +622,7 @@ impl<T> LinkedList<T> {
             mem::swap(&mut self.allocations, &mut other.allocations);
         }
         // self.allocations is now the longest array
-        self.allocations.extend(other.allocations.drain(..));
+        self.allocations.append(&mut other.allocations);
 
         // move unused capacity to self, since self now owns the memory
         self.capacity += other.capacity;
@@ -680,7 +680,7 @@ impl<T> LinkedList<T> {
     /// assert_eq!(None, iter.next());
     /// ```
     #[inline]
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             head: self.head,
             tail: self.tail,
@@ -712,7 +712,7 @@ impl<T> LinkedList<T> {
     /// assert_eq!(None, iter.next());
     /// ```
     #[inline]
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
             head: self.head,
             tail: self.tail,
@@ -741,7 +741,7 @@ impl<T> LinkedList<T> {
     /// # else { unreachable!(); }
     /// ```
     #[inline]
-    pub fn cursor_ref_back(&self) -> Option<CursorRef<T>> {
+    pub fn cursor_ref_back(&self) -> Option<CursorRef<'_, T>> {
         if self.tail.is_null() {
             None
         } else {
@@ -769,7 +769,7 @@ impl<T> LinkedList<T> {
     /// # else { unreachable!(); }
     /// ```
     #[inline]
-    pub fn cursor_ref_front(&self) -> Option<CursorRef<T>> {
+    pub fn cursor_ref_front(&self) -> Option<CursorRef<'_, T>> {
         if self.head.is_null() {
             None
         } else {
@@ -777,7 +777,7 @@ impl<T> LinkedList<T> {
         }
     }
 
-    pub fn cursor_mut_back(&mut self) -> Option<CursorMut<T>> {
+    pub fn cursor_mut_back(&mut self) -> Option<CursorMut<'_, T>> {
         if self.tail.is_null() {
             None
         } else {
@@ -786,7 +786,7 @@ impl<T> LinkedList<T> {
             Some(CursorMut::create(self, tail, len - 1))
         }
     }
-    pub fn cursor_mut_front(&mut self) -> Option<CursorMut<T>> {
+    pub fn cursor_mut_front(&mut self) -> Option<CursorMut<'_, T>> {
         if self.head.is_null() {
             None
         } else {
@@ -1362,7 +1362,7 @@ mod tests {
         let nums: Vec<usize> = (0..16).filter(|&i| mask[i]).map(|i| i + 1).collect();
 
         println!("{:?}", mask);
-        for (a, b) in list.into_iter().zip(nums.into_iter()) {
+        for (a, b) in list.into_iter().zip(nums) {
             assert_eq!(a, b);
         }
     }
@@ -1467,6 +1467,7 @@ mod tests {
             let mut iter_mut = list.iter_mut();
             let ref1 = iter_mut.next().unwrap();
             let ref2 = iter_mut.next().unwrap();
+            #[allow(clippy::drop_non_drop)]
             drop(iter_mut);
             *ref1 = 6;
             *ref2 = 7;
@@ -1680,11 +1681,7 @@ pub mod cursor {
     }
     impl<'a, T> Clone for CursorRef<'a, T> {
         fn clone(&self) -> Self {
-            CursorRef {
-                cursor: self.cursor,
-                index: self.index,
-                marker: PhantomData,
-            }
+            *self
         }
     }
     impl<'a, T> Copy for CursorRef<'a, T> {}
@@ -2451,12 +2448,7 @@ pub mod iter {
     }
     impl<'a, T> Clone for Iter<'a, T> {
         fn clone(&self) -> Self {
-            Iter {
-                head: self.head,
-                tail: self.tail,
-                len: self.len,
-                marker: self.marker,
-            }
+            *self
         }
     }
     impl<'a, T> Copy for Iter<'a, T> {}
@@ -2621,7 +2613,7 @@ pub mod iter {
         fn drop(&mut self) {
             unsafe {
                 // drop remaining elements
-                while let Some(_) = self.next() {}
+                for _ in self.by_ref() {}
 
                 // deallocate memory
                 for &(vecptr, capacity) in &self.allocations {
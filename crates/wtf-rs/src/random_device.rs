@@ -83,7 +83,7 @@ impl RandomDevice {
             CryptReleaseContext(prov, 0);
         }
     }
-    #[allow(clippy::clippy::not_unsafe_ptr_arg_deref)]
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
     pub fn cryptographically_random_values(&self, buffer: *mut u8, length: usize) {
         unsafe {
             self.internal_ranom_values(buffer, length);
@@ -9,5 +9,5 @@ pub fn random_number() -> f64 {
 
 pub fn weak_random_u32() -> u32 {
     static mut WEAK_RANDOM: WeakRandom = WeakRandom::const_new();
-    unsafe { WEAK_RANDOM.get_u32() }
+    unsafe { (*core::ptr::addr_of_mut!(WEAK_RANDOM)).get_u32() }
 }
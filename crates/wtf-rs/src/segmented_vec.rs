@@ -3,7 +3,6 @@ use core::ops::{Index, IndexMut};
 use core::slice::Iter as SliceIter;
 use core::slice::IterMut as SliceIterMut;
 
-use super::RetainMut;
 pub struct SegmentedVec<T> {
     chunks: Vec<Vec<T>>,
     chunk_size: usize,
@@ -20,7 +20,7 @@ impl WeakRandom {
         let y = self.high;
         self.low = y;
         self.high = Self::next_state(x, y);
-        self.high + self.low
+        self.high.wrapping_add(self.low)
     }
 
     pub fn generate(mut seed: u32) -> u64 {
@@ -30,7 +30,7 @@ impl WeakRandom {
         let low = seed as u64;
         let mut high = seed as u64;
         high = Self::next_state(low, high);
-        low + high
+        low.wrapping_add(high)
     }
 
     pub fn low_offset() -> usize {
@@ -14,20 +14,14 @@ fn main() {
     let _ = std::fs::remove_file("test262_result");
     let _ = std::fs::remove_file("test262_passed");
     std::panic::set_hook(Box::new(|descr| unsafe {
-        match RUNNING.take() {
-            Some(running) => {
-                let mut file = OpenOptions::new()
-                    .write(true)
-                    .append(true)
-                    .create(true)
-                    .open("test262_result")
-                    .unwrap();
-                file.write_all(
-                    &format!("Test failed: {}, panic info: {}\n", running, descr).as_bytes(),
-                )
+        if let Some(running) = (*std::ptr::addr_of_mut!(RUNNING)).take() {
+            let mut file = OpenOptions::new()
+                .append(true)
+                .create(true)
+                .open("test262_result")
+                .unwrap();
+            file.write_all(format!("Test failed: {}, panic info: {}\n", running, descr).as_bytes())
                 .unwrap();
-            }
-            _ => (),
         }
     }));
     let test262_path = "test262";
@@ -37,7 +31,6 @@ fn main() {
     let mut failed = 0;
     let _ = std::panic::catch_unwind(AssertUnwindSafe(|| {
         let mut passedf = OpenOptions::new()
-            .write(true)
             .append(true)
             .create(true)
             .open("test262_passed")
@@ -78,7 +71,7 @@ fn main() {
                     let mut code = String::new();
 
                     for include in test.desc.includes.iter() {
-                        match std::fs::read(&format!("test262_harness/{}", include)) {
+                        match std::fs::read(format!("test262_harness/{}", include)) {
                             Ok(contents) => {
                                 code.push_str(&String::from_utf8(contents).unwrap());
                             }
@@ -105,7 +98,7 @@ fn main() {
                                         passed += 1;
                                         passedf
                                             .write_all(
-                                                &format!("Passed {}\n", test.path.display())
+                                                format!("Passed {}\n", test.path.display())
                                                     .as_bytes(),
                                             )
                                             .unwrap();
@@ -126,13 +119,13 @@ fn main() {
                     let panic_ = std::panic::catch_unwind(AssertUnwindSafe(|| match fun {
                         Ok(Some(mut val)) => {
                             let args = Arguments::new(&mut vm, JsValue::undefined(), 0);
-                            let mut args = Handle::new(&mut vm.space(), args);
+                            let mut args = Handle::new(vm.space(), args);
                             match val.as_function_mut().call(&mut vm, &mut args) {
                                 Ok(_) => {
                                     passed += 1;
                                     passedf
                                         .write_all(
-                                            &format!("Passed {}\n", test.path.display()).as_bytes(),
+                                            format!("Passed {}\n", test.path.display()).as_bytes(),
                                         )
                                         .unwrap();
                                 }
@@ -142,7 +135,7 @@ fn main() {
                                             passed += 1;
                                             passedf
                                                 .write_all(
-                                                    &format!("Passed {}\n", test.path.display())
+                                                    format!("Passed {}\n", test.path.display())
                                                         .as_bytes(),
                                                 )
                                                 .unwrap();
@@ -166,7 +159,7 @@ fn main() {
                                     passed += 1;
                                     passedf
                                         .write_all(
-                                            &format!("Passed {}\n", test.path.display()).as_bytes(),
+                                            format!("Passed {}\n", test.path.display()).as_bytes(),
                                         )
                                         .unwrap();
                                 }